@@ -189,6 +189,7 @@ fn default_page_info() -> PageInfo<'static> {
         score: ScoreValue::Integer(0),
         tags: vec![],
         language: cow!("default"),
+        render_timestamp: None,
     }
 }
 
@@ -73,7 +73,7 @@ fn main() {
             }
         };
 
-        let outcome = Outcome::Success(syntax_tree);
+        let outcome = Outcome::Success(Box::new(syntax_tree));
         results.push((path, outcome));
     }
 
@@ -109,7 +109,7 @@ fn write_result(
 
 #[derive(Debug)]
 enum Outcome {
-    Success(ftml::tree::SyntaxTree<'static>),
+    Success(Box<ftml::tree::SyntaxTree<'static>>),
     Failure(String),
     Error(String),
 }
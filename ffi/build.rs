@@ -0,0 +1,19 @@
+use std::env;
+use std::path::Path;
+
+fn main() {
+    // Generate the C header from the exported functions
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let crate_directory =
+        env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let config =
+        cbindgen::Config::from_file(Path::new(&crate_directory).join("cbindgen.toml"))
+            .expect("Unable to read cbindgen configuration");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    cbindgen::generate_with_config(&crate_directory, config)
+        .expect("Unable to generate C header")
+        .write_to_file(Path::new(&out_dir).join("ftml.h"));
+}
@@ -0,0 +1,180 @@
+/*
+ * ftml.h
+ *
+ * C bindings for ftml, a library to parse Wikidot text.
+ * This file is generated by ffi/build.rs, do not edit it manually.
+ */
+
+#ifndef FTML_H
+#define FTML_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+/**
+ * Opaque handle to information about the page being rendered.
+ */
+typedef struct ftml_page_info ftml_page_info;
+
+/**
+ * Opaque handle to the result of parsing, a syntax tree and any warnings.
+ */
+typedef struct ftml_parse_outcome ftml_parse_outcome;
+
+/**
+ * Opaque handle to the settings used for parsing and rendering.
+ */
+typedef struct ftml_settings ftml_settings;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Returns a description of the last error on this thread, or `NULL` if there was none.
+ *
+ * The returned string is owned by the library, and is only
+ * valid until the next ftml function is called on this thread.
+ */
+const char *ftml_last_error(void);
+
+/**
+ * Creates settings with the defaults for a mode and layout.
+ *
+ * The mode is one of `page`, `page-nav`, `draft`, `forum-post`,
+ * `direct-message` or `list`, and the layout is one of `wikidot`
+ * or `wikijump`. Returns `NULL` if either is invalid.
+ *
+ * # Safety
+ * `mode` and `layout` must be valid null-terminated strings.
+ */
+ftml_settings *ftml_settings_new(const char *mode, const char *layout);
+
+/**
+ * Creates settings from their JSON representation. Returns `NULL` if invalid.
+ *
+ * # Safety
+ * `json` must be a valid null-terminated string.
+ */
+ftml_settings *ftml_settings_from_json(const char *json);
+
+/**
+ * Frees a settings handle. Passing `NULL` does nothing.
+ *
+ * # Safety
+ * `settings` must be `NULL` or a handle which has not yet been freed.
+ */
+void ftml_settings_free(ftml_settings *settings);
+
+/**
+ * Creates page information from its JSON representation. Returns `NULL` if invalid.
+ *
+ * # Safety
+ * `json` must be a valid null-terminated string.
+ */
+ftml_page_info *ftml_page_info_from_json(const char *json);
+
+/**
+ * Frees a page information handle. Passing `NULL` does nothing.
+ *
+ * # Safety
+ * `page_info` must be `NULL` or a handle which has not yet been freed.
+ */
+void ftml_page_info_free(ftml_page_info *page_info);
+
+/**
+ * Tokenizes and parses the given wikitext.
+ *
+ * The text should already have been preprocessed with `ftml_preprocess()`.
+ * Parsing itself never fails, this only returns `NULL` for invalid arguments.
+ *
+ * # Safety
+ * `text` must be a valid null-terminated string, and
+ * `page_info` and `settings` must be live handles.
+ */
+ftml_parse_outcome *ftml_parse(const char *text, const ftml_page_info *page_info, const ftml_settings *settings);
+
+/**
+ * Returns the number of warnings produced while parsing.
+ *
+ * # Safety
+ * `outcome` must be `NULL` or a live handle.
+ */
+size_t ftml_parse_outcome_error_count(const ftml_parse_outcome *outcome);
+
+/**
+ * Returns the warnings produced while parsing, as a JSON array.
+ *
+ * # Safety
+ * `outcome` must be a live handle.
+ */
+char *ftml_parse_outcome_errors_json(const ftml_parse_outcome *outcome);
+
+/**
+ * Returns the syntax tree, as a JSON object.
+ *
+ * # Safety
+ * `outcome` must be a live handle.
+ */
+char *ftml_parse_outcome_tree_json(const ftml_parse_outcome *outcome);
+
+/**
+ * Frees a parse outcome handle. Passing `NULL` does nothing.
+ *
+ * # Safety
+ * `outcome` must be `NULL` or a handle which has not yet been freed.
+ */
+void ftml_parse_outcome_free(ftml_parse_outcome *outcome);
+
+/**
+ * Renders a parsed syntax tree as HTML, returning only the body.
+ *
+ * # Safety
+ * `outcome`, `page_info` and `settings` must be live handles.
+ */
+char *ftml_render_html(const ftml_parse_outcome *outcome, const ftml_page_info *page_info, const ftml_settings *settings);
+
+/**
+ * Renders a parsed syntax tree as HTML, returning the full output as a JSON object.
+ *
+ * This includes the body, as well as metadata such as backlinks and code blocks.
+ *
+ * # Safety
+ * `outcome`, `page_info` and `settings` must be live handles.
+ */
+char *ftml_render_html_json(const ftml_parse_outcome *outcome, const ftml_page_info *page_info, const ftml_settings *settings);
+
+/**
+ * Renders a parsed syntax tree as plain text.
+ *
+ * # Safety
+ * `outcome`, `page_info` and `settings` must be live handles.
+ */
+char *ftml_render_text(const ftml_parse_outcome *outcome, const ftml_page_info *page_info, const ftml_settings *settings);
+
+/**
+ * Returns the version of ftml, as a static string.
+ */
+const char *ftml_version(void);
+
+/**
+ * Runs the preprocessor on the given wikitext, returning a new string.
+ *
+ * # Safety
+ * `text` must be a valid null-terminated string.
+ */
+char *ftml_preprocess(const char *text);
+
+/**
+ * Frees a string returned by any ftml function. Passing `NULL` does nothing.
+ *
+ * # Safety
+ * `string` must be `NULL` or a string returned by ftml which has not yet been freed.
+ */
+void ftml_string_free(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* FTML_H */
@@ -0,0 +1,71 @@
+/*
+ * ffi/src/error.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::cell::RefCell;
+use std::ffi::{CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Records an error message, to be retrieved with `ftml_last_error()`.
+pub(crate) fn set_error<S: Into<String>>(message: S) {
+    let message = message.into().replace('\0', " ");
+    let message = CString::new(message).expect("Null bytes were removed");
+    LAST_ERROR.with(|error| *error.borrow_mut() = Some(message));
+}
+
+fn clear_error() {
+    LAST_ERROR.with(|error| *error.borrow_mut() = None);
+}
+
+/// Runs an FFI function body, converting a failure or panic into `NULL`.
+///
+/// Panics must not unwind across the C ABI, so they are caught here
+/// and reported as an error instead.
+pub(crate) fn guard<T, F>(f: F) -> *mut T
+where
+    F: FnOnce() -> Option<*mut T>,
+{
+    clear_error();
+
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Some(pointer)) => pointer,
+        Ok(None) => ptr::null_mut(),
+        Err(_) => {
+            set_error("internal error: ftml panicked");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Returns a description of the last error on this thread, or `NULL` if there was none.
+///
+/// The returned string is owned by the library, and is only
+/// valid until the next ftml function is called on this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn ftml_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| match &*error.borrow() {
+        Some(message) => message.as_ptr(),
+        None => ptr::null(),
+    })
+}
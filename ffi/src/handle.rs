@@ -0,0 +1,145 @@
+/*
+ * ffi/src/handle.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::error::{guard, set_error};
+use crate::string::read_str;
+use ftml::data::PageInfo;
+use ftml::layout::Layout;
+use ftml::settings::{WikitextMode, WikitextSettings};
+use std::ffi::c_char;
+
+/// Opaque handle to the settings used for parsing and rendering.
+#[derive(Debug)]
+pub struct FtmlSettings {
+    pub(crate) inner: WikitextSettings,
+}
+
+/// Opaque handle to information about the page being rendered.
+#[derive(Debug)]
+pub struct FtmlPageInfo {
+    pub(crate) inner: PageInfo<'static>,
+}
+
+/// Creates settings with the defaults for a mode and layout.
+///
+/// The mode is one of `page`, `page-nav`, `draft`, `forum-post`,
+/// `direct-message` or `list`, and the layout is one of `wikidot`
+/// or `wikijump`. Returns `NULL` if either is invalid.
+///
+/// # Safety
+/// `mode` and `layout` must be valid null-terminated strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_settings_new(
+    mode: *const c_char,
+    layout: *const c_char,
+) -> *mut FtmlSettings {
+    guard(|| {
+        let mode = unsafe { read_str(mode, "mode") }?;
+        let layout = unsafe { read_str(layout, "layout") }?;
+
+        let Ok(mode) = mode.parse::<WikitextMode>() else {
+            set_error(format!("invalid wikitext mode: {mode}"));
+            return None;
+        };
+
+        let Ok(layout) = layout.parse::<Layout>() else {
+            set_error(format!("invalid layout: {layout}"));
+            return None;
+        };
+
+        let inner = WikitextSettings::from_mode(mode, layout);
+        Some(Box::into_raw(Box::new(FtmlSettings { inner })))
+    })
+}
+
+/// Creates settings from their JSON representation. Returns `NULL` if invalid.
+///
+/// # Safety
+/// `json` must be a valid null-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_settings_from_json(
+    json: *const c_char,
+) -> *mut FtmlSettings {
+    guard(|| {
+        let json = unsafe { read_str(json, "json") }?;
+        match serde_json::from_str(json) {
+            Ok(inner) => Some(Box::into_raw(Box::new(FtmlSettings { inner }))),
+            Err(error) => {
+                set_error(format!("invalid settings JSON: {error}"));
+                None
+            }
+        }
+    })
+}
+
+/// Frees a settings handle. Passing `NULL` does nothing.
+///
+/// # Safety
+/// `settings` must be `NULL` or a handle which has not yet been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_settings_free(settings: *mut FtmlSettings) {
+    if !settings.is_null() {
+        drop(unsafe { Box::from_raw(settings) });
+    }
+}
+
+/// Creates page information from its JSON representation. Returns `NULL` if invalid.
+///
+/// # Safety
+/// `json` must be a valid null-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_page_info_from_json(
+    json: *const c_char,
+) -> *mut FtmlPageInfo {
+    guard(|| {
+        let json = unsafe { read_str(json, "json") }?;
+        match serde_json::from_str(json) {
+            Ok(inner) => Some(Box::into_raw(Box::new(FtmlPageInfo { inner }))),
+            Err(error) => {
+                set_error(format!("invalid page info JSON: {error}"));
+                None
+            }
+        }
+    })
+}
+
+/// Frees a page information handle. Passing `NULL` does nothing.
+///
+/// # Safety
+/// `page_info` must be `NULL` or a handle which has not yet been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_page_info_free(page_info: *mut FtmlPageInfo) {
+    if !page_info.is_null() {
+        drop(unsafe { Box::from_raw(page_info) });
+    }
+}
+
+/// Borrows the contents of a handle, recording an error if it is `NULL`.
+///
+/// # Safety
+/// The pointer must be `NULL` or a live handle which outlives the returned reference.
+pub(crate) unsafe fn read_handle<'a, T>(pointer: *const T, name: &str) -> Option<&'a T> {
+    let handle = unsafe { pointer.as_ref() };
+    if handle.is_none() {
+        set_error(format!("argument '{name}' is null"));
+    }
+
+    handle
+}
@@ -0,0 +1,52 @@
+/*
+ * ffi/src/lib.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! C bindings for ftml.
+//!
+//! This crate exposes the wikitext pipeline over the C ABI for embedding in
+//! services not written in Rust. It is separate from the main crate because
+//! that forbids unsafe code entirely.
+//!
+//! The API uses opaque handles for settings, page information, and parse
+//! outcomes, each created by a constructor and released with the matching
+//! `*_free()` function. Strings returned by the library are owned by the caller
+//! and must be released with `ftml_string_free()`. Strings passed in are only
+//! borrowed for the duration of the call.
+//!
+//! Functions which fail return `NULL`, after which `ftml_last_error()` gives
+//! a description of what went wrong.
+//!
+//! The C header is generated from this crate by cbindgen in its build script
+//! (configured in `ffi/cbindgen.toml`), and is
+//! checked in at `ffi/include/ftml.h`.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+mod error;
+mod handle;
+mod parse;
+mod render;
+mod string;
+
+pub use self::error::*;
+pub use self::handle::*;
+pub use self::parse::*;
+pub use self::render::*;
+pub use self::string::*;
@@ -0,0 +1,117 @@
+/*
+ * ffi/src/parse.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::error::guard;
+use crate::handle::{FtmlPageInfo, FtmlSettings, read_handle};
+use crate::string::{into_raw_string, read_str, to_json};
+use ftml::parsing::ParseError;
+use ftml::tree::SyntaxTree;
+use std::ffi::c_char;
+
+/// Opaque handle to the result of parsing, a syntax tree and any warnings.
+#[derive(Debug)]
+pub struct FtmlParseOutcome {
+    pub(crate) tree: SyntaxTree<'static>,
+    pub(crate) errors: Vec<ParseError>,
+}
+
+/// Tokenizes and parses the given wikitext.
+///
+/// The text should already have been preprocessed with `ftml_preprocess()`.
+/// Parsing itself never fails, this only returns `NULL` for invalid arguments.
+///
+/// # Safety
+/// `text` must be a valid null-terminated string, and
+/// `page_info` and `settings` must be live handles.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_parse(
+    text: *const c_char,
+    page_info: *const FtmlPageInfo,
+    settings: *const FtmlSettings,
+) -> *mut FtmlParseOutcome {
+    guard(|| {
+        let text = unsafe { read_str(text, "text") }?;
+        let page_info = unsafe { read_handle(page_info, "page_info") }?;
+        let settings = unsafe { read_handle(settings, "settings") }?;
+
+        let tokens = ftml::tokenize(text);
+        let result = ftml::parse(&tokens, &page_info.inner, &settings.inner);
+        let (tree, errors) = result.into();
+        let outcome = FtmlParseOutcome {
+            tree: tree.to_owned(),
+            errors,
+        };
+
+        Some(Box::into_raw(Box::new(outcome)))
+    })
+}
+
+/// Returns the number of warnings produced while parsing.
+///
+/// # Safety
+/// `outcome` must be `NULL` or a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_parse_outcome_error_count(
+    outcome: *const FtmlParseOutcome,
+) -> usize {
+    match unsafe { outcome.as_ref() } {
+        Some(outcome) => outcome.errors.len(),
+        None => 0,
+    }
+}
+
+/// Returns the warnings produced while parsing, as a JSON array.
+///
+/// # Safety
+/// `outcome` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_parse_outcome_errors_json(
+    outcome: *const FtmlParseOutcome,
+) -> *mut c_char {
+    guard(|| {
+        let outcome = unsafe { read_handle(outcome, "outcome") }?;
+        into_raw_string(to_json(&outcome.errors)?)
+    })
+}
+
+/// Returns the syntax tree, as a JSON object.
+///
+/// # Safety
+/// `outcome` must be a live handle.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_parse_outcome_tree_json(
+    outcome: *const FtmlParseOutcome,
+) -> *mut c_char {
+    guard(|| {
+        let outcome = unsafe { read_handle(outcome, "outcome") }?;
+        into_raw_string(to_json(&outcome.tree)?)
+    })
+}
+
+/// Frees a parse outcome handle. Passing `NULL` does nothing.
+///
+/// # Safety
+/// `outcome` must be `NULL` or a handle which has not yet been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_parse_outcome_free(outcome: *mut FtmlParseOutcome) {
+    if !outcome.is_null() {
+        drop(unsafe { Box::from_raw(outcome) });
+    }
+}
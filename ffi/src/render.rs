@@ -0,0 +1,126 @@
+/*
+ * ffi/src/render.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::error::guard;
+use crate::handle::{FtmlPageInfo, FtmlSettings, read_handle};
+use crate::parse::FtmlParseOutcome;
+use crate::string::{into_raw_string, to_json};
+use ftml::render::Render;
+use ftml::render::html::{HtmlOutput, HtmlRender};
+use ftml::render::text::TextRender;
+use std::ffi::c_char;
+
+/// Renders a parsed syntax tree with the given function.
+///
+/// # Safety
+/// All pointers must be live handles.
+unsafe fn render<F>(
+    outcome: *const FtmlParseOutcome,
+    page_info: *const FtmlPageInfo,
+    settings: *const FtmlSettings,
+    f: F,
+) -> *mut c_char
+where
+    F: FnOnce(&FtmlParseOutcome, &FtmlPageInfo, &FtmlSettings) -> Option<String>,
+{
+    guard(|| {
+        let outcome = unsafe { read_handle(outcome, "outcome") }?;
+        let page_info = unsafe { read_handle(page_info, "page_info") }?;
+        let settings = unsafe { read_handle(settings, "settings") }?;
+        into_raw_string(f(outcome, page_info, settings)?)
+    })
+}
+
+fn render_html_output(
+    outcome: &FtmlParseOutcome,
+    page_info: &FtmlPageInfo,
+    settings: &FtmlSettings,
+) -> HtmlOutput {
+    HtmlRender.render(&outcome.tree, &page_info.inner, &settings.inner)
+}
+
+/// Renders a parsed syntax tree as HTML, returning only the body.
+///
+/// # Safety
+/// `outcome`, `page_info` and `settings` must be live handles.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_render_html(
+    outcome: *const FtmlParseOutcome,
+    page_info: *const FtmlPageInfo,
+    settings: *const FtmlSettings,
+) -> *mut c_char {
+    unsafe {
+        render(
+            outcome,
+            page_info,
+            settings,
+            |outcome, page_info, settings| {
+                Some(render_html_output(outcome, page_info, settings).body)
+            },
+        )
+    }
+}
+
+/// Renders a parsed syntax tree as HTML, returning the full output as a JSON object.
+///
+/// This includes the body, as well as metadata such as backlinks and code blocks.
+///
+/// # Safety
+/// `outcome`, `page_info` and `settings` must be live handles.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_render_html_json(
+    outcome: *const FtmlParseOutcome,
+    page_info: *const FtmlPageInfo,
+    settings: *const FtmlSettings,
+) -> *mut c_char {
+    unsafe {
+        render(
+            outcome,
+            page_info,
+            settings,
+            |outcome, page_info, settings| {
+                let output = render_html_output(outcome, page_info, settings);
+                to_json(&output)
+            },
+        )
+    }
+}
+
+/// Renders a parsed syntax tree as plain text.
+///
+/// # Safety
+/// `outcome`, `page_info` and `settings` must be live handles.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_render_text(
+    outcome: *const FtmlParseOutcome,
+    page_info: *const FtmlPageInfo,
+    settings: *const FtmlSettings,
+) -> *mut c_char {
+    unsafe {
+        render(
+            outcome,
+            page_info,
+            settings,
+            |outcome, page_info, settings| {
+                Some(TextRender.render(&outcome.tree, &page_info.inner, &settings.inner))
+            },
+        )
+    }
+}
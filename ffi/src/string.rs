@@ -0,0 +1,101 @@
+/*
+ * ffi/src/string.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::error::{guard, set_error};
+use serde::Serialize;
+use std::ffi::{CStr, CString, c_char};
+use std::sync::LazyLock;
+
+static VERSION: LazyLock<CString> = LazyLock::new(|| {
+    CString::new(ftml::info::VERSION.as_str()).expect("Version contains null byte")
+});
+
+/// Borrows a string argument, recording an error if it is `NULL` or not UTF-8.
+///
+/// # Safety
+/// The pointer must be `NULL` or point to a null-terminated string
+/// which outlives the returned reference.
+pub(crate) unsafe fn read_str<'a>(pointer: *const c_char, name: &str) -> Option<&'a str> {
+    if pointer.is_null() {
+        set_error(format!("argument '{name}' is null"));
+        return None;
+    }
+
+    let string = unsafe { CStr::from_ptr(pointer) };
+    match string.to_str() {
+        Ok(string) => Some(string),
+        Err(error) => {
+            set_error(format!("argument '{name}' is not valid UTF-8: {error}"));
+            None
+        }
+    }
+}
+
+/// Transfers ownership of a string to the caller.
+pub(crate) fn into_raw_string(string: String) -> Option<*mut c_char> {
+    match CString::new(string) {
+        Ok(string) => Some(string.into_raw()),
+        Err(error) => {
+            set_error(format!("output contains a null byte: {error}"));
+            None
+        }
+    }
+}
+
+/// Serializes a value as JSON, recording an error if it fails.
+pub(crate) fn to_json<T: Serialize + ?Sized>(value: &T) -> Option<String> {
+    match serde_json::to_string(value) {
+        Ok(json) => Some(json),
+        Err(error) => {
+            set_error(format!("unable to serialize output as JSON: {error}"));
+            None
+        }
+    }
+}
+
+/// Returns the version of ftml, as a static string.
+#[unsafe(no_mangle)]
+pub extern "C" fn ftml_version() -> *const c_char {
+    VERSION.as_ptr()
+}
+
+/// Runs the preprocessor on the given wikitext, returning a new string.
+///
+/// # Safety
+/// `text` must be a valid null-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_preprocess(text: *const c_char) -> *mut c_char {
+    guard(|| {
+        let mut text = String::from(unsafe { read_str(text, "text") }?);
+        ftml::preprocess(&mut text);
+        into_raw_string(text)
+    })
+}
+
+/// Frees a string returned by any ftml function. Passing `NULL` does nothing.
+///
+/// # Safety
+/// `string` must be `NULL` or a string returned by ftml which has not yet been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ftml_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
@@ -0,0 +1,84 @@
+/*
+ * ffi/tests/c_harness.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Checks the generated C header, and runs the C test harness against the library.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+const GENERATED_HEADER: &str = include_str!(concat!(env!("OUT_DIR"), "/ftml.h"));
+
+fn ffi_directory() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+#[test]
+fn header() {
+    let path = ffi_directory().join("include/ftml.h");
+
+    if env::var_os("FTML_UPDATE_TESTS").is_some() {
+        fs::write(&path, GENERATED_HEADER).expect("Unable to write header");
+        return;
+    }
+
+    let checked_in = fs::read_to_string(&path).unwrap_or_default();
+    assert_eq!(
+        checked_in, GENERATED_HEADER,
+        "Checked-in header is out of date, rerun with FTML_UPDATE_TESTS=1",
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn c_harness() {
+    // The cdylib is placed next to the test binary
+    let library_directory = env::current_exe()
+        .expect("Unable to get test executable path")
+        .parent()
+        .expect("Test executable has no parent directory")
+        .to_path_buf();
+
+    let output = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("ftml-c-harness");
+    let compiler = env::var("CC").unwrap_or_else(|_| String::from("cc"));
+    let directory = ffi_directory();
+
+    let status = Command::new(compiler)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror"])
+        .arg("-I")
+        .arg(directory.join("include"))
+        .arg(directory.join("tests/harness.c"))
+        .arg("-o")
+        .arg(&output)
+        .arg("-L")
+        .arg(&library_directory)
+        .arg("-lftml_ffi")
+        .arg(format!("-Wl,-rpath,{}", library_directory.display()))
+        .status()
+        .expect("Unable to run C compiler");
+
+    assert!(status.success(), "C harness failed to compile");
+
+    let status = Command::new(&output)
+        .status()
+        .expect("Unable to run C harness");
+
+    assert!(status.success(), "C harness reported failures");
+}
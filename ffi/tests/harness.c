@@ -0,0 +1,119 @@
+/*
+ * ffi/tests/harness.c
+ *
+ * Exercises the C bindings through the generated header.
+ * This is compiled and run by c_harness.rs as part of the test suite.
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "ftml.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                               \
+    do {                                                               \
+        if (!(condition)) {                                            \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,     \
+                    __LINE__, #condition);                             \
+            failures++;                                                \
+        }                                                              \
+    } while (0)
+
+static const char *PAGE_INFO =
+    "{\"page\":\"test-page\",\"category\":null,\"site\":\"test\","
+    "\"title\":\"Test page\",\"alt-title\":null,\"score\":0,"
+    "\"tags\":[\"fruit\"],\"language\":\"default\"}";
+
+static void test_version(void) {
+    const char *version = ftml_version();
+    CHECK(version != NULL);
+    CHECK(strlen(version) > 0);
+}
+
+static void test_errors(void) {
+    CHECK(ftml_settings_new("not-a-mode", "wikijump") == NULL);
+    CHECK(ftml_last_error() != NULL);
+    CHECK(strstr(ftml_last_error(), "not-a-mode") != NULL);
+
+    CHECK(ftml_page_info_from_json("{") == NULL);
+    CHECK(ftml_last_error() != NULL);
+
+    CHECK(ftml_preprocess(NULL) == NULL);
+    CHECK(strstr(ftml_last_error(), "text") != NULL);
+
+    /* Freeing NULL is always allowed */
+    ftml_string_free(NULL);
+    ftml_settings_free(NULL);
+    ftml_page_info_free(NULL);
+    ftml_parse_outcome_free(NULL);
+}
+
+static void test_render(void) {
+    ftml_settings *settings = ftml_settings_new("page", "wikijump");
+    ftml_page_info *page_info = ftml_page_info_from_json(PAGE_INFO);
+    CHECK(settings != NULL);
+    CHECK(page_info != NULL);
+    if (settings == NULL || page_info == NULL) {
+        return;
+    }
+
+    char *text = ftml_preprocess("**Apple**   and //banana//\r\n[[span]]");
+    CHECK(text != NULL);
+    CHECK(ftml_last_error() == NULL);
+
+    ftml_parse_outcome *outcome = ftml_parse(text, page_info, settings);
+    CHECK(outcome != NULL);
+    ftml_string_free(text);
+
+    CHECK(ftml_parse_outcome_error_count(outcome) > 0);
+
+    char *errors = ftml_parse_outcome_errors_json(outcome);
+    CHECK(errors != NULL);
+    CHECK(errors[0] == '[');
+    ftml_string_free(errors);
+
+    char *tree = ftml_parse_outcome_tree_json(outcome);
+    CHECK(tree != NULL);
+    CHECK(strstr(tree, "\"elements\"") != NULL);
+    ftml_string_free(tree);
+
+    char *html = ftml_render_html(outcome, page_info, settings);
+    CHECK(html != NULL);
+    CHECK(strstr(html, "<strong>Apple</strong>") != NULL);
+    CHECK(strstr(html, "<em>banana</em>") != NULL);
+    ftml_string_free(html);
+
+    char *json = ftml_render_html_json(outcome, page_info, settings);
+    CHECK(json != NULL);
+    CHECK(strstr(json, "\"body\"") != NULL);
+    ftml_string_free(json);
+
+    char *plain = ftml_render_text(outcome, page_info, settings);
+    CHECK(plain != NULL);
+    CHECK(strstr(plain, "Apple") != NULL);
+    CHECK(strstr(plain, "<strong>") == NULL);
+    ftml_string_free(plain);
+
+    CHECK(ftml_render_html(NULL, page_info, settings) == NULL);
+    CHECK(strstr(ftml_last_error(), "outcome") != NULL);
+
+    ftml_parse_outcome_free(outcome);
+    ftml_page_info_free(page_info);
+    ftml_settings_free(settings);
+}
+
+int main(void) {
+    test_version();
+    test_errors();
+    test_render();
+
+    if (failures > 0) {
+        fprintf(stderr, "%d check(s) failed\n", failures);
+        return EXIT_FAILURE;
+    }
+
+    return EXIT_SUCCESS;
+}
@@ -0,0 +1,300 @@
+/*
+ * bin/ftml/args.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Command-line argument processing.
+
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use ftml::layout::Layout;
+use ftml::settings::WikitextMode;
+use std::path::PathBuf;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Subcommand {
+    Tokenize,
+    Preprocess,
+    Include,
+    Parse,
+    Lint,
+    RenderHtml,
+    RenderText,
+    Site,
+}
+
+#[derive(Debug)]
+pub struct Config {
+    pub subcommand: Subcommand,
+    pub mode: WikitextMode,
+    pub layout: Layout,
+    pub page_info: Option<String>,
+    pub page_info_path: Option<PathBuf>,
+    pub include_directory: Option<PathBuf>,
+    pub input_path: Option<PathBuf>,
+    pub site: Option<String>,
+    pub output_directory: Option<PathBuf>,
+    pub json: bool,
+    pub document: bool,
+    pub pretty: bool,
+    pub strict: bool,
+}
+
+pub fn parse_args() -> Config {
+    let matches = command().get_matches();
+    let (subcommand, matches) = match matches.subcommand() {
+        Some(("tokenize", matches)) => (Subcommand::Tokenize, matches),
+        Some(("preprocess", matches)) => (Subcommand::Preprocess, matches),
+        Some(("include", matches)) => (Subcommand::Include, matches),
+        Some(("parse", matches)) => (Subcommand::Parse, matches),
+        Some(("lint", matches)) => (Subcommand::Lint, matches),
+        Some(("render", matches)) => match matches.subcommand() {
+            Some(("html", matches)) => (Subcommand::RenderHtml, matches),
+            Some(("text", matches)) => (Subcommand::RenderText, matches),
+            _ => unreachable!("Subcommand required by clap"),
+        },
+        Some(("site", matches)) => (Subcommand::Site, matches),
+        _ => unreachable!("Subcommand required by clap"),
+    };
+
+    config_from_matches(subcommand, matches)
+}
+
+fn config_from_matches(subcommand: Subcommand, matches: &ArgMatches) -> Config {
+    macro_rules! flag {
+        ($name:expr) => {
+            matches.try_get_one::<bool>($name).ok().flatten() == Some(&true)
+        };
+    }
+
+    macro_rules! value {
+        ($type:ty, $name:expr) => {
+            matches.try_get_one::<$type>($name).ok().flatten().cloned()
+        };
+    }
+
+    Config {
+        subcommand,
+        mode: value!(WikitextMode, "mode").unwrap_or(WikitextMode::Page),
+        layout: value!(Layout, "layout").unwrap_or(Layout::Wikijump),
+        page_info: value!(String, "page-info"),
+        page_info_path: value!(PathBuf, "page-info-file"),
+        include_directory: value!(PathBuf, "include-dir")
+            .or(value!(PathBuf, "input-dir")),
+        input_path: value!(PathBuf, "input-file"),
+        site: value!(String, "site"),
+        output_directory: value!(PathBuf, "output-dir"),
+        json: flag!("json"),
+        document: flag!("document"),
+        pretty: !flag!("compact"),
+        strict: flag!("strict") || subcommand == Subcommand::Lint,
+    }
+}
+
+fn command() -> Command {
+    // Arguments shared by all subcommands
+    let input_arg = Arg::new("input-file")
+        .value_parser(value_parser!(PathBuf))
+        .value_name("PATH")
+        .help("Read wikitext from this file instead of stdin.");
+
+    let mode_arg = Arg::new("mode")
+        .short('m')
+        .long("mode")
+        .value_name("MODE")
+        .value_parser(|value: &str| {
+            value.parse::<WikitextMode>().map_err(|_| {
+                "expected one of 'page', 'page-nav', 'draft', 'forum-post', 'direct-message', 'list'"
+            })
+        })
+        .help("Which wikitext mode to use. [default: page]");
+
+    let layout_arg = Arg::new("layout")
+        .short('l')
+        .long("layout")
+        .value_name("LAYOUT")
+        .value_parser(|value: &str| {
+            value
+                .parse::<Layout>()
+                .map_err(|_| "expected one of 'wikidot', 'wikijump'")
+        })
+        .help("Which HTML layout to use. [default: wikijump]");
+
+    let page_info_arg = Arg::new("page-info")
+        .long("page-info")
+        .value_name("JSON")
+        .conflicts_with("page-info-file")
+        .help("Specify a custom page info object for use.");
+
+    let page_info_file_arg = Arg::new("page-info-file")
+        .long("page-info-file")
+        .value_name("PATH")
+        .value_parser(value_parser!(PathBuf))
+        .help("Read a custom page info object from a JSON file.");
+
+    let include_dir_arg = Arg::new("include-dir")
+        .short('I')
+        .long("include-dir")
+        .value_name("DIRECTORY")
+        .value_parser(value_parser!(PathBuf))
+        .help("Substitute [[include]] blocks using pages from this directory.");
+
+    let json_arg = Arg::new("json")
+        .short('j')
+        .long("json")
+        .action(ArgAction::SetTrue)
+        .help("Emit output as JSON.");
+
+    let compact_arg = Arg::new("compact")
+        .short('k')
+        .long("compact")
+        .action(ArgAction::SetTrue)
+        .help("Emit compact JSON instead of prettified.");
+
+    let strict_arg = Arg::new("strict")
+        .short('s')
+        .long("strict")
+        .action(ArgAction::SetTrue)
+        .help("Exit with a failure status if any parser warnings were produced.");
+
+    // Helper to add the arguments needed for parsing
+    let parse_args = |command: Command| {
+        command
+            .arg(&mode_arg)
+            .arg(&layout_arg)
+            .arg(&page_info_arg)
+            .arg(&page_info_file_arg)
+            .arg(&include_dir_arg)
+            .arg(&input_arg)
+    };
+
+    Command::new("ftml")
+        .author(ftml::info::PKG_AUTHORS)
+        .version(ftml::info::VERSION.as_str())
+        .long_version(ftml::info::FULL_VERSION.as_str())
+        .about(ftml::info::PKG_DESCRIPTION)
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .after_help(
+            "Exit status is 0 on success, 1 if the wikitext produced warnings \
+             (with --strict or lint), 2 for invalid arguments, and 3 for I/O errors.",
+        )
+        .subcommand(
+            Command::new("tokenize")
+                .about("Print the tokens the wikitext is split into.")
+                .arg(&json_arg)
+                .arg(&compact_arg)
+                .arg(&input_arg),
+        )
+        .subcommand(
+            Command::new("preprocess")
+                .about("Print the wikitext after preprocessing.")
+                .arg(&input_arg),
+        )
+        .subcommand(
+            Command::new("include")
+                .about("Print the wikitext after substituting [[include]] blocks.")
+                .arg(&mode_arg)
+                .arg(&page_info_arg)
+                .arg(&page_info_file_arg)
+                .arg(include_dir_arg.clone().required(true))
+                .arg(&input_arg),
+        )
+        .subcommand(
+            parse_args(Command::new("parse"))
+                .about("Print the syntax tree and warnings as JSON.")
+                .arg(&compact_arg)
+                .arg(&strict_arg),
+        )
+        .subcommand(
+            parse_args(Command::new("lint"))
+                .about("Print any warnings produced while parsing.")
+                .arg(&json_arg)
+                .arg(&compact_arg),
+        )
+        .subcommand(
+            Command::new("render")
+                .about("Render the wikitext.")
+                .subcommand_required(true)
+                .subcommand(
+                    parse_args(Command::new("html"))
+                        .about("Render as HTML.")
+                        .arg(
+                            json_arg.clone().help(
+                                "Emit the full HTML output (with metadata) as JSON.",
+                            ),
+                        )
+                        .arg(
+                            Arg::new("document")
+                                .short('d')
+                                .long("document")
+                                .action(ArgAction::SetTrue)
+                                .conflicts_with("json")
+                                .help(
+                                    "Emit a standalone HTML document, with a baseline stylesheet and scripts.",
+                                ),
+                        )
+                        .arg(&compact_arg)
+                        .arg(&strict_arg),
+                )
+                .subcommand(
+                    parse_args(Command::new("text"))
+                        .about("Render as plain text.")
+                        .arg(&strict_arg),
+                ),
+        )
+        .subcommand(
+            Command::new("site")
+                .about("Render a directory of pages into a static HTML site.")
+                .long_about(
+                    "Render a directory of pages into a static HTML site.\n\n\
+                     Pages are read from {input-dir}/{site}/{page}.ftml, with optional \
+                     metadata in {page}.json and attached files in files/{page}/. \
+                     Each page is written to {output-dir}/{page}.html, alongside a \
+                     backlinks.json index.",
+                )
+                .arg(
+                    Arg::new("site")
+                        .long("site")
+                        .value_name("SITE")
+                        .required(true)
+                        .help("The site whose pages to render."),
+                )
+                .arg(
+                    Arg::new("input-dir")
+                        .value_name("INPUT")
+                        .value_parser(value_parser!(PathBuf))
+                        .required(true)
+                        .help("The directory containing a subdirectory for each site."),
+                )
+                .arg(
+                    Arg::new("output-dir")
+                        .value_name("OUTPUT")
+                        .value_parser(value_parser!(PathBuf))
+                        .required(true)
+                        .help("The directory to write the generated site to."),
+                )
+                .arg(&mode_arg)
+                .arg(&layout_arg)
+                .arg(&strict_arg),
+        )
+}
+
+#[test]
+fn verify_command() {
+    command().debug_assert();
+}
@@ -0,0 +1,385 @@
+/*
+ * bin/ftml/main.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Command-line interface to ftml.
+//!
+//! This exposes each stage of the wikitext pipeline (include, preprocess,
+//! tokenize, parse, render) for use in scripts and CI checks of wiki content.
+
+#[macro_use]
+extern crate str_macro;
+
+mod args;
+
+use self::args::{Config, Subcommand, parse_args};
+use ftml::data::{PageInfo, ScoreValue};
+use ftml::includes::DirectoryIncluder;
+use ftml::parsing::ParseError;
+use ftml::render::Render;
+use ftml::render::html::{HtmlDocumentRender, HtmlRender};
+use ftml::render::text::TextRender;
+use ftml::settings::WikitextSettings;
+use ftml::site::SiteGenerator;
+use ftml::tree::SyntaxTree;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+/// Exit status when the wikitext produced parser warnings.
+const EXIT_WARNINGS: u8 = 1;
+
+/// Exit status for invalid arguments, matching clap's own usage errors.
+const EXIT_USAGE: u8 = 2;
+
+/// Exit status when reading input or writing output failed.
+const EXIT_IO: u8 = 3;
+
+#[derive(Debug)]
+enum Failure {
+    Usage(String),
+    Io(String, io::Error),
+}
+
+impl Failure {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Failure::Usage(message) => {
+                eprintln!("error: {message}");
+                ExitCode::from(EXIT_USAGE)
+            }
+            Failure::Io(context, error) => {
+                eprintln!("error: {context}: {error}");
+                ExitCode::from(EXIT_IO)
+            }
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, Failure>;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct ParseOutput<'a, 't> {
+    syntax_tree: &'a SyntaxTree<'t>,
+    errors: &'a [ParseError],
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+struct TokenOutput<'a> {
+    token: &'static str,
+    slice: &'a str,
+    span: [usize; 2],
+}
+
+fn main() -> ExitCode {
+    let config = parse_args();
+    match run(&config) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(EXIT_WARNINGS),
+        Err(failure) => failure.exit_code(),
+    }
+}
+
+/// Runs the subcommand, returning `false` if warnings should fail the run.
+fn run(config: &Config) -> Result<bool> {
+    if config.subcommand == Subcommand::Site {
+        return run_site(config);
+    }
+
+    let input = read_input(config.input_path.as_deref())?;
+    let page_info = read_page_info(config)?;
+    let settings = WikitextSettings::from_mode(config.mode, config.layout);
+
+    match config.subcommand {
+        Subcommand::Tokenize => {
+            let tokens = ftml::tokenize(&input);
+            if config.json {
+                let tokens = tokens
+                    .tokens()
+                    .iter()
+                    .map(|extract| TokenOutput {
+                        token: extract.token.name(),
+                        slice: extract.slice,
+                        span: [extract.span.start, extract.span.end],
+                    })
+                    .collect::<Vec<_>>();
+
+                write_json(config, &tokens)?;
+            } else {
+                let mut output = String::new();
+                for extract in tokens.tokens() {
+                    let span = format!("{}..{}", extract.span.start, extract.span.end);
+                    output.push_str(&format!(
+                        "{span:<14} {:<24} {:?}\n",
+                        extract.token.name(),
+                        extract.slice,
+                    ));
+                }
+
+                write_output(&output)?;
+            }
+
+            Ok(true)
+        }
+        Subcommand::Preprocess => {
+            let mut text = input;
+            ftml::preprocess(&mut text);
+            write_output(&text)?;
+            Ok(true)
+        }
+        Subcommand::Include => {
+            let text = run_include(config, &input, &page_info, &settings)?;
+            write_output(&text)?;
+            Ok(true)
+        }
+        Subcommand::Parse
+        | Subcommand::Lint
+        | Subcommand::RenderHtml
+        | Subcommand::RenderText => {
+            let mut text = run_include(config, &input, &page_info, &settings)?;
+            ftml::preprocess(&mut text);
+
+            let tokens = ftml::tokenize(&text);
+            let result = ftml::parse(&tokens, &page_info, &settings);
+            let (tree, errors) = result.into();
+
+            match config.subcommand {
+                Subcommand::Parse => {
+                    let output = ParseOutput {
+                        syntax_tree: &tree,
+                        errors: &errors,
+                    };
+
+                    write_json(config, &output)?;
+                }
+                Subcommand::Lint => {
+                    if config.json {
+                        write_json(config, &errors)?;
+                    } else {
+                        write_output(&format_errors(config, &text, &errors))?;
+                    }
+                }
+                Subcommand::RenderHtml => {
+                    let output = HtmlRender.render(&tree, &page_info, &settings);
+                    if config.json {
+                        write_json(config, &output)?;
+                    } else if config.document {
+                        let document =
+                            HtmlDocumentRender::STANDALONE.wrap(&output, &page_info);
+                        write_output(&document)?;
+                    } else {
+                        write_output(&output.body)?;
+                    }
+                }
+                Subcommand::RenderText => {
+                    let output = TextRender.render(&tree, &page_info, &settings);
+                    write_output(&output)?;
+                }
+                _ => unreachable!(),
+            }
+
+            Ok(!config.strict || errors.is_empty())
+        }
+        Subcommand::Site => unreachable!("Site generation does not read input"),
+    }
+}
+
+fn run_include(
+    config: &Config,
+    input: &str,
+    page_info: &PageInfo,
+    settings: &WikitextSettings,
+) -> Result<String> {
+    let directory = match &config.include_directory {
+        Some(directory) => directory,
+        None => return Ok(str!(input)),
+    };
+
+    let includer = DirectoryIncluder::new(directory.clone(), page_info.site.to_string());
+    let (output, pages) = ftml::include(input, settings, includer, || {
+        io::Error::other("includer returned mismatched pages")
+    })
+    .map_err(|error| Failure::Io(str!("unable to include pages"), error))?;
+
+    for page in &pages {
+        eprintln!("included: {page}");
+    }
+
+    Ok(output)
+}
+
+fn run_site(config: &Config) -> Result<bool> {
+    let (Some(site), Some(input), Some(output)) = (
+        &config.site,
+        &config.include_directory,
+        &config.output_directory,
+    ) else {
+        unreachable!("Arguments required by clap");
+    };
+
+    let settings = WikitextSettings::from_mode(config.mode, config.layout);
+    let generator =
+        SiteGenerator::new(input.clone(), output.clone(), site.clone(), settings);
+    let result = generator
+        .generate()
+        .map_err(|error| Failure::Io(str!("unable to generate site"), error))?;
+
+    let mut warnings = 0;
+    for page in &result.pages {
+        if !page.errors.is_empty() {
+            eprintln!("{}: {} warning(s)", page.name, page.errors.len());
+            warnings += page.errors.len();
+        }
+    }
+
+    eprintln!(
+        "generated {} page(s) and {} file(s) in {}",
+        result.pages.len(),
+        result.files,
+        output.display(),
+    );
+
+    Ok(!config.strict || warnings == 0)
+}
+
+fn read_input(path: Option<&Path>) -> Result<String> {
+    let mut buffer = String::new();
+
+    match path {
+        Some(path) => {
+            buffer = fs::read_to_string(path).map_err(|error| {
+                Failure::Io(format!("unable to read {}", path.display()), error)
+            })?;
+        }
+        None => {
+            io::stdin()
+                .lock()
+                .read_to_string(&mut buffer)
+                .map_err(|error| Failure::Io(str!("unable to read stdin"), error))?;
+        }
+    }
+
+    Ok(buffer)
+}
+
+fn read_page_info(config: &Config) -> Result<PageInfo<'static>> {
+    let json = match (&config.page_info, &config.page_info_path) {
+        (Some(json), _) => Cow::Borrowed(json.as_str()),
+        (None, Some(path)) => {
+            let json = fs::read_to_string(path).map_err(|error| {
+                Failure::Io(format!("unable to read {}", path.display()), error)
+            })?;
+
+            Cow::Owned(json)
+        }
+        (None, None) => return Ok(default_page_info()),
+    };
+
+    serde_json::from_str(&json)
+        .map_err(|error| Failure::Usage(format!("invalid page info JSON: {error}")))
+}
+
+fn default_page_info() -> PageInfo<'static> {
+    PageInfo {
+        page: Cow::Borrowed("page"),
+        category: None,
+        site: Cow::Borrowed("site"),
+        title: Cow::Borrowed("Page"),
+        alt_title: None,
+        score: ScoreValue::Integer(0),
+        tags: vec![],
+        language: Cow::Borrowed("default"),
+        render_timestamp: None,
+    }
+}
+
+/// Formats parser warnings, one per line, as `{path}:{line}:{column}: {kind}`.
+///
+/// Positions refer to the wikitext after includes and preprocessing.
+fn format_errors(config: &Config, text: &str, errors: &[ParseError]) -> String {
+    let path = match &config.input_path {
+        Some(path) => path.display().to_string(),
+        None => str!("<stdin>"),
+    };
+
+    let mut output = String::new();
+    for error in errors {
+        let (line, column) = line_column(text, error.span().start);
+        output.push_str(&format!(
+            "{path}:{line}:{column}: {} (rule {}, token {})\n",
+            kebab_name(error.kind()),
+            error.rule(),
+            kebab_name(error.token()),
+        ));
+    }
+
+    output
+}
+
+/// Gets the name of an enum variant as it is serialized, e.g. `end-of-input`.
+fn kebab_name<T: Serialize>(value: T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => str!("unknown"),
+    }
+}
+
+/// Converts a byte offset into a 1-based line and column (in characters).
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+fn write_json<T: Serialize>(config: &Config, data: &T) -> Result<()> {
+    let mut json = if config.pretty {
+        serde_json::to_string_pretty(data)
+    } else {
+        serde_json::to_string(data)
+    }
+    .map_err(|error| Failure::Io(str!("unable to serialize JSON"), error.into()))?;
+
+    json.push('\n');
+    write_output(&json)
+}
+
+fn write_output(output: &str) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    stdout
+        .write_all(output.as_bytes())
+        .and_then(|()| stdout.flush())
+        .map_err(|error| Failure::Io(str!("unable to write output"), error))
+}
+
+#[test]
+fn test_line_column() {
+    let text = "apple\nbanana\nçherry";
+    assert_eq!(line_column(text, 0), (1, 1));
+    assert_eq!(line_column(text, 3), (1, 4));
+    assert_eq!(line_column(text, 6), (2, 1));
+    assert_eq!(line_column(text, 15), (3, 2));
+    assert_eq!(line_column(text, 100), (3, 7));
+}
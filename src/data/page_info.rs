@@ -60,9 +60,25 @@ pub struct PageInfo<'a> {
 
     /// The language that this page is being rendered for.
     pub language: Cow<'a, str>,
+
+    /// The time at which this page is being rendered, as a Unix timestamp.
+    ///
+    /// This is provided by the host, since the current time is not available
+    /// on every platform, and rendering should otherwise be deterministic.
+    /// If `None`, then the `render_timestamp` variable is empty.
+    #[serde(default)]
+    pub render_timestamp: Option<i64>,
 }
 
 impl PageInfo<'_> {
+    /// Returns the full name of this page, prefixed by its category if it has one.
+    pub fn full_name(&self) -> Cow<'_, str> {
+        match &self.category {
+            Some(category) => Cow::Owned(format!("{category}:{}", self.page)),
+            None => Cow::Borrowed(&self.page),
+        }
+    }
+
     /// Generate a dummy PageInfo instance for tests.
     #[cfg(test)]
     pub fn dummy() -> Self {
@@ -75,6 +91,7 @@ impl PageInfo<'_> {
             score: ScoreValue::Float(69.0),
             tags: vec![cow!("tale"), cow!("_cc")],
             language: cow!("default"),
+            render_timestamp: None,
         }
     }
 }
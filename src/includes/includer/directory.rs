@@ -0,0 +1,192 @@
+/*
+ * includes/includer/directory.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::includes::missing_page_notice;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use wikidot_normalize::normalize;
+
+/// An [`Includer`] which reads pages from a directory, such as an exported wiki backup.
+///
+/// Pages are stored as `{site}/{category}:{page}.ftml`, or `{site}/{page}.ftml`
+/// for pages in the default category. Names are normalized the same way Wikidot
+/// does, so `[[include Component:Thing]]` reads `{site}/component:thing.ftml`.
+/// Pages without an explicit site are read from the current site's directory.
+///
+/// File reads are cached, so pages included multiple times (or across multiple
+/// renders, by passing `&mut` to [`include()`](crate::include)) are only read once.
+#[derive(Debug, Clone)]
+pub struct DirectoryIncluder {
+    directory: PathBuf,
+    site: String,
+    cache: HashMap<PathBuf, Option<String>>,
+}
+
+impl DirectoryIncluder {
+    pub fn new(directory: PathBuf, mut site: String) -> Self {
+        normalize(&mut site);
+
+        DirectoryIncluder {
+            directory,
+            site,
+            cache: HashMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    #[inline]
+    pub fn site(&self) -> &str {
+        &self.site
+    }
+
+    /// Returns the path the given page is read from.
+    pub fn path(&self, page_ref: &PageRef) -> PathBuf {
+        let (site, page, _) = page_ref.fields_or(&self.site);
+        let mut site = str!(site);
+        let mut page = str!(page);
+        normalize(&mut site);
+        normalize(&mut page);
+
+        // Pages in the default category are stored without it
+        if let Some(stripped) = page.strip_prefix("_default:") {
+            page = str!(stripped);
+        }
+
+        self.directory.join(site).join(format!("{page}.ftml"))
+    }
+
+    /// Reads a page, or returns `None` if it does not exist.
+    pub fn read(&mut self, page_ref: &PageRef) -> io::Result<Option<&str>> {
+        let path = self.path(page_ref);
+
+        if !self.cache.contains_key(&path) {
+            debug!("Reading included page from {}", path.display());
+
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => Some(content),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+                Err(error) => return Err(error),
+            };
+
+            self.cache.insert(path.clone(), content);
+        }
+
+        Ok(self.cache[&path].as_deref())
+    }
+}
+
+impl<'t> Includer<'t> for DirectoryIncluder {
+    type Error = io::Error;
+
+    fn include_pages(
+        &mut self,
+        includes: &[IncludeRef<'t>],
+    ) -> io::Result<Vec<FetchedPage<'t>>> {
+        let mut pages = Vec::with_capacity(includes.len());
+
+        for include in includes {
+            let page_ref = include.page_ref().clone();
+            let content = self
+                .read(&page_ref)?
+                .map(|content| Cow::Owned(str!(content)));
+            pages.push(FetchedPage { page_ref, content });
+        }
+
+        Ok(pages)
+    }
+
+    fn no_such_include(&mut self, page_ref: &PageRef) -> io::Result<Cow<'t, str>> {
+        Ok(Cow::Owned(missing_page_notice(page_ref)))
+    }
+}
+
+#[test]
+fn directory_includer() {
+    use crate::settings::{WikitextMode, WikitextSettings};
+    use crate::test::temp_dir::TempDir;
+
+    let temp_dir = TempDir::new("includes");
+    let directory = temp_dir.path();
+    let mut includer = DirectoryIncluder::new(directory.to_path_buf(), str!("Test"));
+
+    fs::create_dir_all(directory.join("test")).unwrap();
+    fs::create_dir_all(directory.join("other-wiki")).unwrap();
+    fs::write(directory.join("test/component:thing.ftml"), "Thing {$x}").unwrap();
+    fs::write(directory.join("test/scp-001.ftml"), "SCP-001").unwrap();
+    fs::write(directory.join("other-wiki/start.ftml"), "Start").unwrap();
+
+    // Check path mapping
+    assert_eq!(
+        includer.path(&PageRef::page_only("Component:Thing")),
+        directory.join("test/component:thing.ftml"),
+    );
+    assert_eq!(
+        includer.path(&PageRef::page_only("_default:SCP-001")),
+        directory.join("test/scp-001.ftml"),
+    );
+    assert_eq!(
+        includer.path(&PageRef::page_and_site("Other Wiki", "start")),
+        directory.join("other-wiki/start.ftml"),
+    );
+
+    // Check includes, including missing pages
+    let settings =
+        WikitextSettings::from_mode(WikitextMode::Page, crate::layout::Layout::Wikijump);
+    let input = "[[include component:thing x=1]]\n[[include :other-wiki:start]]\n[[include missing]]";
+    let (output, pages) = crate::include(input, &settings, &mut includer, || {
+        panic!("Mismatched pages")
+    })
+    .expect("Unable to include pages");
+
+    assert_eq!(
+        output,
+        "Thing 1\nStart\n[[div class=\"wj-error-block\"]]\nIncluded page \"missing\" does not exist.\n[[/div]]",
+    );
+    assert_eq!(
+        pages,
+        vec![
+            PageRef::page_only("component:thing"),
+            PageRef::page_and_site("other-wiki", "start"),
+            PageRef::page_only("missing"),
+        ],
+    );
+
+    // Check that reads are cached
+    fs::remove_dir_all(directory).unwrap();
+    assert_eq!(
+        includer
+            .read(&PageRef::page_only("scp-001"))
+            .expect("Unable to read page"),
+        None,
+    );
+    assert_eq!(
+        includer
+            .read(&PageRef::page_only("component:thing"))
+            .expect("Unable to read page"),
+        Some("Thing {$x}"),
+    );
+}
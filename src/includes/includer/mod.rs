@@ -22,6 +22,7 @@
 //! block.
 
 mod debug;
+mod directory;
 mod null;
 
 mod prelude {
@@ -34,8 +35,16 @@ use crate::includes::{IncludeRef, PageRef};
 use std::borrow::Cow;
 
 pub use self::debug::DebugIncluder;
+pub use self::directory::DirectoryIncluder;
 pub use self::null::NullIncluder;
 
+/// The wikitext which the built-in includers substitute for a missing page.
+pub(crate) fn missing_page_notice(page_ref: &PageRef) -> String {
+    format!(
+        "[[div class=\"wj-error-block\"]]\nIncluded page \"{page_ref}\" does not exist.\n[[/div]]",
+    )
+}
+
 /// A type used by [`Includer`] which represents a page that is ready to be included.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -60,3 +69,26 @@ pub trait Includer<'t> {
         page_ref: &PageRef,
     ) -> Result<Cow<'t, str>, Self::Error>;
 }
+
+impl<'t, I> Includer<'t> for &mut I
+where
+    I: Includer<'t> + ?Sized,
+{
+    type Error = I::Error;
+
+    #[inline]
+    fn include_pages(
+        &mut self,
+        includes: &[IncludeRef<'t>],
+    ) -> Result<Vec<FetchedPage<'t>>, Self::Error> {
+        (**self).include_pages(includes)
+    }
+
+    #[inline]
+    fn no_such_include(
+        &mut self,
+        page_ref: &PageRef,
+    ) -> Result<Cow<'t, str>, Self::Error> {
+        (**self).no_such_include(page_ref)
+    }
+}
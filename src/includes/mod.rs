@@ -33,13 +33,19 @@ mod includer;
 mod parse;
 
 pub use self::include_ref::IncludeRef;
-pub use self::includer::{DebugIncluder, FetchedPage, Includer, NullIncluder};
+pub use self::includer::{
+    DebugIncluder, DirectoryIncluder, FetchedPage, Includer, NullIncluder,
+};
+
+pub(crate) use self::includer::missing_page_notice;
 
 use self::parse::parse_include_block;
 use crate::data::PageRef;
 use crate::settings::WikitextSettings;
+use crate::templates::{SourceMap, SourceMapBuilder};
 use crate::tree::VariableMap;
 use regex::{Regex, RegexBuilder};
+use std::ops::Range;
 use std::sync::LazyLock;
 
 /// Replaces the include blocks in a string with the content of the pages referenced by those
@@ -47,9 +53,27 @@ use std::sync::LazyLock;
 pub fn include<'t, I, E, F>(
     input: &'t str,
     settings: &WikitextSettings,
-    mut includer: I,
+    includer: I,
     invalid_return: F,
 ) -> Result<(String, Vec<PageRef>), E>
+where
+    I: Includer<'t, Error = E>,
+    F: FnOnce() -> E,
+{
+    let (output, pages, _) = include_with_map(input, settings, includer, invalid_return)?;
+    Ok((output, pages))
+}
+
+/// Replaces the include blocks in a string, like [`include()`].
+///
+/// Also returns a map from the output back to the input, in which
+/// the content of included pages is not covered.
+pub fn include_with_map<'t, I, E, F>(
+    input: &'t str,
+    settings: &WikitextSettings,
+    mut includer: I,
+    invalid_return: F,
+) -> Result<(String, Vec<PageRef>, SourceMap), E>
 where
     I: Includer<'t, Error = E>,
     F: FnOnce() -> E,
@@ -68,7 +92,8 @@ where
 
         let output = str!(input);
         let pages = vec![];
-        return Ok((output, pages));
+        let map = SourceMapBuilder::new().finish(input.len());
+        return Ok((output, pages, map));
     }
 
     info!(
@@ -89,6 +114,15 @@ where
             mtch.as_str(),
         );
 
+        // Skip matches within the arguments of the previous include
+        if ranges
+            .last()
+            .is_some_and(|range: &Range<usize>| start < range.end)
+        {
+            debug!("Include match is within the previous include block, skipping");
+            continue;
+        }
+
         match parse_include_block(input, start) {
             Ok((include, end)) => {
                 ranges.push(start..end);
@@ -120,6 +154,7 @@ where
     // (slices from the input string), and replace it with new content.
     let mut output = String::from(input);
     let mut pages = Vec::new();
+    let mut replacements = Vec::new();
 
     for ((range, include), fetched) in joined_iter {
         let (page_ref, variables) = include.into();
@@ -150,14 +185,21 @@ where
         pages.push(page_ref);
 
         // Perform the substitution
+        replacements.push((range.clone(), replace_with.len()));
         output.replace_range(range, &replace_with);
     }
 
     // Since we iterate in reverse order, the pages are reversed.
     pages.reverse();
 
+    // Build the source map, going forwards through the replacements
+    let mut map = SourceMapBuilder::new();
+    for (range, length) in replacements.into_iter().rev() {
+        map.replace(range, length);
+    }
+
     // Return
-    Ok((output, pages))
+    Ok((output, pages, map.finish(input.len())))
 }
 
 /// Replaces all specified variables in the content to be included.
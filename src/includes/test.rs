@@ -18,7 +18,7 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
-use super::{DebugIncluder, PageRef, include};
+use super::{DebugIncluder, PageRef, include, include_with_map};
 use crate::layout::Layout;
 use crate::settings::{WikitextMode, WikitextSettings};
 
@@ -391,3 +391,35 @@ fn includes() {
         vec![],
     );
 }
+
+#[test]
+fn include_map() {
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let input =
+        "A\n[[include apple]]\nB\n[[include banana\n| x = [[\n[[include cherry]]\nC";
+    let (output, pages, map) =
+        include_with_map(input, &settings, DebugIncluder, || panic!())
+            .expect("Fetching pages failed");
+
+    // Includes within the arguments of another are not separate
+    assert_eq!(
+        output,
+        "A\n<MISSING-PAGE apple>\nB\n<INCLUDED-PAGE banana {\"x\" => \"[[\\n[[include cherry\"}>\nC",
+    );
+    assert_eq!(
+        pages,
+        vec![PageRef::page_only("apple"), PageRef::page_only("banana")],
+    );
+
+    // Text outside of includes maps exactly, included text to the start of its block
+    let b = output.find('B').unwrap();
+    assert_eq!(map.get_source_position(b), input.find('B').unwrap());
+    assert_eq!(map.get_source_position(3), 2);
+    assert_eq!(map.get_source_position(10), 2);
+    assert_eq!(map.get_source_position(output.len()), input.len());
+    assert_eq!(
+        map.get_source_span(b..b + 1),
+        Some(input.find('B').unwrap()..input.find('B').unwrap() + 1)
+    );
+    assert_eq!(map.get_source_span(2..10), None);
+}
@@ -127,13 +127,21 @@ pub mod layout;
 pub mod parsing;
 pub mod preproc;
 pub mod render;
+pub mod sections;
 pub mod settings;
+
+#[cfg(feature = "html")]
+pub mod site;
+
+pub mod templates;
 pub mod tokenizer;
 pub mod tree;
 
 pub use self::includes::include;
 pub use self::parsing::parse;
 pub use self::preproc::preprocess;
+pub use self::sections::replace_section;
+pub use self::templates::{apply_form_template, apply_template};
 pub use self::tokenizer::{Tokenization, tokenize};
 pub use self::utf16::Utf16IndexMap;
 
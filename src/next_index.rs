@@ -67,3 +67,19 @@ impl NextIndex<TableOfContentsIndex> for Incrementer {
         }
     }
 }
+
+// Heading anchors
+
+/// Trait to yield the anchor ID for the next heading in the table of contents.
+///
+/// Like [`NextIndex`], this should always return `None` if IDs are disabled.
+pub trait NextHeadingId {
+    fn next_heading_id(&mut self) -> Option<String>;
+}
+
+impl NextHeadingId for Incrementer {
+    #[inline]
+    fn next_heading_id(&mut self) -> Option<String> {
+        NextIndex::<TableOfContentsIndex>::next(self).map(|index| format!("toc{index}"))
+    }
+}
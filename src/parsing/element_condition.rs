@@ -18,9 +18,31 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+//! Conditions determining element presence, for blocks like `[[iftags]]`.
+//!
+//! There are two syntaxes for these. The original is a space-separated
+//! list of values, each optionally prefixed with `+` (required) or
+//! `-` (prohibited). It is evaluated as
+//! `all(required) && any(present) && all(prohibited)`.
+//!
+//! The other is a boolean expression, used if the specification contains
+//! parentheses or any of the (uppercase) keywords `AND`, `OR`, or `NOT`.
+//! For instance, `(scp OR tale) AND NOT _hidden`. Here `NOT` binds the
+//! tightest, followed by `AND`, then `OR`. The prefixes `+` and `-` are
+//! still accepted, meaning nothing and `NOT` respectively.
+//!
+//! In both syntaxes, values are compared case-insensitively, and may
+//! contain the glob wildcards `*` (any sequence of characters) and
+//! `?` (any single character).
+
+use super::{ParseError, ParseErrorKind, Parser};
 use std::borrow::Cow;
+use std::ops::Range;
 use strum_macros::IntoStaticStr;
 
+/// The maximum number of parentheses which may be nested within each other.
+const MAX_NESTING_DEPTH: usize = 100;
+
 /// Representation of a single condition to determine element presence.
 ///
 /// A list of these constitutes a full condition specification, and is
@@ -64,48 +86,312 @@ impl<'t> ElementCondition<'t> {
             })
             .collect()
     }
+}
 
-    /// Determines if this condition is satisfied.
-    ///
-    /// * `ElementConditionType::Required` -- All values of this kind must be present.
-    /// * `ElementConditionType::Prohibited` -- All values of this kind must be absent.
-    /// * `ElementConditionType::Present` -- Some values of this kind must be present.
-    ///
-    /// The full logic is essentially `all(required) && any(present) && all(prohibited)`.
-    pub fn check(conditions: &[ElementCondition], values: &[Cow<str>]) -> bool {
-        let mut required = true;
-        let mut prohibited = true;
-        let mut present = false;
-        let mut had_present = false; // whether there were any present conditions
-
-        for condition in conditions {
-            let has_value = values.contains(&condition.value);
-
-            match condition.ctype {
-                ElementConditionType::Required => required &= has_value,
-                ElementConditionType::Prohibited => prohibited &= !has_value,
-                ElementConditionType::Present => {
-                    present |= has_value;
-                    had_present = true;
+/// A parsed condition specification, which can be checked against a set of values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConditionExpression<'t> {
+    /// The value, or glob pattern, is present.
+    Value(Cow<'t, str>),
+
+    /// The inner expression is false.
+    Not(Box<ConditionExpression<'t>>),
+
+    /// All of the inner expressions are true. Vacuously true if empty.
+    All(Vec<ConditionExpression<'t>>),
+
+    /// Any of the inner expressions are true.
+    Any(Vec<ConditionExpression<'t>>),
+}
+
+impl<'t> ConditionExpression<'t> {
+    /// Parses a condition specification, in either syntax.
+    pub fn parse(spec: &'t str) -> Result<Self, ConditionSyntaxError> {
+        if Self::is_legacy(spec) {
+            return Ok(Self::from_legacy(ElementCondition::parse(spec)));
+        }
+
+        let tokens = lex(spec);
+        let mut parser = ConditionParser {
+            spec,
+            tokens,
+            position: 0,
+            depth: 0,
+        };
+
+        let expression = parser.parse_or()?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(expression),
+            Some((_, span)) => Err(ConditionSyntaxError { span: span.clone() }),
+        }
+    }
+
+    /// Determines if this specification uses the original, flat syntax.
+    pub fn is_legacy(spec: &str) -> bool {
+        !spec.contains(['(', ')'])
+            && !spec
+                .split_whitespace()
+                .any(|word| matches!(word, "AND" | "OR" | "NOT"))
+    }
+
+    /// Builds an expression from a list of conditions in the original syntax.
+    pub fn from_legacy(conditions: Vec<ElementCondition<'t>>) -> Self {
+        let mut all = Vec::new();
+        let mut any = Vec::new();
+
+        for ElementCondition { ctype, value } in conditions {
+            let value = ConditionExpression::Value(value);
+            match ctype {
+                ElementConditionType::Required => all.push(value),
+                ElementConditionType::Prohibited => {
+                    all.push(ConditionExpression::Not(Box::new(value)));
                 }
+                ElementConditionType::Present => any.push(value),
             }
         }
 
-        // Since this is false by default, if there are no present conditions,
-        // it's effectively true.
+        // If there are no present conditions, then it is effectively true.
         //
         // Otherwise you have to include a present condition for any iftags to pass!
-        //
-        // We could do "required && prohibited && (present || !had_present)" instead,
-        // but this if block is more readable.
-        if !had_present {
-            present = true;
+        if !any.is_empty() {
+            all.push(ConditionExpression::Any(any));
         }
 
-        required && prohibited && present
+        ConditionExpression::All(all)
+    }
+
+    /// Determines if this condition is satisfied by the given values.
+    pub fn check(&self, values: &[Cow<str>]) -> bool {
+        match self {
+            ConditionExpression::Value(pattern) => {
+                let pattern = pattern.to_lowercase();
+                values
+                    .iter()
+                    .any(|value| glob_match(&pattern, &value.to_lowercase()))
+            }
+            ConditionExpression::Not(inner) => !inner.check(values),
+            ConditionExpression::All(inner) => inner.iter().all(|e| e.check(values)),
+            ConditionExpression::Any(inner) => inner.iter().any(|e| e.check(values)),
+        }
+    }
+}
+
+/// A syntax error in a condition expression.
+///
+/// The span is relative to the start of the specification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConditionSyntaxError {
+    pub span: Range<usize>,
+}
+
+impl ConditionSyntaxError {
+    /// Converts into a parse error, pointing to the problem within the block head.
+    pub fn to_parse_error(&self, parser: &Parser, spec: &str) -> ParseError {
+        let offset = parser.full_text().offset_of(spec);
+        let span = offset + self.span.start..offset + self.span.end;
+        parser
+            .make_err(ParseErrorKind::BlockMalformedArguments)
+            .with_span(span)
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ConditionToken<'t> {
+    Value(&'t str),
+    Prohibit,
+    And,
+    Or,
+    Not,
+    LeftParen,
+    RightParen,
+}
+
+fn lex(spec: &str) -> Vec<(ConditionToken<'_>, Range<usize>)> {
+    let mut tokens = Vec::new();
+    let mut chars = spec.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let token = match c {
+            _ if c.is_whitespace() => continue,
+            '(' => ConditionToken::LeftParen,
+            ')' => ConditionToken::RightParen,
+            '-' => ConditionToken::Prohibit,
+            '+' => continue,
+            _ => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(index, c)) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    }
+
+                    end = index + c.len_utf8();
+                    chars.next();
+                }
+
+                let span = start..end;
+                let token = match &spec[span.clone()] {
+                    "AND" => ConditionToken::And,
+                    "OR" => ConditionToken::Or,
+                    "NOT" => ConditionToken::Not,
+                    value => ConditionToken::Value(value),
+                };
+
+                tokens.push((token, span));
+                continue;
+            }
+        };
+
+        tokens.push((token, start..start + c.len_utf8()));
+    }
+
+    tokens
+}
+
+/// Recursive descent parser for condition expressions.
+#[derive(Debug)]
+struct ConditionParser<'t> {
+    spec: &'t str,
+    tokens: Vec<(ConditionToken<'t>, Range<usize>)>,
+    position: usize,
+    depth: usize,
+}
+
+impl<'t> ConditionParser<'t> {
+    fn next_if(&mut self, token: ConditionToken) -> bool {
+        match self.tokens.get(self.position) {
+            Some((current, _)) if *current == token => {
+                self.position += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<ConditionExpression<'t>, ConditionSyntaxError> {
+        let mut terms = vec![self.parse_and()?];
+        while self.next_if(ConditionToken::Or) {
+            terms.push(self.parse_and()?);
+        }
+
+        Ok(flatten(terms, ConditionExpression::Any))
+    }
+
+    fn parse_and(&mut self) -> Result<ConditionExpression<'t>, ConditionSyntaxError> {
+        let mut terms = vec![self.parse_not()?];
+        while self.next_if(ConditionToken::And) {
+            terms.push(self.parse_not()?);
+        }
+
+        Ok(flatten(terms, ConditionExpression::All))
+    }
+
+    fn parse_not(&mut self) -> Result<ConditionExpression<'t>, ConditionSyntaxError> {
+        // Consecutive negations are counted rather than nested,
+        // since only whether there is an odd number of them matters.
+        let mut negated = false;
+        while self.next_if(ConditionToken::Not) || self.next_if(ConditionToken::Prohibit)
+        {
+            negated = !negated;
+        }
+
+        let inner = self.parse_primary()?;
+        if negated {
+            Ok(ConditionExpression::Not(Box::new(inner)))
+        } else {
+            Ok(inner)
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<ConditionExpression<'t>, ConditionSyntaxError> {
+        let (token, span) = match self.tokens.get(self.position) {
+            Some((token, span)) => (*token, span.clone()),
+            None => {
+                // Point to the last token, or the whole specification if there are none
+                let span = match self.tokens.last() {
+                    Some((_, span)) => span.clone(),
+                    None => 0..self.spec.len(),
+                };
+
+                return Err(ConditionSyntaxError { span });
+            }
+        };
+
+        match token {
+            ConditionToken::Value(value) => {
+                self.position += 1;
+                Ok(ConditionExpression::Value(cow!(value)))
+            }
+            ConditionToken::LeftParen => {
+                // Too deeply nested, point to the first parenthesis past the limit
+                if self.depth >= MAX_NESTING_DEPTH {
+                    return Err(ConditionSyntaxError { span });
+                }
+
+                self.position += 1;
+                self.depth += 1;
+                let inner = self.parse_or()?;
+                self.depth -= 1;
+
+                if !self.next_if(ConditionToken::RightParen) {
+                    // Unclosed, point to the opening parenthesis
+                    return Err(ConditionSyntaxError { span });
+                }
+
+                Ok(inner)
+            }
+            _ => Err(ConditionSyntaxError { span }),
+        }
+    }
+}
+
+/// Avoids wrapping a single expression in `All` or `Any`.
+fn flatten<'t, F>(
+    mut terms: Vec<ConditionExpression<'t>>,
+    f: F,
+) -> ConditionExpression<'t>
+where
+    F: FnOnce(Vec<ConditionExpression<'t>>) -> ConditionExpression<'t>,
+{
+    if terms.len() == 1 {
+        terms.pop().unwrap()
+    } else {
+        f(terms)
+    }
+}
+
+/// Matches a value against a pattern with `*` and `?` wildcards.
+fn glob_match(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let value = value.chars().collect::<Vec<_>>();
+
+    // Iterative matching, backtracking to the most recent '*'
+    let (mut p, mut v) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, v));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match star {
+                Some((star_p, star_v)) => {
+                    p = star_p + 1;
+                    v = star_v + 1;
+                    star = Some((star_p, star_v + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 #[derive(
     Serialize, Deserialize, IntoStaticStr, Debug, Copy, Clone, Hash, PartialEq, Eq,
 )]
@@ -122,3 +408,91 @@ impl ElementConditionType {
         self.into()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn glob() {
+        assert!(glob_match("series-*", "series-1"));
+        assert!(glob_match("series-*", "series-"));
+        assert!(!glob_match("series-*", "series"));
+        assert!(glob_match("*-archived", "scp-archived"));
+        assert!(glob_match("a*b*c", "aXXbYYbc"));
+        assert!(glob_match("ta?e", "tale"));
+        assert!(!glob_match("ta?e", "tae"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("scp", "scp-001"));
+    }
+
+    #[test]
+    fn check() {
+        let values = [cow!("scp"), cow!("Keter"), cow!("series-3"), cow!("_cc")];
+
+        macro_rules! check {
+            ($spec:expr, $expected:expr $(,)?) => {{
+                let spec = $spec;
+                let expression =
+                    ConditionExpression::parse(spec).expect("Unable to parse condition");
+
+                assert_eq!(
+                    expression.check(&values),
+                    $expected,
+                    "Condition {:?} evaluated incorrectly",
+                    spec,
+                );
+            }};
+        }
+
+        // Original syntax
+        check!("", true);
+        check!("scp", true);
+        check!("tale scp", true);
+        check!("+scp -_cc", false);
+        check!("+scp -tale keter", true);
+        check!("+scp +tale", false);
+        check!("series-*", true);
+
+        // Expressions
+        check!("(scp OR tale) AND NOT _hidden", true);
+        check!("(scp OR tale) AND NOT _cc", false);
+        check!("scp AND -_cc", false);
+        check!("tale OR scp AND keter", true);
+        check!("(tale OR scp) AND euclid", false);
+        check!("NOT NOT scp", true);
+        check!("NOT -NOT scp", false);
+        check!(&format!("{}scp", "NOT ".repeat(100_000)), true);
+        check!(&format!("{}scp", "NOT ".repeat(100_001)), false);
+        check!(&format!("{}scp{}", "(".repeat(100), ")".repeat(100)), true);
+        check!("series-? AND KETER", true);
+        check!("((scp))", true);
+    }
+
+    #[test]
+    fn errors() {
+        macro_rules! check {
+            ($spec:expr, $span:expr $(,)?) => {{
+                let error = ConditionExpression::parse($spec)
+                    .expect_err("Malformed condition parsed successfully");
+
+                assert_eq!(error.span, $span, "Condition {:?} has wrong span", $spec);
+            }};
+        }
+
+        check!("(scp OR tale", 0..1);
+        check!("scp OR tale)", 11..12);
+        check!("scp AND", 4..7);
+        check!("scp tale AND keter", 4..8);
+        check!("scp OR AND keter", 7..10);
+        check!("()", 1..2);
+        check!("NOT", 0..3);
+
+        // Deeply nested input is rejected at the first level past the limit
+        let spec = format!("{}scp{}", "(".repeat(100_000), ")".repeat(100_000));
+        check!(&spec, 100..101);
+
+        let spec = format!("{}scp{}", "NOT (".repeat(100_000), ")".repeat(100_000));
+        check!(&spec, 504..505);
+    }
+}
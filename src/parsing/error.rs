@@ -57,6 +57,22 @@ impl ParseError {
         }
     }
 
+    /// Creates a warning for a block found while rendering, rather than parsing.
+    ///
+    /// There is no current token at that point, so the span is given directly.
+    pub(crate) fn new_rendered(
+        kind: ParseErrorKind,
+        block_name: &'static str,
+        span: Range<usize>,
+    ) -> Self {
+        ParseError {
+            token: Token::LeftBlock,
+            rule: cow!(block_name),
+            span,
+            kind,
+        }
+    }
+
     #[inline]
     pub fn token(&self) -> Token {
         self.token
@@ -77,6 +93,15 @@ impl ParseError {
         self.kind
     }
 
+    /// Returns a copy of this error with a different span.
+    #[must_use]
+    pub fn with_span(&self, span: Range<usize>) -> Self {
+        ParseError {
+            span,
+            ..self.clone()
+        }
+    }
+
     #[must_use]
     pub fn to_utf16_indices(&self, map: &Utf16IndexMap) -> Self {
         // Copy fields
@@ -162,6 +187,12 @@ pub enum ParseErrorKind {
     /// Footnotes are not permitted from inside footnotes.
     FootnotesNested,
 
+    /// This footnote reference names a footnote which has not been defined.
+    FootnoteUndefinedReference,
+
+    /// A footnote with this name has already been defined.
+    FootnoteDuplicateName,
+
     /// This native blockquote tries to nest too deeply.
     BlockquoteDepthExceeded,
 
@@ -171,6 +202,15 @@ pub enum ParseErrorKind {
     /// Bibliography contains an element other than a definition list.
     BibliographyContainsNonDefinitionList,
 
+    /// The BibTeX in this bibliography is not valid.
+    BibliographyMalformedBibtex,
+
+    /// This citation refers to a label with no bibliography reference.
+    BibliographyCiteUndefined,
+
+    /// This bibliography reference is never cited on the page.
+    BibliographyReferenceUncited,
+
     /// There is no rule for the block name specified.
     NoSuchBlock,
 
@@ -201,6 +241,13 @@ pub enum ParseErrorKind {
     /// No embed with this name exists.
     NoSuchEmbed,
 
+    /// This iframe is not permitted by the current iframe policy.
+    ///
+    /// The element is still emitted, but will be rendered as a notice.
+    /// For `[[html]]` blocks given to an `HtmlBlockStore`, this is instead
+    /// reported by the renderer, since the URL isn't known until then.
+    IframeDisallowed,
+
     /// This no rule for the module name specified.
     NoSuchModule,
 
@@ -213,8 +260,40 @@ pub enum ParseErrorKind {
     /// The given variable was not found, and thus not substituted.
     NoSuchVariable,
 
+    /// This variable is not built-in or registered in the settings.
+    ///
+    /// It will only be substituted if an include provides it.
+    UnknownVariable,
+
     /// The URL passed here was invalid.
     InvalidUrl,
+
+    /// This expression contains a character or word which is not recognized.
+    ExpressionInvalidToken,
+
+    /// This expression has a value where an operator was expected.
+    ExpressionUnexpectedToken,
+
+    /// This expression has an operator which is missing a value.
+    ExpressionMissingOperand,
+
+    /// This expression has parentheses which are not matched.
+    ExpressionUnbalancedParentheses,
+
+    /// This expression attempts to divide by zero.
+    ExpressionDivisionByZero,
+
+    /// This expression calls a function with a value outside its domain.
+    ExpressionInvalidArgument,
+
+    /// This expression nests too many operators, functions, or parentheses.
+    ExpressionTooDeep,
+
+    /// The data form definition in this block is not valid.
+    FormMalformed,
+
+    /// A data form was already defined on this page.
+    FormAlreadyDefined,
 }
 
 impl ParseErrorKind {
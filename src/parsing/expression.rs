@@ -0,0 +1,656 @@
+/*
+ * parsing/expression.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Evaluator for the expressions used in `[[#ifexpr]]` and `[[#expr]]`.
+//!
+//! This follows Wikidot's expression syntax, with operators from lowest to highest
+//! precedence:
+//!
+//! * `or`
+//! * `and`
+//! * `=`, `!=` (or `<>`), `<`, `>`, `<=`, `>=`
+//! * `round`
+//! * `+`, `-`
+//! * `*`, `/`, `mod` (or `%`)
+//! * `^`
+//! * Unary `+`, `-`, `not`, and the functions `abs`, `ceil`, `floor`, `trunc`,
+//!   `sqrt`, `ln`, and `exp`
+//!
+//! Parentheses can be used for grouping, and `pi` and `e` are available as constants.
+//! Comparison and boolean operators produce `1` for true and `0` for false.
+
+use super::ParseErrorKind;
+use std::f64::consts::{E, PI};
+use std::ops::Range;
+
+/// The maximum number of unary operators, functions, exponents,
+/// and parentheses which may be nested within each other.
+const MAX_NESTING_DEPTH: usize = 100;
+
+/// An error produced while evaluating an expression.
+///
+/// The span is relative to the start of the expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpressionError {
+    pub kind: ParseErrorKind,
+    pub span: Range<usize>,
+}
+
+/// Evaluates the given expression into a number.
+pub fn evaluate_expression(input: &str) -> Result<f64, ExpressionError> {
+    let tokens = lex(input)?;
+    let mut parser = ExpressionParser {
+        input,
+        tokens,
+        position: 0,
+        depth: 0,
+    };
+
+    let value = parser.parse_or()?;
+    match parser.peek() {
+        None => Ok(value),
+        Some((ExpressionToken::RightParen, span)) => Err(ExpressionError {
+            kind: ParseErrorKind::ExpressionUnbalancedParentheses,
+            span,
+        }),
+        Some((_, span)) => Err(ExpressionError {
+            kind: ParseErrorKind::ExpressionUnexpectedToken,
+            span,
+        }),
+    }
+}
+
+/// Determines if a string value is considered true by `[[#if]]`.
+///
+/// Any value except an empty string, `false`, `null`, or `0` is true.
+pub fn is_truthy(value: &str) -> bool {
+    let value = value.trim();
+
+    !(value.is_empty()
+        || value == "0"
+        || value.eq_ignore_ascii_case("false")
+        || value.eq_ignore_ascii_case("null"))
+}
+
+/// Formats a number the same way Wikidot does.
+///
+/// Values are rounded to 14 significant digits, integers are written without
+/// a fractional part, and very large or small values use scientific notation,
+/// such as `1.0E+20`.
+pub fn format_number(value: f64) -> String {
+    const PRECISION: i32 = 14;
+
+    if value.is_nan() {
+        return str!("NAN");
+    }
+
+    if value.is_infinite() {
+        return str!(if value > 0.0 { "INF" } else { "-INF" });
+    }
+
+    if value == 0.0 {
+        return str!("0");
+    }
+
+    // Get the significant digits and exponent, after rounding
+    let scientific = format!("{:.*e}", (PRECISION - 1) as usize, value.abs());
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("No exponent in scientific format");
+
+    let exponent: i32 = exponent.parse().expect("Exponent is not an integer");
+    let digits = mantissa.replace('.', "");
+    let digits = digits.trim_end_matches('0');
+
+    let mut output = String::new();
+    if value < 0.0 {
+        output.push('-');
+    }
+
+    if (-4..PRECISION).contains(&exponent) {
+        // Fixed notation
+        if exponent < 0 {
+            output.push_str("0.");
+            for _ in 0..(-exponent - 1) {
+                output.push('0');
+            }
+            output.push_str(digits);
+        } else {
+            let integer_length = (exponent + 1) as usize;
+            if digits.len() <= integer_length {
+                output.push_str(digits);
+                for _ in digits.len()..integer_length {
+                    output.push('0');
+                }
+            } else {
+                output.push_str(&digits[..integer_length]);
+                output.push('.');
+                output.push_str(&digits[integer_length..]);
+            }
+        }
+    } else {
+        // Scientific notation
+        let (first, rest) = digits.split_at(1);
+        let rest = if rest.is_empty() { "0" } else { rest };
+        let sign = if exponent < 0 { '-' } else { '+' };
+        str_write!(output, "{first}.{rest}E{sign}{}", exponent.abs());
+    }
+
+    output
+}
+
+#[derive(Debug, Copy, Clone)]
+enum ExpressionToken {
+    Number(f64),
+    Constant(f64),
+    Function(fn(f64) -> Option<f64>),
+    Operator(Operator),
+    LeftParen,
+    RightParen,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Operator {
+    Or,
+    And,
+    Not,
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    Round,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Power,
+}
+
+fn lex(input: &str) -> Result<Vec<(ExpressionToken, Range<usize>)>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        // Skip whitespace
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        // Numbers, including scientific notation
+        if c.is_ascii_digit() || c == '.' {
+            let number = regex!(r"^(?:[0-9]+\.?[0-9]*|\.[0-9]+)(?:[eE][+\-]?[0-9]+)?");
+            let span = match number.find(&input[start..]) {
+                Some(mtch) => start..start + mtch.end(),
+                None => {
+                    return Err(ExpressionError {
+                        kind: ParseErrorKind::ExpressionInvalidToken,
+                        span: start..start + 1,
+                    });
+                }
+            };
+
+            let value = input[span.clone()]
+                .parse()
+                .expect("Number regex matched invalid float");
+
+            while chars.peek().is_some_and(|&(index, _)| index < span.end) {
+                chars.next();
+            }
+
+            tokens.push((ExpressionToken::Number(value), span));
+            continue;
+        }
+
+        // Words, such as operators and functions
+        if c.is_ascii_alphabetic() {
+            let mut end = start;
+            while let Some(&(index, c)) = chars.peek() {
+                if !c.is_ascii_alphabetic() {
+                    break;
+                }
+
+                end = index + c.len_utf8();
+                chars.next();
+            }
+
+            let span = start..end;
+            let token = match input[span.clone()].to_ascii_lowercase().as_str() {
+                "or" => ExpressionToken::Operator(Operator::Or),
+                "and" => ExpressionToken::Operator(Operator::And),
+                "not" => ExpressionToken::Operator(Operator::Not),
+                "round" => ExpressionToken::Operator(Operator::Round),
+                "mod" => ExpressionToken::Operator(Operator::Modulo),
+                "pi" => ExpressionToken::Constant(PI),
+                "e" => ExpressionToken::Constant(E),
+                "abs" => ExpressionToken::Function(|x| Some(x.abs())),
+                "ceil" => ExpressionToken::Function(|x| Some(x.ceil())),
+                "floor" => ExpressionToken::Function(|x| Some(x.floor())),
+                "trunc" => ExpressionToken::Function(|x| Some(x.trunc())),
+                "sqrt" => ExpressionToken::Function(|x| (x >= 0.0).then(|| x.sqrt())),
+                "ln" => ExpressionToken::Function(|x| (x > 0.0).then(|| x.ln())),
+                "exp" => ExpressionToken::Function(|x| Some(x.exp())),
+                _ => {
+                    return Err(ExpressionError {
+                        kind: ParseErrorKind::ExpressionInvalidToken,
+                        span,
+                    });
+                }
+            };
+
+            tokens.push((token, span));
+            continue;
+        }
+
+        // Symbols
+        chars.next();
+        let next = chars.peek().map(|&(_, c)| c);
+        let (token, length) = match (c, next) {
+            ('<', Some('=')) => (ExpressionToken::Operator(Operator::LessEqual), 2),
+            ('>', Some('=')) => (ExpressionToken::Operator(Operator::GreaterEqual), 2),
+            ('<', Some('>')) | ('!', Some('=')) => {
+                (ExpressionToken::Operator(Operator::NotEqual), 2)
+            }
+            ('=', Some('=')) => (ExpressionToken::Operator(Operator::Equal), 2),
+            ('=', _) => (ExpressionToken::Operator(Operator::Equal), 1),
+            ('<', _) => (ExpressionToken::Operator(Operator::Less), 1),
+            ('>', _) => (ExpressionToken::Operator(Operator::Greater), 1),
+            ('+', _) => (ExpressionToken::Operator(Operator::Add), 1),
+            ('-', _) => (ExpressionToken::Operator(Operator::Subtract), 1),
+            ('*', _) => (ExpressionToken::Operator(Operator::Multiply), 1),
+            ('/', _) => (ExpressionToken::Operator(Operator::Divide), 1),
+            ('%', _) => (ExpressionToken::Operator(Operator::Modulo), 1),
+            ('^', _) => (ExpressionToken::Operator(Operator::Power), 1),
+            ('(', _) => (ExpressionToken::LeftParen, 1),
+            (')', _) => (ExpressionToken::RightParen, 1),
+            _ => {
+                return Err(ExpressionError {
+                    kind: ParseErrorKind::ExpressionInvalidToken,
+                    span: start..start + c.len_utf8(),
+                });
+            }
+        };
+
+        if length == 2 {
+            chars.next();
+        }
+
+        tokens.push((token, start..start + length));
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser which evaluates as it goes.
+#[derive(Debug)]
+struct ExpressionParser<'a> {
+    input: &'a str,
+    tokens: Vec<(ExpressionToken, Range<usize>)>,
+    position: usize,
+    depth: usize,
+}
+
+impl ExpressionParser<'_> {
+    fn peek(&self) -> Option<(ExpressionToken, Range<usize>)> {
+        self.tokens.get(self.position).cloned()
+    }
+
+    fn next_operator(
+        &mut self,
+        operators: &[Operator],
+    ) -> Option<(Operator, Range<usize>)> {
+        match self.peek() {
+            Some((ExpressionToken::Operator(operator), span))
+                if operators.contains(&operator) =>
+            {
+                self.position += 1;
+                Some((operator, span))
+            }
+            _ => None,
+        }
+    }
+
+    /// Runs a parsing method one level deeper, failing if there are too many levels.
+    ///
+    /// This keeps deeply nested input from overflowing the stack.
+    fn parse_nested(
+        &mut self,
+        span: Range<usize>,
+        parse: fn(&mut Self) -> Result<f64, ExpressionError>,
+    ) -> Result<f64, ExpressionError> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(ExpressionError {
+                kind: ParseErrorKind::ExpressionTooDeep,
+                span,
+            });
+        }
+
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_or(&mut self) -> Result<f64, ExpressionError> {
+        let mut value = self.parse_and()?;
+        while self.next_operator(&[Operator::Or]).is_some() {
+            let right = self.parse_and()?;
+            value = from_bool(value != 0.0 || right != 0.0);
+        }
+
+        Ok(value)
+    }
+
+    fn parse_and(&mut self) -> Result<f64, ExpressionError> {
+        let mut value = self.parse_comparison()?;
+        while self.next_operator(&[Operator::And]).is_some() {
+            let right = self.parse_comparison()?;
+            value = from_bool(value != 0.0 && right != 0.0);
+        }
+
+        Ok(value)
+    }
+
+    fn parse_comparison(&mut self) -> Result<f64, ExpressionError> {
+        const OPERATORS: [Operator; 6] = [
+            Operator::Equal,
+            Operator::NotEqual,
+            Operator::Less,
+            Operator::Greater,
+            Operator::LessEqual,
+            Operator::GreaterEqual,
+        ];
+
+        let mut value = self.parse_round()?;
+        while let Some((operator, _)) = self.next_operator(&OPERATORS) {
+            let right = self.parse_round()?;
+            value = from_bool(match operator {
+                Operator::Equal => value == right,
+                Operator::NotEqual => value != right,
+                Operator::Less => value < right,
+                Operator::Greater => value > right,
+                Operator::LessEqual => value <= right,
+                Operator::GreaterEqual => value >= right,
+                _ => unreachable!(),
+            });
+        }
+
+        Ok(value)
+    }
+
+    fn parse_round(&mut self) -> Result<f64, ExpressionError> {
+        let mut value = self.parse_additive()?;
+        while self.next_operator(&[Operator::Round]).is_some() {
+            let places = self.parse_additive()?.trunc();
+            let factor = 10f64.powf(places);
+            value = (value * factor).round() / factor;
+        }
+
+        Ok(value)
+    }
+
+    fn parse_additive(&mut self) -> Result<f64, ExpressionError> {
+        let mut value = self.parse_multiplicative()?;
+        while let Some((operator, _)) =
+            self.next_operator(&[Operator::Add, Operator::Subtract])
+        {
+            let right = self.parse_multiplicative()?;
+            value = match operator {
+                Operator::Add => value + right,
+                Operator::Subtract => value - right,
+                _ => unreachable!(),
+            };
+        }
+
+        Ok(value)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<f64, ExpressionError> {
+        const OPERATORS: [Operator; 3] =
+            [Operator::Multiply, Operator::Divide, Operator::Modulo];
+
+        let mut value = self.parse_unary()?;
+        while let Some((operator, span)) = self.next_operator(&OPERATORS) {
+            let right = self.parse_unary()?;
+            value = match operator {
+                Operator::Multiply => value * right,
+                Operator::Divide if right == 0.0 => return Err(division_by_zero(span)),
+                Operator::Divide => value / right,
+
+                // Like Wikidot, the modulo operates on integers
+                Operator::Modulo if right.trunc() == 0.0 => {
+                    return Err(division_by_zero(span));
+                }
+                Operator::Modulo => value.trunc() % right.trunc(),
+                _ => unreachable!(),
+            };
+        }
+
+        Ok(value)
+    }
+
+    fn parse_unary(&mut self) -> Result<f64, ExpressionError> {
+        match self.peek() {
+            Some((ExpressionToken::Operator(Operator::Add), span)) => {
+                self.position += 1;
+                self.parse_nested(span, Self::parse_unary)
+            }
+            Some((ExpressionToken::Operator(Operator::Subtract), span)) => {
+                self.position += 1;
+                Ok(-self.parse_nested(span, Self::parse_unary)?)
+            }
+            Some((ExpressionToken::Operator(Operator::Not), span)) => {
+                self.position += 1;
+                Ok(from_bool(
+                    self.parse_nested(span, Self::parse_unary)? == 0.0,
+                ))
+            }
+            Some((ExpressionToken::Function(function), span)) => {
+                self.position += 1;
+                let value = self.parse_nested(span.clone(), Self::parse_unary)?;
+                function(value).ok_or(ExpressionError {
+                    kind: ParseErrorKind::ExpressionInvalidArgument,
+                    span,
+                })
+            }
+            _ => self.parse_power(),
+        }
+    }
+
+    fn parse_power(&mut self) -> Result<f64, ExpressionError> {
+        let base = self.parse_primary()?;
+        if let Some((_, span)) = self.next_operator(&[Operator::Power]) {
+            // Right-associative, and allows a signed exponent
+            let exponent = self.parse_nested(span, Self::parse_unary)?;
+            return Ok(base.powf(exponent));
+        }
+
+        Ok(base)
+    }
+
+    fn parse_primary(&mut self) -> Result<f64, ExpressionError> {
+        let (token, span) = match self.peek() {
+            Some(item) => item,
+            None => {
+                // Point to the last operator, or the whole input if there are none
+                let span = match self.tokens.last() {
+                    Some((_, span)) => span.clone(),
+                    None => 0..self.input.len(),
+                };
+
+                return Err(ExpressionError {
+                    kind: ParseErrorKind::ExpressionMissingOperand,
+                    span,
+                });
+            }
+        };
+
+        self.position += 1;
+        match token {
+            ExpressionToken::Number(value) | ExpressionToken::Constant(value) => {
+                Ok(value)
+            }
+            ExpressionToken::LeftParen => {
+                let value = self.parse_nested(span.clone(), Self::parse_or)?;
+                match self.peek() {
+                    Some((ExpressionToken::RightParen, _)) => {
+                        self.position += 1;
+                        Ok(value)
+                    }
+                    _ => Err(ExpressionError {
+                        kind: ParseErrorKind::ExpressionUnbalancedParentheses,
+                        span,
+                    }),
+                }
+            }
+            ExpressionToken::RightParen | ExpressionToken::Operator(_) => {
+                Err(ExpressionError {
+                    kind: ParseErrorKind::ExpressionMissingOperand,
+                    span,
+                })
+            }
+            ExpressionToken::Function(_) => unreachable!(),
+        }
+    }
+}
+
+#[inline]
+fn from_bool(value: bool) -> f64 {
+    if value { 1.0 } else { 0.0 }
+}
+
+#[inline]
+fn division_by_zero(span: Range<usize>) -> ExpressionError {
+    ExpressionError {
+        kind: ParseErrorKind::ExpressionDivisionByZero,
+        span,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn evaluate() {
+        macro_rules! check {
+            ($input:expr, $expected:expr $(,)?) => {{
+                let value = evaluate_expression($input).expect("Unable to evaluate");
+                assert_eq!(
+                    format_number(value),
+                    $expected,
+                    "Expression {:?} evaluated incorrectly",
+                    $input,
+                );
+            }};
+        }
+
+        check!("1 + 2", "3");
+        check!("1 + 2 * 3", "7");
+        check!("(1 + 2) * 3", "9");
+        check!("2 ^ 3 ^ 2", "512");
+        check!("-2 ^ 2", "-4");
+        check!("2 ^ -1", "0.5");
+        check!("1 / 3", "0.33333333333333");
+        check!("2 / 3", "0.66666666666667");
+        check!("7 mod 3", "1");
+        check!("7.9 % 3", "1");
+        check!("10 / 4", "2.5");
+        check!("1e20", "1.0E+20");
+        check!("1.5e-7", "1.5E-7");
+        check!("0.0001", "0.0001");
+        check!("12345678901234", "12345678901234");
+        check!("123456789012344", "1.2345678901234E+14");
+        check!("pi", "3.1415926535898");
+        check!("pi round 2", "3.14");
+        check!("1 + 1 round 0", "2");
+        check!("abs -5 + 1", "6");
+        check!("floor 2.7 + ceil 2.2", "5");
+        check!("3 > 2", "1");
+        check!("3 <= 2", "0");
+        check!("2 <> 2", "0");
+        check!("1 + 1 = 2 and 2 != 3", "1");
+        check!("0 or 0", "0");
+        check!("not 0", "1");
+        check!("NOT 1 OR 1", "1");
+        check!("1 = 1 = 1", "1");
+        check!(&format!("{}1", "not ".repeat(100)), "1");
+    }
+
+    #[test]
+    fn errors() {
+        macro_rules! check {
+            ($input:expr, $kind:ident, $span:expr $(,)?) => {{
+                let error = evaluate_expression($input)
+                    .expect_err("Malformed expression evaluated successfully");
+
+                assert_eq!(
+                    error,
+                    ExpressionError {
+                        kind: ParseErrorKind::$kind,
+                        span: $span,
+                    },
+                    "Expression {:?} produced the wrong error",
+                    $input,
+                );
+            }};
+        }
+
+        check!("", ExpressionMissingOperand, 0..0);
+        check!("1 +", ExpressionMissingOperand, 2..3);
+        check!("1 + * 2", ExpressionMissingOperand, 4..5);
+        check!("(1 + 2", ExpressionUnbalancedParentheses, 0..1);
+        check!("1 + 2)", ExpressionUnbalancedParentheses, 5..6);
+        check!("1 2", ExpressionUnexpectedToken, 2..3);
+        check!("1 + apple", ExpressionInvalidToken, 4..9);
+        check!("1 $ 2", ExpressionInvalidToken, 2..3);
+        check!("1 / 0", ExpressionDivisionByZero, 2..3);
+        check!("5 mod 0.5", ExpressionDivisionByZero, 2..5);
+        check!("sqrt -1", ExpressionInvalidArgument, 0..4);
+
+        // Deeply nested input is rejected at the first level past the limit
+        let input = format!("{}1", "not ".repeat(100_000));
+        check!(&input, ExpressionTooDeep, 400..403);
+
+        let input = format!("{}1", "(".repeat(100_000));
+        check!(&input, ExpressionTooDeep, 100..101);
+
+        let input = format!("{}1", "-abs ".repeat(100_000));
+        check!(&input, ExpressionTooDeep, 250..251);
+
+        let input = format!("2{}", " ^ 2".repeat(100_000));
+        check!(&input, ExpressionTooDeep, 402..403);
+    }
+
+    #[test]
+    fn truthy() {
+        assert!(is_truthy("yes"));
+        assert!(is_truthy(" 1 "));
+        assert!(is_truthy("{$missing}"));
+        assert!(!is_truthy(""));
+        assert!(!is_truthy("  "));
+        assert!(!is_truthy("0"));
+        assert!(!is_truthy("false"));
+        assert!(!is_truthy("NULL"));
+    }
+}
@@ -29,6 +29,7 @@ mod consume;
 mod depth;
 mod element_condition;
 mod error;
+mod expression;
 mod outcome;
 mod paragraph;
 mod parser;
@@ -37,6 +38,7 @@ mod result;
 mod rule;
 mod string;
 mod strip;
+mod toc;
 mod token;
 
 mod prelude {
@@ -49,21 +51,24 @@ mod prelude {
 }
 
 use self::depth::{DepthItem, DepthList, process_depths};
-use self::element_condition::{ElementCondition, ElementConditionType};
+use self::element_condition::{
+    ConditionExpression, ElementCondition, ElementConditionType,
+};
 use self::paragraph::{NO_CLOSE_CONDITION, gather_paragraphs};
 use self::parser::Parser;
 use self::parser_wrap::ParserWrap;
 use self::rule::impls::RULE_PAGE;
 use self::strip::{strip_newlines, strip_whitespace};
+use self::toc::TocAnchors;
 use crate::data::PageInfo;
-use crate::next_index::{Incrementer, NextIndex};
 use crate::settings::WikitextSettings;
 use crate::tokenizer::Tokenization;
 use crate::tree::{
-    AttributeMap, BibliographyList, CodeBlock, Element, LinkLabel, LinkLocation,
-    LinkType, ListItem, ListType, SyntaxTree,
+    AttributeMap, BibliographyList, CodeBlock, Element, Form, HeadingSection, LinkLabel,
+    LinkLocation, LinkType, ListItem, ListType, SyntaxTree, set_section_ends,
 };
 use std::borrow::Cow;
+use std::num::NonZeroUsize;
 
 pub use self::boolean::{NonBooleanValue, parse_boolean};
 pub use self::error::{ParseError, ParseErrorKind};
@@ -88,13 +93,16 @@ where
         html_blocks,
         code_blocks,
         table_of_contents_depths,
+        mut headings,
         footnotes,
+        footnote_refs,
         has_footnote_block,
         bibliographies,
+        form,
     } = parse_internal(page_info, settings, tokenization);
 
     // Mutable state
-    let mut toc_indexer = settings.id_indexer();
+    let mut toc_anchors = TocAnchors::new(settings);
 
     debug!("Finished paragraph gathering, matching on consumption");
     match result {
@@ -116,9 +124,12 @@ where
             // Convert TOC depth lists
             let table_of_contents = process_depths((), table_of_contents_depths)
                 .into_iter()
-                .map(|(_, items)| build_toc_list_element(&mut toc_indexer, items))
+                .map(|(_, items)| build_toc_list_element(&mut toc_anchors, items))
                 .collect::<Vec<_>>();
 
+            // Each heading's section runs until the next one at its level or above.
+            set_section_ends(&mut headings, tokenization.full_text().len());
+
             // This flag notes that the renderer needs to insert its own footnote
             // block. This happens when there are footnotes to be rendered, but
             // there isn't a manually-placed footnote block somewhere in the elements.
@@ -128,9 +139,9 @@ where
                 elements,
                 errors,
                 (html_blocks, code_blocks),
-                table_of_contents,
-                (footnotes, needs_footnote_block),
-                bibliographies,
+                (table_of_contents, headings),
+                (footnotes, footnote_refs, needs_footnote_block),
+                (bibliographies, form),
                 tokenization.full_text().len(),
             )
         }
@@ -145,17 +156,20 @@ where
             let elements = vec![text!(wikitext)];
             let errors = vec![error];
             let table_of_contents = vec![];
+            let headings = vec![];
             let footnotes = vec![];
+            let footnote_refs = vec![];
             let needs_footnote_block = true;
             let bibliographies = BibliographyList::new();
+            let form = None;
 
             SyntaxTree::from_element_result(
                 elements,
                 errors,
                 (html_blocks, code_blocks),
-                table_of_contents,
-                (footnotes, needs_footnote_block),
-                bibliographies,
+                (table_of_contents, headings),
+                (footnotes, footnote_refs, needs_footnote_block),
+                (bibliographies, form),
                 tokenization.full_text().len(),
             )
         }
@@ -175,41 +189,54 @@ where
 
     // At the top level, we gather elements into paragraphs
     info!("Running parser on {} tokens", tokenization.tokens().len());
-    let result = gather_paragraphs(&mut parser, RULE_PAGE, NO_CLOSE_CONDITION);
+    let mut result = gather_paragraphs(&mut parser, RULE_PAGE, NO_CLOSE_CONDITION);
+
+    // Warn about missing or unused bibliography references
+    if let Ok(ref mut success) = result {
+        success
+            .errors
+            .append(&mut parser.check_bibliography_labels());
+    }
 
     // Build and return
     let html_blocks = parser.remove_html_blocks();
     let code_blocks = parser.remove_code_blocks();
     let table_of_contents_depths = parser.remove_table_of_contents();
+    let headings = parser.remove_headings();
     let footnotes = parser.remove_footnotes();
+    let footnote_refs = parser.remove_footnote_refs();
     let has_footnote_block = parser.has_footnote_block();
     let bibliographies = parser.remove_bibliographies();
+    let form = parser.remove_form();
 
     UnstructuredParseResult {
         result,
         html_blocks,
         code_blocks,
         table_of_contents_depths,
+        headings,
         footnotes,
+        footnote_refs,
         has_footnote_block,
         bibliographies,
+        form,
     }
 }
 
 // Helper functions
 
 fn build_toc_list_element(
-    incr: &mut Incrementer,
+    anchors: &mut TocAnchors,
     list: DepthList<(), String>,
 ) -> Element<'static> {
     let build_item = |item| match item {
         DepthItem::List(_, list) => ListItem::SubList {
-            element: Box::new(build_toc_list_element(incr, list)),
+            element: Box::new(build_toc_list_element(anchors, list)),
         },
         DepthItem::Item(name) => {
-            let anchor = match incr.next() {
+            let anchor = match anchors.next(&name) {
                 None => Cow::Borrowed("javascript:;"),
-                Some(index) => Cow::Owned(format!("#toc{index}")),
+                Some(id) => Cow::Owned(format!("#{id}")),
             };
             let link = Element::Link {
                 ltype: LinkType::TableOfContents,
@@ -252,12 +279,22 @@ pub struct UnstructuredParseResult<'r, 't> {
     /// Each value is a zero-indexed depth of how
     pub table_of_contents_depths: Vec<(usize, String)>,
 
+    /// The list of headings, in document order.
+    ///
+    /// See `src/tree/section.rs`.
+    pub headings: Vec<HeadingSection>,
+
     /// The list of footnotes.
     ///
     /// Each entry is a series of elements, in combination
     /// they make the contents of one footnote.
     pub footnotes: Vec<Vec<Element<'t>>>,
 
+    /// The list of footnote references.
+    ///
+    /// Each entry is the index of a named footnote cited again.
+    pub footnote_refs: Vec<NonZeroUsize>,
+
     /// Whether a footnote block was placed during parsing.
     pub has_footnote_block: bool,
 
@@ -265,4 +302,9 @@ pub struct UnstructuredParseResult<'r, 't> {
     ///
     /// See `src/tree/bibliography.rs`.
     pub bibliographies: BibliographyList<'t>,
+
+    /// The data form defined on this page, if any.
+    ///
+    /// See `src/tree/form.rs`.
+    pub form: Option<Form<'t>>,
 }
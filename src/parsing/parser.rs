@@ -26,10 +26,13 @@ use crate::data::PageInfo;
 use crate::render::text::TextRender;
 use crate::tokenizer::Tokenization;
 use crate::tree::{
-    AcceptsPartial, Bibliography, BibliographyList, CodeBlock, HeadingLevel,
+    AcceptsPartial, Bibliography, BibliographyList, CodeBlock, Form, Heading,
+    HeadingSection,
 };
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::rc::Rc;
 use std::{mem, ptr};
 
@@ -61,6 +64,11 @@ pub struct Parser<'r, 't> {
     //       here preserved across parser child instances.
     table_of_contents: Rc<RefCell<Vec<(usize, String)>>>,
 
+    // Every heading, including those without a table of contents entry.
+    //
+    // Section spans only have their start set until parsing finishes.
+    headings: Rc<RefCell<Vec<HeadingSection>>>,
+
     // HTML blocks with data to expose
     html_blocks: Rc<RefCell<Vec<Cow<'t, str>>>>,
 
@@ -72,6 +80,14 @@ pub struct Parser<'r, 't> {
     // Schema: Vec<List of elements in a footnote>
     footnotes: Rc<RefCell<Vec<Vec<Element<'t>>>>>,
 
+    // Named footnotes
+    //
+    // Schema: HashMap<name, footnote index>
+    footnote_names: Rc<RefCell<HashMap<Cow<'t, str>, NonZeroUsize>>>,
+
+    // Footnote references, each an index of the footnote being cited again
+    footnote_refs: Rc<RefCell<Vec<NonZeroUsize>>>,
+
     // Bibliographies
     //
     // Each bibliography block is separate, but the citations
@@ -79,6 +95,18 @@ pub struct Parser<'r, 't> {
     // overriding later ones.
     bibliographies: Rc<RefCell<BibliographyList<'t>>>,
 
+    // Bibliography labels which were cited, and which were defined
+    //
+    // These are compared after parsing, to warn about
+    // undefined citations and unused references.
+    bibliography_cites: Rc<RefCell<Vec<BibliographyLabel<'t>>>>,
+    bibliography_definitions: Rc<RefCell<Vec<BibliographyLabel<'t>>>>,
+
+    // Data form
+    //
+    // Only one [[form]] block is permitted per page.
+    form: Rc<RefCell<Option<Form<'t>>>>,
+
     // Flags
     accepts_partial: AcceptsPartial,
     in_footnote: bool, // Whether we're currently inside [[footnote]] ... [[/footnote]].
@@ -111,10 +139,16 @@ impl<'r, 't> Parser<'r, 't> {
             rule: RULE_PAGE,
             depth: 0,
             table_of_contents: make_shared_vec(),
+            headings: make_shared_vec(),
             html_blocks: make_shared_vec(),
             code_blocks: make_shared_vec(),
             footnotes: make_shared_vec(),
+            footnote_names: Rc::new(RefCell::new(HashMap::new())),
+            footnote_refs: make_shared_vec(),
             bibliographies: Rc::new(RefCell::new(BibliographyList::new())),
+            bibliography_cites: make_shared_vec(),
+            bibliography_definitions: make_shared_vec(),
+            form: Rc::new(RefCell::new(None)),
             accepts_partial: AcceptsPartial::None,
             in_footnote: false,
             has_footnote_block: false,
@@ -129,7 +163,7 @@ impl<'r, 't> Parser<'r, 't> {
     }
 
     #[inline]
-    pub fn settings(&self) -> &WikitextSettings {
+    pub fn settings(&self) -> &'r WikitextSettings {
         self.settings
     }
 
@@ -213,9 +247,13 @@ impl<'r, 't> Parser<'r, 't> {
     pub fn get_mutable_state(&self) -> ParserMutableState {
         ParserMutableState {
             footnote_index: self.footnotes.borrow().len(),
+            footnote_ref_index: self.footnote_refs.borrow().len(),
+            bibliography_cite_index: self.bibliography_cites.borrow().len(),
+            bibliography_definition_index: self.bibliography_definitions.borrow().len(),
             html_block_index: self.html_blocks.borrow().len(),
             code_block_index: self.code_blocks.borrow().len(),
             table_of_contents_index: self.table_of_contents.borrow().len(),
+            heading_index: self.headings.borrow().len(),
         }
     }
 
@@ -226,17 +264,29 @@ impl<'r, 't> Parser<'r, 't> {
         &mut self,
         ParserMutableState {
             footnote_index,
+            footnote_ref_index,
+            bibliography_cite_index,
+            bibliography_definition_index,
             html_block_index,
             code_block_index,
             table_of_contents_index,
+            heading_index,
         }: ParserMutableState,
     ) {
-        self.footnotes.borrow_mut().truncate(footnote_index);
+        self.truncate_footnotes(footnote_index);
+        self.footnote_refs.borrow_mut().truncate(footnote_ref_index);
+        self.bibliography_cites
+            .borrow_mut()
+            .truncate(bibliography_cite_index);
+        self.bibliography_definitions
+            .borrow_mut()
+            .truncate(bibliography_definition_index);
         self.html_blocks.borrow_mut().truncate(html_block_index);
         self.code_blocks.borrow_mut().truncate(code_block_index);
         self.table_of_contents
             .borrow_mut()
             .truncate(table_of_contents_index);
+        self.headings.borrow_mut().truncate(heading_index);
     }
 
     // Parse settings helpers
@@ -248,20 +298,40 @@ impl<'r, 't> Parser<'r, 't> {
         }
     }
 
-    /// Add heading element to table of contents.
-    pub fn push_table_of_contents_entry(
+    /// Add a heading to the list of headings.
+    ///
+    /// If it wants one, it also gets a table of contents entry.
+    pub fn push_heading(
         &mut self,
-        heading: HeadingLevel,
+        heading: Heading,
+        start: usize,
         name_elements: &[Element],
     ) {
-        // Headings are 1-indexed (e.g. H1), but depth lists are 0-indexed
-        let level = usize::from(heading.value()) - 1;
-
         // Render name as text, so it lacks formatting
         let name =
             TextRender.render_partial(name_elements, self.page_info, self.settings, 0);
 
-        self.table_of_contents.borrow_mut().push((level, name));
+        let toc_index = if heading.has_toc {
+            // Headings are 1-indexed (e.g. H1), but depth lists are 0-indexed
+            let level = usize::from(heading.level.value()) - 1;
+            let mut guard = self.table_of_contents.borrow_mut();
+            guard.push((level, name.clone()));
+            Some(guard.len() - 1)
+        } else {
+            None
+        };
+
+        // Headings directly on the page are consumed at the first level,
+        // anything deeper is within a block such as [[div]].
+        let editable = self.depth <= 1;
+
+        self.headings.borrow_mut().push(HeadingSection {
+            heading,
+            name,
+            toc_index,
+            editable,
+            span: start..start,
+        });
     }
 
     #[cold]
@@ -279,9 +349,28 @@ impl<'r, 't> Parser<'r, 't> {
         mem::take(&mut self.table_of_contents.borrow_mut())
     }
 
+    #[cold]
+    pub fn remove_headings(&mut self) -> Vec<HeadingSection> {
+        mem::take(&mut self.headings.borrow_mut())
+    }
+
     // Footnotes
-    pub fn push_footnote(&mut self, contents: Vec<Element<'t>>) {
-        self.footnotes.borrow_mut().push(contents);
+    pub fn push_footnote(&mut self, contents: Vec<Element<'t>>) -> NonZeroUsize {
+        let mut guard = self.footnotes.borrow_mut();
+        guard.push(contents);
+        NonZeroUsize::new(guard.len()).unwrap()
+    }
+
+    pub fn push_footnote_name(&mut self, name: Cow<'t, str>, index: NonZeroUsize) {
+        self.footnote_names.borrow_mut().insert(name, index);
+    }
+
+    pub fn get_footnote_index(&self, name: &str) -> Option<NonZeroUsize> {
+        self.footnote_names.borrow().get(name).copied()
+    }
+
+    pub fn push_footnote_ref(&mut self, index: NonZeroUsize) {
+        self.footnote_refs.borrow_mut().push(index);
     }
 
     pub fn footnote_count(&self) -> usize {
@@ -290,6 +379,9 @@ impl<'r, 't> Parser<'r, 't> {
 
     pub fn truncate_footnotes(&mut self, count: usize) {
         self.footnotes.borrow_mut().truncate(count);
+        self.footnote_names
+            .borrow_mut()
+            .retain(|_, index| index.get() <= count);
     }
 
     #[cold]
@@ -297,6 +389,11 @@ impl<'r, 't> Parser<'r, 't> {
         mem::take(&mut self.footnotes.borrow_mut())
     }
 
+    #[cold]
+    pub fn remove_footnote_refs(&mut self) -> Vec<NonZeroUsize> {
+        mem::take(&mut self.footnote_refs.borrow_mut())
+    }
+
     // Blocks
     pub fn push_html_block(&mut self, new_block: Cow<'t, str>) {
         self.html_blocks.borrow_mut().push(new_block);
@@ -318,11 +415,72 @@ impl<'r, 't> Parser<'r, 't> {
         index
     }
 
+    pub fn push_bibliography_cite(&mut self, label: Cow<'t, str>, error: ParseError) {
+        self.bibliography_cites
+            .borrow_mut()
+            .push(BibliographyLabel { label, error });
+    }
+
+    pub fn push_bibliography_definition(
+        &mut self,
+        label: Cow<'t, str>,
+        error: ParseError,
+    ) {
+        self.bibliography_definitions
+            .borrow_mut()
+            .push(BibliographyLabel { label, error });
+    }
+
+    /// Produces warnings for citations without a reference,
+    /// and references which are never cited.
+    ///
+    /// Must be called before `remove_bibliographies()`.
+    #[cold]
+    pub fn check_bibliography_labels(&self) -> Vec<ParseError> {
+        let bibliographies = self.bibliographies.borrow();
+        let cites = self.bibliography_cites.borrow();
+        let definitions = self.bibliography_definitions.borrow();
+        let mut errors = Vec::new();
+
+        for cite in cites.iter() {
+            if bibliographies.get_reference(&cite.label).is_none() {
+                warn!("Bibliography citation '{}' has no reference", cite.label);
+                errors.push(cite.error.clone());
+            }
+        }
+
+        for definition in definitions.iter() {
+            if !cites.iter().any(|cite| cite.label == definition.label) {
+                warn!(
+                    "Bibliography reference '{}' is never cited",
+                    definition.label
+                );
+                errors.push(definition.error.clone());
+            }
+        }
+
+        errors
+    }
+
     #[cold]
     pub fn remove_bibliographies(&mut self) -> BibliographyList<'t> {
         mem::take(&mut self.bibliographies.borrow_mut())
     }
 
+    // Data form
+    pub fn has_form(&self) -> bool {
+        self.form.borrow().is_some()
+    }
+
+    pub fn set_form(&mut self, form: Form<'t>) {
+        *self.form.borrow_mut() = Some(form);
+    }
+
+    #[cold]
+    pub fn remove_form(&mut self) -> Option<Form<'t>> {
+        self.form.borrow_mut().take()
+    }
+
     // Special for [[include]], appending a SyntaxTree
     pub fn append_shared_items(
         &mut self,
@@ -330,6 +488,7 @@ impl<'r, 't> Parser<'r, 't> {
         code_blocks: &mut Vec<CodeBlock<'t>>,
         table_of_contents: &mut Vec<(usize, String)>,
         footnotes: &mut Vec<Vec<Element<'t>>>,
+        footnote_refs: &mut Vec<NonZeroUsize>,
         bibliographies: &mut BibliographyList<'t>,
     ) {
         self.html_blocks.borrow_mut().append(html_blocks);
@@ -342,6 +501,8 @@ impl<'r, 't> Parser<'r, 't> {
 
         self.footnotes.borrow_mut().append(footnotes);
 
+        self.footnote_refs.borrow_mut().append(footnote_refs);
+
         self.bibliographies.borrow_mut().append(bibliographies);
     }
 
@@ -611,9 +772,23 @@ impl<'r, 't> Parser<'r, 't> {
 #[derive(Debug, Copy, Clone)]
 pub struct ParserMutableState {
     footnote_index: usize,
+    footnote_ref_index: usize,
+    bibliography_cite_index: usize,
+    bibliography_definition_index: usize,
     html_block_index: usize,
     code_block_index: usize,
     table_of_contents_index: usize,
+    heading_index: usize,
+}
+
+/// A bibliography label which was cited or defined.
+///
+/// The error is the warning to produce if it turns out to be
+/// undefined (for citations) or unused (for definitions).
+#[derive(Debug, Clone)]
+struct BibliographyLabel<'t> {
+    label: Cow<'t, str>,
+    error: ParseError,
 }
 
 #[inline]
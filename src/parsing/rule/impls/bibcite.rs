@@ -35,7 +35,7 @@ fn try_consume_fn<'r, 't>(
     // This is like a poor man's block, it's "((bibcite <label>))"
     let current = parser.current();
     if current.token != Token::Identifier
-        && !current.slice.eq_ignore_ascii_case("bibcite")
+        || !current.slice.eq_ignore_ascii_case("bibcite")
     {
         warn!("'((' not followed by 'bibcite', failing rule");
         return Err(parser.make_err(ParseErrorKind::RuleFailed));
@@ -61,6 +61,13 @@ fn try_consume_fn<'r, 't>(
         None,
     )?;
 
+    let start = parser.full_text().offset_of(label);
+    let error = parser
+        .make_err(ParseErrorKind::BibliographyCiteUndefined)
+        .with_span(start..start + label.len());
+
+    parser.push_bibliography_cite(cow!(label), error);
+
     ok!(Element::BibliographyCite {
         label: cow!(label),
         brackets: false,
@@ -114,11 +114,13 @@ impl<'t> Arguments<'t> {
     /// Similar to `to_hash_map()`, but creates an `AttributeMap` instead.
     ///
     /// Because all fields are passed from the user, this does ID isolation
-    /// if that is enabled, and so needs `WikitextSettings` to be passed in.
+    /// if that is enabled and style sanitization, and so needs
+    /// `WikitextSettings` to be passed in.
     #[inline]
     pub fn to_attribute_map(&self, settings: &WikitextSettings) -> AttributeMap<'t> {
         let mut map = AttributeMap::from_arguments(&self.inner);
         map.isolate_id(settings);
+        map.sanitize_style(settings);
         map
     }
 }
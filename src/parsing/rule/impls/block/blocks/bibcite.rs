@@ -55,6 +55,13 @@ fn parse_fn<'r, 't>(
     // "bibcite_" means it's bare, like ((bibcite))
     let brackets = !flag_score;
 
+    let start = parser.full_text().offset_of(label);
+    let error = parser
+        .make_err(ParseErrorKind::BibliographyCiteUndefined)
+        .with_span(start..start + label.len());
+
+    parser.push_bibliography_cite(cow!(label), error);
+
     ok!(Element::BibliographyCite {
         label: cow!(label),
         brackets,
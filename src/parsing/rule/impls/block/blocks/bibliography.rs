@@ -19,7 +19,7 @@
  */
 
 use super::prelude::*;
-use crate::tree::{Bibliography, DefinitionListItem};
+use crate::tree::{Bibliography, CitationStyle, DefinitionListItem, parse_bibtex};
 
 pub const BLOCK_BIBLIOGRAPHY: BlockRule = BlockRule {
     name: "block-bibliography",
@@ -48,7 +48,42 @@ fn parse_fn<'r, 't>(
 
     let title = arguments.get("title");
     let hide = arguments.get_bool(parser, "hide")?.unwrap_or(false);
+    let style = arguments
+        .get_value(parser, "style")?
+        .unwrap_or(CitationStyle::Numeric);
 
+    let bibtex = match arguments.get("type") {
+        None => false,
+        Some(value) if value.eq_ignore_ascii_case("bibtex") => true,
+        Some(value) if value.eq_ignore_ascii_case("list") => false,
+        Some(value) => {
+            warn!("Invalid bibliography type: {value}");
+            return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
+        }
+    };
+
+    let mut bibliography = Bibliography::new(style);
+    let errors = if bibtex {
+        parse_bibtex_body(parser, &mut bibliography)?;
+        vec![]
+    } else {
+        parse_list_body(parser, &mut bibliography)?
+    };
+
+    // Add bibliography object to parser for unified tracking, like footnotes.
+    let index = parser.push_bibliography(bibliography);
+
+    ok!(Element::BibliographyBlock { index, title, hide }, errors)
+}
+
+/// Parses a bibliography made of a definition list of references.
+fn parse_list_body<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+    bibliography: &mut Bibliography<'t>,
+) -> Result<Vec<ParseError>, ParseError>
+where
+    'r: 't,
+{
     // Get body content. The contents should only be a definition list, but
     // we use the regular elements parser to make it easy on us. If we find
     // anything else, we fail the rule.
@@ -62,8 +97,6 @@ fn parse_fn<'r, 't>(
     //
     // Look through to find definition lists, ignoring "space" type elements,
     // and adding definition list values to the bibliography as we find them.
-    let mut bibliography = Bibliography::new();
-
     for element in elements {
         match element {
             // Append definition list entries
@@ -74,6 +107,12 @@ fn parse_fn<'r, 't>(
                     ..
                 } in items
                 {
+                    let start = parser.full_text().offset_of(&key_string);
+                    let error = parser
+                        .make_err(ParseErrorKind::BibliographyReferenceUncited)
+                        .with_span(start..start + key_string.len());
+
+                    parser.push_bibliography_definition(key_string.clone(), error);
                     bibliography.add(key_string, value_elements);
                 }
             }
@@ -94,8 +133,35 @@ fn parse_fn<'r, 't>(
         }
     }
 
-    // Add bibliography object to parser for unified tracking, like footnotes.
-    let index = parser.push_bibliography(bibliography);
+    Ok(errors)
+}
 
-    ok!(Element::BibliographyBlock { index, title, hide }, errors)
+/// Parses a bibliography made of BibTeX entries.
+fn parse_bibtex_body<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+    bibliography: &mut Bibliography<'t>,
+) -> Result<(), ParseError>
+where
+    'r: 't,
+{
+    let body = parser.get_body_text(&BLOCK_BIBLIOGRAPHY)?;
+    let offset = parser.full_text().offset_of(body);
+    let references = parse_bibtex(body).map_err(|error| {
+        let span = offset + error.span.start..offset + error.span.end;
+        parser
+            .make_err(ParseErrorKind::BibliographyMalformedBibtex)
+            .with_span(span)
+    })?;
+
+    for (label, reference) in references {
+        let start = parser.full_text().offset_of(&label);
+        let error = parser
+            .make_err(ParseErrorKind::BibliographyReferenceUncited)
+            .with_span(start..start + label.len());
+
+        parser.push_bibliography_definition(label.clone(), error);
+        bibliography.add_reference(label, reference);
+    }
+
+    Ok(())
 }
@@ -0,0 +1,61 @@
+/*
+ * parsing/rule/impls/block/blocks/button.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::tree::PageAction;
+
+pub const BLOCK_BUTTON: BlockRule = BlockRule {
+    name: "block-button",
+    accepts_names: &["button"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: false,
+    parse_fn,
+};
+
+fn parse_fn<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    debug!("Parsing button block (name '{name}', in-head {in_head})");
+    assert!(!flag_star, "Button doesn't allow star flag");
+    assert!(!flag_score, "Button doesn't allow score flag");
+    assert_block_name(&BLOCK_BUTTON, name);
+
+    // Buttons act on the page, so they only make sense there
+    parser.check_page_syntax()?;
+
+    let (action, mut arguments) = parser.get_head_name_map(&BLOCK_BUTTON, in_head)?;
+    let action = match PageAction::try_from(action.to_ascii_lowercase().as_str()) {
+        Ok(action) => action,
+        Err(_) => return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments)),
+    };
+
+    let element = Element::Button {
+        action,
+        label: arguments.get("text"),
+        attributes: arguments.to_attribute_map(parser.settings()),
+    };
+
+    ok!(element)
+}
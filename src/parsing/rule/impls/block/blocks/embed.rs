@@ -19,12 +19,10 @@
  */
 
 use super::prelude::*;
+use crate::settings::EmbedProvider;
 use crate::tree::Embed;
-
-type EmbedBuilderFn = for<'p, 't> fn(
-    &'p Parser<'_, 't>,
-    &'p mut Arguments<'t>,
-) -> Result<Embed<'t>, ParseError>;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 
 pub const BLOCK_EMBED: BlockRule = BlockRule {
     name: "block-embed",
@@ -61,44 +59,80 @@ fn build_embed<'r, 't>(
 where
     'r: 't,
 {
-    const EMBED_BUILDERS: &[(&str, EmbedBuilderFn)] =
-        &[("youtube", build_youtube), ("vimeo", build_vimeo)];
-
-    for &(embed_name, builder) in EMBED_BUILDERS {
-        if embed_name.eq_ignore_ascii_case(name) {
-            return builder(parser, arguments);
-        }
-    }
+    let provider = parser
+        .settings()
+        .embed
+        .get(name)
+        .ok_or_else(|| parser.make_err(ParseErrorKind::NoSuchEmbed))?;
 
-    Err(parser.make_err(ParseErrorKind::NoSuchEmbed))
-}
+    debug!("Building embed with provider '{}'", provider.name);
+    let mut values = get_arguments(parser, provider, arguments)?;
+    let mut take = |key| values.remove(key).unwrap_or(Cow::Borrowed(""));
 
-// Different embed builders
+    // Built-in providers have their own variants,
+    // unless they have been overridden by the host.
+    let builtin = !parser
+        .settings()
+        .embed
+        .providers
+        .iter()
+        .any(|registered| registered.name.eq_ignore_ascii_case(&provider.name));
 
-fn build_youtube<'p, 't>(
-    parser: &'p Parser<'_, 't>,
-    arguments: &'p mut Arguments<'t>,
-) -> Result<Embed<'t>, ParseError> {
-    let video_id = arguments
-        .get("video")
-        .ok_or_else(|| parser.make_err(ParseErrorKind::BlockMissingArguments))?;
+    let embed = match provider.name.as_ref() {
+        "youtube" if builtin => Embed::Youtube {
+            video_id: take("video"),
+        },
+        "vimeo" if builtin => Embed::Vimeo {
+            video_id: take("video"),
+        },
+        "github-gist" if builtin => Embed::GithubGist {
+            username: take("username"),
+            hash: take("hash"),
+        },
+        "gitlab-snippet" if builtin => Embed::GitlabSnippet {
+            snippet_id: take("id"),
+        },
+        _ => Embed::Custom {
+            provider: Cow::Owned(provider.name.to_ascii_lowercase()),
+            arguments: values
+                .into_iter()
+                .map(|(key, value)| (Cow::Borrowed(key), value))
+                .collect(),
+        },
+    };
 
-    Ok(Embed::Youtube { video_id })
+    Ok(embed)
 }
 
-fn build_vimeo<'p, 't>(
-    parser: &'p Parser<'_, 't>,
-    arguments: &'p mut Arguments<'t>,
-) -> Result<Embed<'t>, ParseError> {
-    let video_id = arguments
-        .get("video")
-        .ok_or_else(|| parser.make_err(ParseErrorKind::BlockMissingArguments))?;
+/// Gets and validates each argument in the provider's schema.
+fn get_arguments<'r, 't>(
+    parser: &Parser<'r, 't>,
+    provider: &'r EmbedProvider,
+    arguments: &mut Arguments<'t>,
+) -> Result<BTreeMap<&'t str, Cow<'t, str>>, ParseError>
+where
+    'r: 't,
+{
+    let mut values = BTreeMap::new();
 
-    Ok(Embed::Vimeo { video_id })
-}
+    for argument in provider.arguments.iter() {
+        let key: &'r str = &argument.name;
+
+        match arguments.get(key) {
+            Some(value) => {
+                if !argument.validate(&value) {
+                    warn!("Invalid value for embed argument '{key}': {value:?}");
+                    return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
+                }
+
+                values.insert(key, value);
+            }
+            None if argument.optional => (),
+            None => {
+                return Err(parser.make_err(ParseErrorKind::BlockMissingArguments));
+            }
+        }
+    }
 
-#[test]
-fn embed_builder_types() {
-    let _: EmbedBuilderFn = build_youtube;
-    let _: EmbedBuilderFn = build_vimeo;
+    Ok(values)
 }
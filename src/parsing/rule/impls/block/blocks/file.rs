@@ -0,0 +1,68 @@
+/*
+ * parsing/rule/impls/block/blocks/file.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::tree::FileSource;
+
+pub const BLOCK_FILE: BlockRule = BlockRule {
+    name: "block-file",
+    accepts_names: &["file"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: false,
+    parse_fn,
+};
+
+fn parse_fn<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    debug!("Parsing file block (name '{name}', in-head {in_head})");
+    assert!(!flag_star, "File doesn't allow star flag");
+    assert!(!flag_score, "File doesn't allow score flag");
+    assert_block_name(&BLOCK_FILE, name);
+
+    // Arguments are of the form "source" or "source | label"
+    let (source, label) =
+        parser.get_head_value(&BLOCK_FILE, in_head, |parser, value| match value {
+            Some(value) => {
+                let (source, label) = match value.split_once('|') {
+                    Some((source, label)) => (source, Some(label.trim())),
+                    None => (value, None),
+                };
+
+                match FileSource::parse(source.trim()) {
+                    Some(source) => Ok((source, label.filter(|label| !label.is_empty()))),
+                    None => Err(parser.make_err(ParseErrorKind::BlockMalformedArguments)),
+                }
+            }
+            None => Err(parser.make_err(ParseErrorKind::BlockMissingArguments)),
+        })?;
+
+    let element = Element::File {
+        source,
+        label: label.map(|label| cow!(label)),
+    };
+
+    ok!(element)
+}
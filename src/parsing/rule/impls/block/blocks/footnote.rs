@@ -64,7 +64,47 @@ fn parse_footnote_ref<'r, 't>(
     assert!(!flag_score, "Footnote reference doesn't allow score flag");
     assert_block_name(&BLOCK_FOOTNOTE, name);
 
-    parser.get_head_none(&BLOCK_FOOTNOTE, in_head)?;
+    let mut arguments = parser.get_head_map(&BLOCK_FOOTNOTE, in_head)?;
+    let footnote_name = arguments.get("name");
+    let footnote_ref = arguments.get("ref");
+
+    if !arguments.is_empty() {
+        warn!("Invalid argument keys found");
+        return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
+    }
+
+    // Citing a named footnote again.
+    //
+    // This has no body, and does not create a new footnote.
+    if let Some(footnote_ref) = footnote_ref {
+        if footnote_name.is_some() {
+            warn!("Footnote has both a name and a reference");
+            return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
+        }
+
+        let index = match parser.get_footnote_index(&footnote_ref) {
+            Some(index) => index,
+            None => {
+                warn!("No footnote named '{footnote_ref}' was defined");
+                return Err(parser.make_err(ParseErrorKind::FootnoteUndefinedReference));
+            }
+        };
+
+        parser.push_footnote_ref(index);
+
+        return ok!(Element::FootnoteRef {
+            name: footnote_ref,
+            index,
+        });
+    }
+
+    // Ensure footnote names are unique
+    if let Some(ref footnote_name) = footnote_name
+        && parser.get_footnote_index(footnote_name).is_some()
+    {
+        warn!("A footnote named '{footnote_name}' was already defined");
+        return Err(parser.make_err(ParseErrorKind::FootnoteDuplicateName));
+    }
 
     // Gather footnote contents with paragraphs.
     //
@@ -88,8 +128,12 @@ fn parse_footnote_ref<'r, 't>(
         };
     }
 
-    // Append footnote contents and return.
-    parser.push_footnote(elements);
+    // Append footnote contents, and its name if it has one, and return.
+    let index = parser.push_footnote(elements);
+
+    if let Some(footnote_name) = footnote_name {
+        parser.push_footnote_name(footnote_name, index);
+    }
 
     ok!(Element::Footnote, errors)
 }
@@ -0,0 +1,66 @@
+/*
+ * parsing/rule/impls/block/blocks/form.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::tree::Form;
+
+pub const BLOCK_FORM: BlockRule = BlockRule {
+    name: "block-form",
+    accepts_names: &["form"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: true,
+    parse_fn,
+};
+
+fn parse_fn<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    debug!("Parsing form block (in-head {in_head})");
+    assert!(!flag_star, "Form doesn't allow star flag");
+    assert!(!flag_score, "Form doesn't allow score flag");
+    assert_block_name(&BLOCK_FORM, name);
+
+    parser.check_page_syntax()?;
+
+    if parser.has_form() {
+        return Err(parser.make_err(ParseErrorKind::FormAlreadyDefined));
+    }
+
+    parser.get_head_none(&BLOCK_FORM, in_head)?;
+
+    let definition = parser.get_body_text(&BLOCK_FORM)?;
+    let form = Form::parse(definition).map_err(|error| {
+        let offset = parser.full_text().offset_of(definition);
+        let span = offset + error.span.start..offset + error.span.end;
+        parser
+            .make_err(ParseErrorKind::FormMalformed)
+            .with_span(span)
+    })?;
+
+    // The form only describes page data, it has no output of its own
+    trace!("Parsed form definition ({} fields)", form.fields.len());
+    parser.set_form(form);
+    ok!(Elements::None)
+}
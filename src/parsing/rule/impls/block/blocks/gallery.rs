@@ -0,0 +1,135 @@
+/*
+ * parsing/rule/impls/block/blocks/gallery.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::tree::{FileSource, GalleryImage, GalleryOrder, GallerySize, LinkLocation};
+
+pub const BLOCK_GALLERY: BlockRule = BlockRule {
+    name: "block-gallery",
+    accepts_names: &["gallery"],
+    accepts_star: false,
+    accepts_score: false,
+    accepts_newlines: true,
+    parse_fn,
+};
+
+fn parse_fn<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+    name: &'t str,
+    flag_star: bool,
+    flag_score: bool,
+    in_head: bool,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    debug!("Parsing gallery block (in-head {in_head})");
+    assert!(!flag_star, "Gallery doesn't allow star flag");
+    assert!(!flag_score, "Gallery doesn't allow score flag");
+    assert_block_name(&BLOCK_GALLERY, name);
+
+    let mut arguments = parser.get_head_map(&BLOCK_GALLERY, in_head)?;
+    let size = arguments
+        .get_value::<GallerySize>(parser, "size")?
+        .unwrap_or_default();
+    let order = arguments.get_value::<GalleryOrder>(parser, "order")?;
+
+    // Without a body, the gallery shows all the attached files.
+    //
+    // A body is only present if the next line is an image
+    // or the end of the block, so that a later gallery's
+    // end block is not mistaken for this one's.
+    let has_body = parser.evaluate_fn(|parser| {
+        parser.get_optional_line_break()?;
+
+        if parser.current().slice.starts_with(':') {
+            return Ok(true);
+        }
+
+        let name = parser.get_end_block()?;
+        Ok(name.eq_ignore_ascii_case("gallery"))
+    });
+
+    let images = if has_body {
+        let body = parser.get_body_text(&BLOCK_GALLERY)?;
+        Some(parse_images(parser, body)?)
+    } else {
+        None
+    };
+
+    let element = Element::Gallery {
+        images,
+        size,
+        order,
+        attributes: arguments.to_attribute_map(parser.settings()),
+    };
+
+    ok!(element)
+}
+
+/// Parses the list of images, each line of the form
+/// `: source link="..." title="..."`.
+fn parse_images<'t>(
+    parser: &Parser<'_, 't>,
+    body: &'t str,
+) -> Result<Vec<GalleryImage<'t>>, ParseError> {
+    let argument_regex = regex!(r#"^([A-Za-z_\-]+)\s*=\s*"([^"]*)"\s*"#);
+    let mut images = Vec::new();
+
+    for line in body.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let make_err = || {
+            let offset = parser.full_text().offset_of(line);
+            parser
+                .make_err(ParseErrorKind::BlockMalformedArguments)
+                .with_span(offset..offset + line.len())
+        };
+
+        let line = line.strip_prefix(':').ok_or_else(make_err)?.trim_start();
+        let (source, mut rest) =
+            line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let source = FileSource::parse(source).ok_or_else(make_err)?;
+        let mut link = None;
+        let mut caption = None;
+
+        rest = rest.trim_start();
+        while !rest.is_empty() {
+            let captures = argument_regex.captures(rest).ok_or_else(make_err)?;
+            let value = captures.get(2).unwrap().as_str();
+
+            match captures[1].to_ascii_lowercase().as_str() {
+                "link" => link = Some(LinkLocation::parse(cow!(value))),
+                "title" => caption = Some(cow!(value)),
+                key => warn!("Ignoring unknown gallery image argument '{key}'"),
+            }
+
+            rest = &rest[captures.get(0).unwrap().end()..];
+        }
+
+        images.push(GalleryImage {
+            source,
+            link,
+            caption,
+        });
+    }
+
+    Ok(images)
+}
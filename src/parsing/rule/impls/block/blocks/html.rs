@@ -43,10 +43,26 @@ fn parse_fn<'r, 't>(
 
     let arguments = parser.get_head_map(&BLOCK_HTML, in_head)?;
     let html = parser.get_body_text(&BLOCK_HTML)?;
+    let offset = parser.full_text().offset_of(html);
+    let span = offset..offset + html.len();
+
+    // The hosted URL isn't known until rendering, so only check if iframes are enabled.
+    // Any origin restrictions are checked by the renderer, which warns using this span.
+    let mut errors = Vec::new();
+    if !parser.settings().iframe.enabled {
+        warn!("HTML block used while iframes are disabled");
+        errors.push(
+            parser
+                .make_err(ParseErrorKind::IframeDisallowed)
+                .with_span(span.clone()),
+        );
+    }
+
     let element = Element::Html {
         contents: cow!(html),
         attributes: arguments.to_attribute_map(parser.settings()),
+        span,
     };
     parser.push_html_block(cow!(html));
-    ok!(element)
+    ok!(element, errors)
 }
@@ -20,7 +20,7 @@
 
 use super::prelude::*;
 use crate::data::PageInfo;
-use crate::parsing::{ElementCondition, ElementConditionType};
+use crate::parsing::{ConditionExpression, ElementCondition, ElementConditionType};
 
 pub const BLOCK_IFCATEGORY: BlockRule = BlockRule {
     name: "block-ifcategory",
@@ -43,11 +43,11 @@ fn parse_fn<'r, 't>(
     assert!(!flag_score, "IfCategory doesn't allow score flag");
     assert_block_name(&BLOCK_IFCATEGORY, name);
 
-    // Parse out tag conditions
-    let conditions =
+    // Parse out category conditions
+    let condition =
         parser.get_head_value(&BLOCK_IFCATEGORY, in_head, |parser, spec| match spec {
             None => Err(parser.make_err(ParseErrorKind::BlockMissingArguments)),
-            Some(spec) => {
+            Some(spec) if ConditionExpression::is_legacy(spec) => {
                 let mut conditions = ElementCondition::parse(spec);
 
                 conditions.iter_mut().for_each(|condition| {
@@ -62,8 +62,10 @@ fn parse_fn<'r, 't>(
                     }
                 });
 
-                Ok(conditions)
+                Ok(ConditionExpression::from_legacy(conditions))
             }
+            Some(spec) => ConditionExpression::parse(spec)
+                .map_err(|error| error.to_parse_error(parser, spec)),
         })?;
 
     // Get body content, never with paragraphs
@@ -71,13 +73,12 @@ fn parse_fn<'r, 't>(
         parser.get_body_elements(&BLOCK_IFCATEGORY, false)?.into();
 
     trace!(
-        "IfCategory conditions parsed (conditions length {}, elements length {})",
-        conditions.len(),
+        "IfCategory condition parsed (elements length {})",
         elements.len(),
     );
 
     // Return elements based on condition
-    let elements = if check_ifcategory(parser.page_info(), &conditions) {
+    let elements = if check_ifcategory(parser.page_info(), &condition) {
         trace!("Conditions passed, including elements");
 
         Elements::Multiple(elements)
@@ -90,12 +91,12 @@ fn parse_fn<'r, 't>(
     ok!(paragraph_safe; elements, errors)
 }
 
-pub fn check_ifcategory(info: &PageInfo, conditions: &[ElementCondition]) -> bool {
+pub fn check_ifcategory(info: &PageInfo, condition: &ConditionExpression) -> bool {
     let category = match &info.category {
         Some(category) => category,
         None => "_default",
     };
 
     trace!("Checking ifcategory (category '{category}')");
-    ElementCondition::check(conditions, &[cow!(category)])
+    condition.check(&[cow!(category)])
 }
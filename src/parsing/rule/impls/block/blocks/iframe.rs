@@ -48,10 +48,17 @@ fn parse_fn<'r, 't>(
         return Err(parser.make_err(ParseErrorKind::BlockMalformedArguments));
     }
 
+    // Disallowed iframes are still emitted, the renderer replaces them with a notice.
+    let mut errors = Vec::new();
+    if !parser.settings().iframe.allows(url) {
+        warn!("Iframe block references disallowed URL: {url}");
+        errors.push(parser.make_err(ParseErrorKind::IframeDisallowed));
+    }
+
     let element = Element::Iframe {
         url: cow!(url),
         attributes: arguments.to_attribute_map(parser.settings()),
     };
 
-    ok!(element)
+    ok!(element, errors)
 }
@@ -20,7 +20,7 @@
 
 use super::prelude::*;
 use crate::data::PageInfo;
-use crate::parsing::ElementCondition;
+use crate::parsing::ConditionExpression;
 
 pub const BLOCK_IFTAGS: BlockRule = BlockRule {
     name: "block-iftags",
@@ -44,9 +44,10 @@ fn parse_fn<'r, 't>(
     assert_block_name(&BLOCK_IFTAGS, name);
 
     // Parse out tag conditions
-    let conditions =
+    let condition =
         parser.get_head_value(&BLOCK_IFTAGS, in_head, |parser, spec| match spec {
-            Some(spec) => Ok(ElementCondition::parse(spec)),
+            Some(spec) => ConditionExpression::parse(spec)
+                .map_err(|error| error.to_parse_error(parser, spec)),
             None => Err(parser.make_err(ParseErrorKind::BlockMissingArguments)),
         })?;
 
@@ -55,13 +56,12 @@ fn parse_fn<'r, 't>(
         parser.get_body_elements(&BLOCK_IFTAGS, false)?.into();
 
     trace!(
-        "IfTags conditions parsed (conditions length {}, elements length {})",
-        conditions.len(),
+        "IfTags condition parsed (elements length {})",
         elements.len(),
     );
 
     // Return elements based on condition
-    let elements = if check_iftags(parser.page_info(), &conditions) {
+    let elements = if check_iftags(parser.page_info(), &condition) {
         trace!("Conditions passed, including elements");
 
         Elements::Multiple(elements)
@@ -74,7 +74,7 @@ fn parse_fn<'r, 't>(
     ok!(paragraph_safe; elements, errors)
 }
 
-pub fn check_iftags(info: &PageInfo, conditions: &[ElementCondition]) -> bool {
+pub fn check_iftags(info: &PageInfo, condition: &ConditionExpression) -> bool {
     trace!("Checking iftags");
-    ElementCondition::check(conditions, &info.tags)
+    condition.check(&info.tags)
 }
@@ -65,9 +65,12 @@ fn parse_fn<'r, 't>(
         mut html_blocks,
         mut code_blocks,
         mut table_of_contents_depths,
+        headings: _, // spans are in the other page's source
         mut footnotes,
+        mut footnote_refs,
         has_footnote_block,
         mut bibliographies,
+        form: _,
     } = include_page(parser, &page_ref)?;
 
     if has_footnote_block {
@@ -88,6 +91,7 @@ fn parse_fn<'r, 't>(
         &mut code_blocks,
         &mut table_of_contents_depths,
         &mut footnotes,
+        &mut footnote_refs,
         &mut bibliographies,
     );
 
@@ -121,8 +125,11 @@ fn include_page<'r, 't>(
         html_blocks: vec![],
         code_blocks: vec![],
         table_of_contents_depths: vec![],
+        headings: vec![],
         footnotes: vec![],
+        footnote_refs: vec![],
         has_footnote_block: false,
         bibliographies: Default::default(),
+        form: None,
     })
 }
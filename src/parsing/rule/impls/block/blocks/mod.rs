@@ -64,6 +64,7 @@ mod bibcite;
 mod bibliography;
 mod blockquote;
 mod bold;
+mod button;
 mod char;
 mod checkbox;
 mod code;
@@ -73,7 +74,10 @@ mod del;
 mod div;
 mod embed;
 mod equation_ref;
+mod file;
 mod footnote;
+mod form;
+mod gallery;
 mod hidden;
 mod html;
 mod ifcategory;
@@ -118,6 +122,7 @@ pub use self::bibcite::BLOCK_BIBCITE;
 pub use self::bibliography::BLOCK_BIBLIOGRAPHY;
 pub use self::blockquote::BLOCK_BLOCKQUOTE;
 pub use self::bold::BLOCK_BOLD;
+pub use self::button::BLOCK_BUTTON;
 pub use self::char::BLOCK_CHAR;
 pub use self::checkbox::BLOCK_CHECKBOX;
 pub use self::code::BLOCK_CODE;
@@ -127,7 +132,10 @@ pub use self::del::BLOCK_DEL;
 pub use self::div::BLOCK_DIV;
 pub use self::embed::BLOCK_EMBED;
 pub use self::equation_ref::BLOCK_EQUATION_REF;
+pub use self::file::BLOCK_FILE;
 pub use self::footnote::{BLOCK_FOOTNOTE, BLOCK_FOOTNOTE_BLOCK};
+pub use self::form::BLOCK_FORM;
+pub use self::gallery::BLOCK_GALLERY;
 pub use self::hidden::BLOCK_HIDDEN;
 pub use self::html::BLOCK_HTML;
 pub use self::ifcategory::BLOCK_IFCATEGORY;
@@ -56,6 +56,7 @@ fn parse_fn<'r, 't>(
     let attributes = {
         let mut map = AttributeMap::new();
         map.insert("style", Cow::Owned(size));
+        map.sanitize_style(parser.settings());
         map
     };
 
@@ -23,7 +23,7 @@ use std::collections::HashMap;
 use std::sync::LazyLock;
 use unicase::UniCase;
 
-pub const BLOCK_RULES: [BlockRule; 63] = [
+pub const BLOCK_RULES: [BlockRule; 67] = [
     BLOCK_ALIGN_CENTER,
     BLOCK_ALIGN_JUSTIFY,
     BLOCK_ALIGN_LEFT,
@@ -34,6 +34,7 @@ pub const BLOCK_RULES: [BlockRule; 63] = [
     BLOCK_BIBLIOGRAPHY,
     BLOCK_BLOCKQUOTE,
     BLOCK_BOLD,
+    BLOCK_BUTTON,
     BLOCK_CHAR,
     BLOCK_CHECKBOX,
     BLOCK_CODE,
@@ -43,8 +44,11 @@ pub const BLOCK_RULES: [BlockRule; 63] = [
     BLOCK_DIV,
     BLOCK_EMBED,
     BLOCK_EQUATION_REF,
+    BLOCK_FILE,
     BLOCK_FOOTNOTE,
     BLOCK_FOOTNOTE_BLOCK,
+    BLOCK_FORM,
+    BLOCK_GALLERY,
     BLOCK_HIDDEN,
     BLOCK_HTML,
     BLOCK_IFCATEGORY,
@@ -45,7 +45,8 @@ fn try_consume_fn<'r, 't>(
     }
 
     // Get header depth
-    let heading = step!(Token::Heading)
+    let heading_token = step!(Token::Heading);
+    let heading = heading_token
         .slice
         .try_into()
         .expect("Received invalid heading length token slice");
@@ -67,18 +68,16 @@ fn try_consume_fn<'r, 't>(
     )?
     .into();
 
-    // If this heading wants a table of contents (TOC) entry, then add one
-    if heading.has_toc {
-        // collect_container() always produces one Element::Container.
-        // We unwrap it so we can get the elements composing the name.
-        let elements = match elements {
-            Elements::Single(Element::Container(ref container)) => container.elements(),
-            _ => panic!("Collected heading produced a non-single non-container element"),
-        };
+    // Record this heading, adding a table of contents (TOC) entry if it wants one.
+    //
+    // collect_container() always produces one Element::Container.
+    // We unwrap it so we can get the elements composing the name.
+    let name_elements = match elements {
+        Elements::Single(Element::Container(ref container)) => container.elements(),
+        _ => panic!("Collected heading produced a non-single non-container element"),
+    };
 
-        // Create table of contents entry with the given level and name.
-        parser.push_table_of_contents_entry(heading.level, elements);
-    }
+    parser.push_heading(heading, heading_token.span.start, name_elements);
 
     // Recursively collect headings until we hit an error.
     //
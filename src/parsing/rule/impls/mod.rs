@@ -57,6 +57,7 @@ mod math;
 mod monospace;
 mod null;
 mod page;
+mod parser_function;
 mod raw;
 mod strikethrough;
 mod subscript;
@@ -96,6 +97,7 @@ pub use self::math::RULE_MATH;
 pub use self::monospace::RULE_MONOSPACE;
 pub use self::null::RULE_NULL;
 pub use self::page::RULE_PAGE;
+pub use self::parser_function::RULE_PARSER_FUNCTION;
 pub use self::raw::RULE_RAW;
 pub use self::strikethrough::{RULE_STRIKETHROUGH_DASH, RULE_STRIKETHROUGH_TILDE};
 pub use self::subscript::RULE_SUBSCRIPT;
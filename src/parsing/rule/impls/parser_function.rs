@@ -0,0 +1,170 @@
+/*
+ * parsing/rule/impls/parser_function.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Rule for Wikidot parser functions.
+//!
+//! These are `[[#if]]`, `[[#ifexpr]]`, and `[[#expr]]`, which conditionally
+//! include text or evaluate arithmetic. They are mostly used by pages meant to
+//! be included, together with `{$variable}` parameters, which have already been
+//! substituted by the time the page is parsed.
+//!
+//! Not to be confused with anchor names (`[[# name]]`), which require a space.
+
+use super::prelude::*;
+use crate::parsing::expression::{
+    ExpressionError, evaluate_expression, format_number, is_truthy,
+};
+use crate::parsing::strip::strip_whitespace;
+use std::borrow::Cow;
+
+pub const RULE_PARSER_FUNCTION: Rule = Rule {
+    name: "parser-function",
+    position: LineRequirement::Any,
+    try_consume_fn,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ParserFunction {
+    If,
+    IfExpr,
+    Expr,
+}
+
+fn try_consume_fn<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+) -> ParseResult<'r, 't, Elements<'t>> {
+    debug!("Trying to evaluate a parser function");
+    assert_step(parser, Token::LeftBlockAnchor)?;
+
+    // Get the function name
+    let current = parser.current();
+    let function = match (current.token, current.slice.to_ascii_lowercase().as_str()) {
+        (Token::Identifier, "if") => ParserFunction::If,
+        (Token::Identifier, "ifexpr") => ParserFunction::IfExpr,
+        (Token::Identifier, "expr") => ParserFunction::Expr,
+        _ => return Err(parser.make_err(ParseErrorKind::RuleFailed)),
+    };
+
+    parser.step()?;
+    parser.get_optional_space()?;
+
+    // Gather the condition or expression
+    let start = parser.current();
+    let close_conditions: &[ParseCondition] = match function {
+        ParserFunction::Expr => &[ParseCondition::current(Token::RightBlock)],
+        _ => &[
+            ParseCondition::current(Token::RightBlock),
+            ParseCondition::current(Token::Pipe),
+            ParseCondition::current(Token::TableColumn),
+        ],
+    };
+
+    let (argument, last) = collect_text_keep(
+        parser,
+        RULE_PARSER_FUNCTION,
+        close_conditions,
+        &[ParseCondition::current(Token::ParagraphBreak)],
+        None,
+    )?;
+
+    let offset = start.span.start;
+    let evaluate = |input: &str| {
+        evaluate_expression(input).map_err(|ExpressionError { kind, span }| {
+            let span = offset + span.start..offset + span.end;
+            ParseError::new(kind, RULE_PARSER_FUNCTION, start).with_span(span)
+        })
+    };
+
+    // Determine which branch to use, or produce the value
+    let condition = match function {
+        ParserFunction::If => is_truthy(argument),
+
+        // An empty expression is false, like an empty string in [[#if]]
+        ParserFunction::IfExpr if argument.trim().is_empty() => false,
+        ParserFunction::IfExpr => evaluate(argument)? != 0.0,
+
+        ParserFunction::Expr if argument.trim().is_empty() => return ok!(Elements::None),
+        ParserFunction::Expr => {
+            let value = format_number(evaluate(argument)?);
+            return ok!(Element::Text(Cow::Owned(value)));
+        }
+    };
+
+    debug!("Evaluated parser function condition (result {condition})");
+
+    // Parse each branch, separated by pipes.
+    //
+    // A double pipe is tokenized separately, but here just means that the
+    // "then" branch is empty.
+    let (then_branch, else_branch) = match last.token {
+        Token::RightBlock => (None, None),
+        Token::TableColumn => (None, Some(parse_branch(parser, !condition, false)?)),
+        Token::Pipe => {
+            let then_branch = parse_branch(parser, condition, true)?;
+            let else_branch = match then_branch.item.1.token {
+                Token::Pipe => Some(parse_branch(parser, !condition, false)?),
+                _ => None,
+            };
+
+            (Some(then_branch), else_branch)
+        }
+        _ => unreachable!(),
+    };
+
+    let branch = if condition { then_branch } else { else_branch };
+    match branch {
+        Some(success) => {
+            let ((mut elements, _), errors, paragraph_safe) = success.into();
+            strip_whitespace(&mut elements);
+            ok!(paragraph_safe; elements, errors)
+        }
+        None => ok!(Elements::None),
+    }
+}
+
+/// Parses the elements in one of the branches.
+///
+/// The branch which is not taken is still parsed in order to find where
+/// it ends, but its effects on the parser state, such as adding footnotes,
+/// are rolled back.
+fn parse_branch<'r, 't>(
+    parser: &mut Parser<'r, 't>,
+    taken: bool,
+    allow_pipe: bool,
+) -> ParseResult<'r, 't, (Vec<Element<'t>>, &'r ExtractedToken<'t>)> {
+    let close_conditions: &[ParseCondition] = if allow_pipe {
+        &[
+            ParseCondition::current(Token::RightBlock),
+            ParseCondition::current(Token::Pipe),
+        ]
+    } else {
+        &[ParseCondition::current(Token::RightBlock)]
+    };
+
+    let state = parser.get_mutable_state();
+    let success =
+        collect_consume_keep(parser, RULE_PARSER_FUNCTION, close_conditions, &[], None)?;
+
+    if !taken {
+        parser.reset_mutable_state(state);
+    }
+
+    Ok(success)
+}
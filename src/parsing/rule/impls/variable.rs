@@ -19,6 +19,7 @@
  */
 
 use super::prelude::*;
+use crate::tree::is_known_variable;
 
 pub const RULE_VARIABLE: Rule = Rule {
     name: "variable",
@@ -39,5 +40,14 @@ fn try_consume_fn<'r, 't>(
         .expect("Capture group not found")
         .as_str();
 
-    ok!(Element::Variable(cow!(variable)))
+    // Variables are only substituted when rendering, since they may be
+    // provided by an include. But outside of that, only the built-in and
+    // registered variables are available.
+    let mut errors = Vec::new();
+    if !is_known_variable(variable, parser.settings()) {
+        debug!("Variable '{variable}' is not built-in or registered");
+        errors.push(parser.make_err(ParseErrorKind::UnknownVariable));
+    }
+
+    ok!(Element::Variable(cow!(variable)), errors)
 }
@@ -40,11 +40,11 @@ pub static RULE_MAP: LazyLock<EnumMap<Token, Vec<Rule>>> = LazyLock::new(|| {
         Token::RightBracket => vec![RULE_TEXT],
         Token::LeftBlock => vec![RULE_BLOCK],
         Token::LeftBlockEnd => vec![],
-        Token::LeftBlockAnchor => vec![RULE_ANCHOR],
+        Token::LeftBlockAnchor => vec![RULE_ANCHOR, RULE_PARSER_FUNCTION],
         Token::LeftBlockStar => vec![RULE_BLOCK_STAR],
         Token::RightBlock => vec![],
         Token::LeftParentheses => vec![RULE_BIBCITE, RULE_TEXT],
-        Token::RightParentheses => vec![RULE_BIBCITE, RULE_TEXT],
+        Token::RightParentheses => vec![RULE_TEXT],
         Token::LeftMath => vec![RULE_MATH],
         Token::RightMath => vec![],
         Token::DoubleDash => vec![RULE_STRIKETHROUGH_DASH, RULE_DASH],
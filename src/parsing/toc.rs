@@ -0,0 +1,135 @@
+/*
+ * parsing/toc.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Generation of the anchor IDs linking table of contents entries to headings.
+//!
+//! The legacy Wikidot layout numbers headings sequentially (`toc0`, `toc1`, ...),
+//! which means every anchor after a newly-inserted heading changes. The Wikijump
+//! layout instead derives a readable ID from the heading's text, so that links
+//! remain valid as the page is edited.
+
+use crate::layout::Layout;
+use crate::next_index::{Incrementer, NextIndex};
+use crate::settings::WikitextSettings;
+use std::collections::HashSet;
+use wikidot_normalize::normalize;
+
+/// The ID used for headings whose text produces an empty slug.
+const FALLBACK_SLUG: &str = "heading";
+
+#[derive(Debug)]
+pub enum TocAnchors {
+    /// Anchors are numbered, as `toc{n}`.
+    Indexed(Incrementer),
+
+    /// Anchors are derived from the heading name, with suffixes to deduplicate.
+    Slug(HashSet<String>),
+}
+
+impl TocAnchors {
+    pub fn new(settings: &WikitextSettings) -> Self {
+        match settings.layout {
+            Layout::Wikijump if settings.use_true_ids => TocAnchors::Slug(HashSet::new()),
+            _ => TocAnchors::Indexed(settings.id_indexer()),
+        }
+    }
+
+    /// Yields the anchor ID for the next heading, given its name.
+    ///
+    /// Returns `None` if IDs are disabled.
+    pub fn next(&mut self, name: &str) -> Option<String> {
+        match self {
+            TocAnchors::Indexed(incr) => incr.next().map(|index| format!("toc{index}")),
+            TocAnchors::Slug(seen) => {
+                let slug = heading_slug(name);
+
+                // Add a numeric suffix for repeated headings,
+                // checking the result doesn't collide either.
+                let mut id = slug.clone();
+                let mut suffix = 1;
+                while seen.contains(&id) {
+                    id = format!("{slug}-{suffix}");
+                    suffix += 1;
+                }
+
+                seen.insert(id.clone());
+                Some(id)
+            }
+        }
+    }
+}
+
+/// Converts a heading's text into a slug suitable for use as an HTML ID.
+pub fn heading_slug(name: &str) -> String {
+    let mut slug = str!(name);
+    normalize(&mut slug);
+
+    // Normalization preserves category separators, which we don't want here.
+    let slug = slug.replace(':', "-");
+
+    if slug.is_empty() {
+        str!(FALLBACK_SLUG)
+    } else {
+        slug
+    }
+}
+
+#[test]
+fn slug_anchors() {
+    use crate::settings::WikitextMode;
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump);
+    let mut anchors = TocAnchors::new(&settings);
+
+    macro_rules! test {
+        ($name:expr, $expected:expr $(,)?) => {
+            assert_eq!(
+                anchors.next($name).as_deref(),
+                Some($expected),
+                "Actual heading anchor doesn't match expected",
+            );
+        };
+    }
+
+    test!("Alpha", "alpha");
+    test!("Sub-category here", "sub-category-here");
+    test!("Part 1: The Beginning", "part-1-the-beginning");
+    test!("Alpha", "alpha-1");
+    test!("ALPHA!", "alpha-2");
+    test!("Alpha 1", "alpha-1-1");
+    test!("🌊", "heading");
+    test!("", "heading-1");
+    test!("Café", "café");
+}
+
+#[test]
+fn indexed_anchors() {
+    use crate::settings::WikitextMode;
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut anchors = TocAnchors::new(&settings);
+    assert_eq!(anchors.next("Alpha").as_deref(), Some("toc0"));
+    assert_eq!(anchors.next("Alpha").as_deref(), Some("toc1"));
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Draft, Layout::Wikijump);
+    let mut anchors = TocAnchors::new(&settings);
+    assert_eq!(anchors.next("Alpha"), None);
+    assert_eq!(anchors.next("Beta"), None);
+}
@@ -27,6 +27,7 @@ pub mod whitespace;
 #[cfg(test)]
 mod test;
 
+use crate::templates::{SourceMap, SourceMapBuilder};
 use regex::Regex;
 
 /// Helper struct to easily perform string replacements.
@@ -61,9 +62,19 @@ pub enum Replacer {
 impl Replacer {
     /// Replaces the text in the manner defined by its enum, using the buffer as a temporary space
     /// to copy to.
-    fn replace(&self, text: &mut String, buffer: &mut String) {
+    ///
+    /// If a source map is given, it is updated to account for the replacements.
+    fn replace(
+        &self,
+        text: &mut String,
+        buffer: &mut String,
+        map: Option<&mut SourceMap>,
+    ) {
         use self::Replacer::*;
 
+        let source_len = text.len();
+        let mut builder = SourceMapBuilder::new();
+
         match *self {
             RegexReplace {
                 ref regex,
@@ -87,6 +98,12 @@ impl Replacer {
                         mtch.range()
                     };
 
+                    builder.replace(
+                        builder.source_position(range.start)
+                            ..builder.source_position(range.end),
+                        replacement.len(),
+                    );
+
                     text.replace_range(range, replacement);
                 }
             }
@@ -118,6 +135,15 @@ impl Replacer {
                         full_mtch.range()
                     };
 
+                    // The content is kept, only its exterior is replaced
+                    let before = builder.source_position(range.start)
+                        ..builder.source_position(mtch.start());
+                    let after = builder.source_position(mtch.end())
+                        ..builder.source_position(range.end);
+
+                    builder.replace(before, begin.len());
+                    builder.replace(after, end.len());
+
                     buffer.clear();
                     buffer.push_str(begin);
                     buffer.push_str(mtch.as_str());
@@ -127,6 +153,10 @@ impl Replacer {
                 }
             }
         }
+
+        if let Some(map) = map {
+            *map = builder.finish(source_len).compose(map);
+        }
     }
 }
 
@@ -148,6 +178,23 @@ pub fn preprocess(text: &mut String) {
     debug!("Finished preprocessing of text ({} bytes)", text.len());
 }
 
+/// Run the preprocessor on the given wikitext, like [`preprocess()`].
+///
+/// Returns a map from the preprocessed wikitext back to the original,
+/// which can be used to find where parser output came from.
+pub fn preprocess_with_map(text: &mut String) -> SourceMap {
+    info!(
+        "Beginning preprocessing of text with source map ({} bytes)",
+        text.len(),
+    );
+
+    let mut map = SourceMapBuilder::new().finish(text.len());
+    whitespace::substitute_with_map(text, Some(&mut map));
+    typography::substitute_with_map(text, Some(&mut map));
+    debug!("Finished preprocessing of text ({} bytes)", text.len());
+    map
+}
+
 #[test]
 fn fn_type() {
     type SubstituteFn = fn(&mut String);
@@ -18,7 +18,7 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
-use super::preprocess;
+use super::{preprocess, preprocess_with_map};
 use proptest::prelude::*;
 
 pub fn test_substitution<F>(filter_name: &str, mut substitute: F, tests: &[(&str, &str)])
@@ -78,9 +78,60 @@ fn prefilter() {
     test_substitution("prefilter", preprocess, &PREFILTER_TEST_CASES);
 }
 
+#[test]
+fn prefilter_map() {
+    let source = "\r\n+ Apple\r\n\r\n\r\nWait... ``what''\r\n+ Banana\tsplit\r\n\r\n";
+    let mut text = str!(source);
+    let map = preprocess_with_map(&mut text);
+    assert_eq!(
+        text,
+        "+ Apple\n\nWait\u{2026} \u{201c}what\u{201d}\n+ Banana    split"
+    );
+
+    // Copied text is the same in both
+    for segment in map.segments() {
+        assert_eq!(
+            &text[segment.output.clone()],
+            &source[segment.source.clone()]
+        );
+    }
+
+    // Line starts and ends of the text map exactly
+    let banana = text.find("+ Banana").unwrap();
+    assert_eq!(map.get_source_position(0), 2);
+    assert_eq!(
+        map.get_source_position(banana),
+        source.find("+ Banana").unwrap()
+    );
+    assert_eq!(map.get_source_position(text.len()), source.len());
+
+    // Replaced text maps to where it begins
+    let ellipsis = text.find('\u{2026}').unwrap();
+    assert_eq!(
+        map.get_source_position(ellipsis + 1),
+        source.find("...").unwrap()
+    );
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(4096))]
 
+    #[test]
+    fn prefilter_map_prop(source in ".*") {
+        let mut text = source.clone();
+        let map = preprocess_with_map(&mut text);
+
+        let mut expected = source.clone();
+        preprocess(&mut expected);
+        assert_eq!(&text, &expected);
+
+        for segment in map.segments() {
+            assert_eq!(&text[segment.output.clone()], &source[segment.source.clone()]);
+        }
+
+        assert_eq!(map.get_source_position(text.len()), source.len());
+    }
+
     #[test]
     fn prefilter_prop(mut s in ".*") {
         crate::preprocess(&mut s);
@@ -33,6 +33,7 @@
 //! the `--` in `[!--` and `--]` into em dashes.
 
 use super::Replacer;
+use crate::templates::SourceMap;
 use regex::Regex;
 use std::sync::LazyLock;
 
@@ -68,13 +69,19 @@ static HORIZONTAL_ELLIPSIS: LazyLock<Replacer> =
     });
 
 /// Performs all typographic substitutions in-place in the given text
+#[inline]
 pub fn substitute(text: &mut String) {
+    substitute_with_map(text, None);
+}
+
+/// Performs all typographic substitutions, updating the source map if one is given.
+pub(crate) fn substitute_with_map(text: &mut String, mut map: Option<&mut SourceMap>) {
     let mut buffer = String::new();
     debug!("Performing typography substitutions");
 
     macro_rules! replace {
         ($replacer:expr) => {
-            $replacer.replace(text, &mut buffer)
+            $replacer.replace(text, &mut buffer, map.as_deref_mut())
         };
     }
 
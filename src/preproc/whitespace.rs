@@ -28,6 +28,7 @@
 //! * Compress groups of 3+ newlines into 2 newlines
 
 use super::Replacer;
+use crate::templates::{SourceMap, SourceMapBuilder};
 use regex::{Regex, RegexBuilder};
 use std::sync::LazyLock;
 
@@ -71,12 +72,18 @@ static NULL_SPACE: LazyLock<Replacer> = LazyLock::new(|| Replacer::RegexReplace
 });
 
 /// Performs all whitespace substitutions in-place in the given text.
+#[inline]
 pub fn substitute(text: &mut String) {
+    substitute_with_map(text, None);
+}
+
+/// Performs all whitespace substitutions, updating the source map if one is given.
+pub(crate) fn substitute_with_map(text: &mut String, mut map: Option<&mut SourceMap>) {
     let mut buffer = String::new();
 
     macro_rules! replace {
         ($replacer:expr) => {
-            $replacer.replace(text, &mut buffer)
+            $replacer.replace(text, &mut buffer, map.as_deref_mut())
         };
     }
 
@@ -86,7 +93,7 @@ pub fn substitute(text: &mut String) {
     // Replace leading non-standard spaces with regular spaces
     // Leave other non-standard spaces as-is (such as nbsp in
     // the middle of paragraphs)
-    replace_leading_spaces(text);
+    replace_leading_spaces(text, map.as_deref_mut());
 
     // Strip lines with only whitespace
     replace!(WHITESPACE_ONLY_LINE);
@@ -106,9 +113,11 @@ pub fn substitute(text: &mut String) {
 }
 
 /// In-place replaces the leading non-standard spaces (such as nbsp) on each line with standard spaces
-fn replace_leading_spaces(text: &mut String) {
+fn replace_leading_spaces(text: &mut String, map: Option<&mut SourceMap>) {
     trace!("Replacing leading non-standard spaces with regular spaces");
 
+    let source_len = text.len();
+    let mut builder = SourceMapBuilder::new();
     let mut offset = 0;
 
     while let Some(capture) = LEADING_NONSTANDARD_WHITESPACE.captures_at(text, offset) {
@@ -121,8 +130,17 @@ fn replace_leading_spaces(text: &mut String) {
 
         offset = mtch.start() + count;
 
+        builder.replace(
+            builder.source_position(mtch.start())..builder.source_position(mtch.end()),
+            count,
+        );
+
         text.replace_range(mtch.range(), &spaces);
     }
+
+    if let Some(map) = map {
+        *map = builder.finish(source_len).compose(map);
+    }
 }
 
 #[cfg(test)]
@@ -51,9 +51,9 @@ fn debug_render() {
         vec![],
         vec![],
         (vec![], vec![]),
-        vec![],
-        (vec![], true),
-        BibliographyList::new(),
+        (vec![], vec![]),
+        (vec![], vec![], true),
+        (BibliographyList::new(), None),
         0,
     );
     let (tree, _) = result.into();
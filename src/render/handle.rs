@@ -18,6 +18,7 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+use super::{FileInfo, PageResolver};
 use crate::data::{KarmaLevel, PageInfo, UserInfo};
 use crate::settings::WikitextSettings;
 use crate::tree::{FileSource, LinkLabel, LinkLocation, Module};
@@ -25,10 +26,20 @@ use crate::url::BuildSiteUrl;
 use std::borrow::Cow;
 use std::num::NonZeroUsize;
 
-#[derive(Debug)]
-pub struct Handle;
+#[cfg(feature = "html")]
+use crate::url::normalize_link;
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Handle<'r> {
+    resolver: Option<&'r dyn PageResolver>,
+}
+
+impl<'r> Handle<'r> {
+    #[inline]
+    pub fn new(resolver: Option<&'r dyn PageResolver>) -> Self {
+        Handle { resolver }
+    }
 
-impl Handle {
     pub fn render_module(&self, buffer: &mut String, module: &Module) {
         // Modules only render to HTML
         debug!("Rendering module '{}'", module.name());
@@ -38,6 +49,10 @@ impl Handle {
     pub fn get_page_title(&self, _site: &str, _page: &str) -> Option<String> {
         debug!("Fetching page title");
 
+        if let Some(resolver) = self.resolver {
+            return resolver.page_title(_site, _page);
+        }
+
         // TODO
         Some(format!("TODO: actual title ({_site} {_page})"))
     }
@@ -45,6 +60,10 @@ impl Handle {
     pub fn get_page_exists(&self, _site: &str, _page: &str) -> bool {
         debug!("Checking page existence");
 
+        if let Some(resolver) = self.resolver {
+            return resolver.page_exists(_site, _page);
+        }
+
         // For testing
         #[cfg(test)]
         if _page == "missing" {
@@ -69,6 +88,7 @@ impl Handle {
         info: &PageInfo,
         settings: &WikitextSettings,
     ) -> Option<Cow<'a, str>> {
+        let full_name;
         let (site, page, file): (&str, &str, &str) = match source {
             FileSource::Url(url) => return Some(Cow::clone(url)),
             FileSource::File1 { .. }
@@ -79,17 +99,73 @@ impl Handle {
                 warn!("Specified path file source when local paths are disabled");
                 return None;
             }
-            FileSource::File1 { file } => (&info.site, &info.page, file),
+            FileSource::File1 { file } => {
+                full_name = info.full_name();
+                (&info.site, &full_name, file)
+            }
             FileSource::File2 { page, file } => (&info.site, page, file),
             FileSource::File3 { site, page, file } => (site, page, file),
         };
 
+        if let Some(url) = self
+            .resolver
+            .and_then(|resolver| resolver.file_url(site, page, file))
+        {
+            return Some(Cow::Owned(url));
+        }
+
         // TODO: emit url
         Some(Cow::Owned(format!(
             "https://{site}.wjfiles.com/local--files/{page}/{file}",
         )))
     }
 
+    pub fn get_file_info(
+        &self,
+        source: &FileSource,
+        info: &PageInfo,
+    ) -> Option<FileInfo> {
+        let full_name;
+        let (site, page, file): (&str, &str, &str) = match source {
+            FileSource::Url(_) => return None,
+            FileSource::File1 { file } => {
+                full_name = info.full_name();
+                (&info.site, &full_name, file)
+            }
+            FileSource::File2 { page, file } => (&info.site, page, file),
+            FileSource::File3 { site, page, file } => (site, page, file),
+        };
+
+        debug!("Fetching file information (site '{site}', page '{page}', file '{file}')");
+        self.resolver
+            .and_then(|resolver| resolver.file_info(site, page, file))
+    }
+
+    pub fn get_page_files(&self, site: &str, page: &str) -> Vec<String> {
+        debug!("Fetching attached files (site '{site}', page '{page}')");
+
+        match self.resolver {
+            Some(resolver) => resolver.page_files(site, page),
+            None => Vec::new(),
+        }
+    }
+
+    #[cfg(feature = "html")]
+    pub fn get_link_url<'a>(
+        &self,
+        site: &str,
+        link: &'a LinkLocation<'a>,
+    ) -> Cow<'a, str> {
+        if let (Some(resolver), LinkLocation::Page(page_ref)) = (self.resolver, link) {
+            let (site, page, _) = page_ref.fields_or(site);
+            if let Some(url) = resolver.page_url(site, page, page_ref.extra()) {
+                return Cow::Owned(url);
+            }
+        }
+
+        normalize_link(link, self)
+    }
+
     pub fn get_link_label<F>(
         &self,
         site: &str,
@@ -159,6 +235,24 @@ impl Handle {
         // TODO
         match message {
             "button-copy-clipboard" => "Copy to Clipboard",
+            "button-download-code" => "Download",
+            "button-edit" => "Edit",
+            "button-edit-append" => "Append",
+            "button-edit-sections" => "Edit Sections",
+            "button-history" => "History",
+            "button-print" => "Print",
+            "button-files" => "Files",
+            "button-tags" => "Tags",
+            "button-source" => "Source",
+            "button-backlinks" => "Backlinks",
+            "button-talk" => "Discuss",
+            "button-delete" => "Delete",
+            "button-rename" => "Rename",
+            "button-site-tools" => "Site Tools",
+            "button-edit-meta" => "Page Metadata",
+            "button-watchers" => "Watchers",
+            "button-parent" => "Parent",
+            "button-lock-page" => "Lock Page",
             "collapsible-open" => "+ open block",
             "collapsible-hide" => "- hide block",
             "table-of-contents" => "Table of Contents",
@@ -168,6 +262,8 @@ impl Handle {
             "bibliography-block-title" => "Bibliography",
             "bibliography-cite-not-found" => "Bibliography item not found",
             "image-context-bad" => "No images in this context",
+            "html-block-error" => "Unable to load HTML block",
+            "iframe-disallowed" => "Embedded content from this source is not permitted",
             "user-missing-pre" => "",
             "user-missing-post" => " does not match any existing user name",
             _ => {
@@ -177,27 +273,18 @@ impl Handle {
         }
     }
 
-    pub fn post_html(&self, info: &PageInfo, html: &str) -> String {
-        debug!("Submitting HTML to create iframe-able snippet");
-
-        let _ = info;
-        let _ = html;
-
-        // TODO
-        str!("https://example.com/")
-    }
-
-    pub fn post_code(&self, index: NonZeroUsize, code: &str) {
-        debug!("Submitting code snippet (index {})", index.get());
-
-        let _ = index;
-        let _ = code;
+    pub fn get_code_url(&self, info: &PageInfo, index: NonZeroUsize) -> String {
+        debug!("Building code snippet URL (index {})", index.get());
 
-        // TODO
+        // Mirrors Wikidot's raw code download path
+        match &info.category {
+            Some(category) => format!("/local--code/{category}:{}/{index}", info.page),
+            None => format!("/local--code/{}/{index}", info.page),
+        }
     }
 }
 
-impl BuildSiteUrl for Handle {
+impl BuildSiteUrl for Handle<'_> {
     fn build_url(&self, site: &str, path: &str, extra: Option<&str>) -> String {
         // TODO make this a parser setting
         // get url of wikijump instance here
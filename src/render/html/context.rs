@@ -21,23 +21,29 @@
 use super::builder::HtmlBuilder;
 use super::escape::escape;
 use super::meta::{HtmlMeta, HtmlMetaType};
-use super::output::HtmlOutput;
+use super::output::{HtmlBlock, HtmlCodeBlock, HtmlOutlineEntry, HtmlOutput};
 use super::random::Random;
+use super::store::{HtmlBlockKey, HtmlBlockStore};
 use crate::data::PageRef;
 use crate::data::{Backlinks, PageInfo};
 use crate::info;
 use crate::layout::Layout;
-use crate::next_index::{Incrementer, NextIndex, TableOfContentsIndex};
+use crate::next_index::{Incrementer, NextHeadingId, NextIndex};
+use crate::parsing::ParseError;
 use crate::render::Handle;
 use crate::settings::WikitextSettings;
 use crate::tree::{
-    Bibliography, BibliographyList, Element, LinkLocation, VariableScopes,
+    Bibliography, BibliographyContents, BibliographyList, CitationStyle, Element,
+    FootnoteCitations, HeadingSection, LinkLocation, ListItem, SyntaxTree,
+    VariableScopes,
 };
 use crate::url::is_url;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::{self, Write};
+use std::iter::Peekable;
 use std::num::NonZeroUsize;
+use std::slice;
 
 #[derive(Debug)]
 pub struct HtmlContext<'i, 'h, 'e, 't>
@@ -48,9 +54,13 @@ where
     meta: Vec<HtmlMeta>,
     backlinks: Backlinks<'static>,
     info: &'i PageInfo<'i>,
-    handle: &'h Handle,
+    handle: &'h Handle<'h>,
+    html_block_store: Option<&'h dyn HtmlBlockStore>,
     settings: &'e WikitextSettings,
     random: Random,
+    html_blocks: Vec<HtmlBlock>,
+    code_blocks: Vec<HtmlCodeBlock>,
+    warnings: Vec<ParseError>,
 
     //
     // Included page scopes
@@ -61,6 +71,7 @@ where
     // Fields from syntax tree
     //
     table_of_contents: &'e [Element<'t>],
+    headings: &'e [HeadingSection],
     footnotes: &'e [Vec<Element<'t>>],
     bibliographies: &'e BibliographyList<'t>,
 
@@ -68,6 +79,7 @@ where
     // Cached data
     //
     pages_exists: HashMap<PageRef, bool>,
+    heading_ids: Vec<Option<String>>,
 
     //
     // Other fields to track
@@ -76,18 +88,17 @@ where
     table_of_contents_index: Incrementer,
     equation_index: NonZeroUsize,
     footnote_index: NonZeroUsize,
+    footnote_citations: FootnoteCitations,
 }
 
 impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
     #[inline]
     pub fn new(
         info: &'i PageInfo<'i>,
-        handle: &'h Handle,
+        handle: &'h Handle<'h>,
+        html_block_store: Option<&'h dyn HtmlBlockStore>,
         settings: &'e WikitextSettings,
-        table_of_contents: &'e [Element<'t>],
-        footnotes: &'e [Vec<Element<'t>>],
-        bibliographies: &'e BibliographyList<'t>,
-        wikitext_len: usize,
+        tree: &'e SyntaxTree<'t>,
     ) -> Self {
         // Heuristic for improving rendering performance by avoiding reallocating.
         //
@@ -95,7 +106,7 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         // below ~12% of the wikitext input byte length, with the greatest differences
         // being small inputs.
         let capacity = {
-            let input = wikitext_len as f32;
+            let input = tree.wikitext_len as f32;
             let output = input * 1.12;
 
             // Basic sanity check, if this fails
@@ -114,17 +125,37 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
             backlinks: Backlinks::new(),
             info,
             handle,
+            html_block_store,
             settings,
-            random: Random::default(),
-            variables: VariableScopes::new(),
-            table_of_contents,
-            footnotes,
-            bibliographies,
+            random: Random::new(&settings.id_strategy, info),
+            html_blocks: tree
+                .html_blocks
+                .iter()
+                .map(|contents| HtmlBlock {
+                    contents: str!(contents),
+                    // Only needed for matching blocks submitted to the store
+                    hash: html_block_store
+                        .map(|_| HtmlBlockKey::new(info, contents).hash),
+                    url: None,
+                })
+                .collect(),
+            code_blocks: Vec::new(),
+            warnings: Vec::new(),
+            variables: VariableScopes::for_page(info, settings),
+            table_of_contents: &tree.table_of_contents,
+            headings: &tree.headings,
+            footnotes: &tree.footnotes,
+            bibliographies: &tree.bibliographies,
             pages_exists: HashMap::new(),
+            heading_ids: collect_heading_ids(&tree.table_of_contents),
             code_snippet_index: NonZeroUsize::new(1).unwrap(),
             table_of_contents_index: settings.id_indexer(),
             equation_index: NonZeroUsize::new(1).unwrap(),
             footnote_index: NonZeroUsize::new(1).unwrap(),
+            footnote_citations: FootnoteCitations::new(
+                tree.footnotes.len(),
+                &tree.footnote_refs,
+            ),
         }
     }
 
@@ -180,10 +211,15 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
     }
 
     #[inline]
-    pub fn handle(&self) -> &'h Handle {
+    pub fn handle(&self) -> &'h Handle<'h> {
         self.handle
     }
 
+    #[inline]
+    pub fn html_block_store(&self) -> Option<&'h dyn HtmlBlockStore> {
+        self.html_block_store
+    }
+
     #[inline]
     pub fn random(&mut self) -> &mut Random {
         &mut self.random
@@ -222,7 +258,7 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
     pub fn get_bibliography_ref(
         &self,
         label: &str,
-    ) -> Option<(usize, &'e [Element<'t>])> {
+    ) -> Option<(CitationStyle, usize, &'e BibliographyContents<'t>)> {
         self.bibliographies.get_reference(label)
     }
 
@@ -249,6 +285,16 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         index
     }
 
+    #[inline]
+    pub fn footnote_citation_total(&self, index: NonZeroUsize) -> usize {
+        self.footnote_citations.total(index)
+    }
+
+    #[inline]
+    pub fn next_footnote_citation(&mut self, index: NonZeroUsize) -> usize {
+        self.footnote_citations.next(index)
+    }
+
     #[inline]
     pub fn get_footnote(&self, index_one: NonZeroUsize) -> Option<&'e [Element<'t>]> {
         self.footnotes
@@ -290,6 +336,64 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         }
     }
 
+    /// Submits an `[[html]]` block to the store, returning its URL.
+    ///
+    /// Blocks which were already stored during this render are not resubmitted.
+    pub fn store_html_block(
+        &mut self,
+        store: &dyn HtmlBlockStore,
+        contents: &str,
+    ) -> Option<String> {
+        let key = HtmlBlockKey::new(self.info, contents);
+        let existing = self
+            .html_blocks
+            .iter()
+            .find(|block| block.hash.as_ref() == Some(&key.hash))
+            .and_then(|block| block.url.as_ref());
+
+        if let Some(url) = existing {
+            return Some(url.clone());
+        }
+
+        let url = match store.store(&key, contents) {
+            Ok(url) => url,
+            Err(error) => {
+                error!("Unable to store HTML block {}: {error}", key.hash);
+                return None;
+            }
+        };
+
+        // Record the URL for every matching block from the syntax tree,
+        // or add it if this block didn't come from there.
+        let mut found = false;
+        for block in &mut self.html_blocks {
+            if block.hash.as_ref() == Some(&key.hash) {
+                block.url = Some(url.clone());
+                found = true;
+            }
+        }
+
+        if !found {
+            self.html_blocks.push(HtmlBlock {
+                contents: str!(contents),
+                hash: Some(key.hash),
+                url: Some(url.clone()),
+            });
+        }
+
+        Some(url)
+    }
+
+    #[inline]
+    pub fn add_code_block(&mut self, code_block: HtmlCodeBlock) {
+        self.code_blocks.push(code_block);
+    }
+
+    #[inline]
+    pub fn add_warning(&mut self, warning: ParseError) {
+        self.warnings.push(warning);
+    }
+
     pub fn page_exists(&mut self, page_ref: &PageRef) -> bool {
         let (site, page, _) = page_ref.fields_or(&self.info.site);
 
@@ -311,6 +415,52 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
         self.backlinks.included_pages.push(page.to_owned());
     }
 
+    // Headings
+    fn heading_id(&self, toc_index: usize) -> String {
+        match self.heading_ids.get(toc_index) {
+            Some(Some(id)) => id.clone(),
+            _ => format!("toc{toc_index}"),
+        }
+    }
+
+    /// Builds the document outline from the headings in the syntax tree.
+    ///
+    /// Anchor IDs are found the same way as when rendering each heading,
+    /// so they always match the output.
+    fn outline(&self) -> Vec<HtmlOutlineEntry> {
+        fn nest(
+            ctx: &HtmlContext,
+            headings: &mut Peekable<slice::Iter<HeadingSection>>,
+            parent_level: u8,
+        ) -> Vec<HtmlOutlineEntry> {
+            let mut entries = Vec::new();
+
+            while let Some(section) =
+                headings.next_if(|section| section.heading.level.value() > parent_level)
+            {
+                let id = match section.toc_index {
+                    Some(index) if ctx.settings.use_true_ids => {
+                        Some(ctx.heading_id(index))
+                    }
+                    _ => None,
+                };
+
+                entries.push(HtmlOutlineEntry {
+                    level: section.heading.level,
+                    name: section.name.clone(),
+                    id,
+                    has_toc: section.heading.has_toc,
+                    span: section.span.clone(),
+                    children: nest(ctx, headings, section.heading.level.value()),
+                });
+            }
+
+            entries
+        }
+
+        nest(self, &mut self.headings.iter().peekable(), 0)
+    }
+
     // Buffer management
     #[inline]
     pub fn buffer(&mut self) -> &mut String {
@@ -341,10 +491,14 @@ impl<'i, 'h, 'e, 't> HtmlContext<'i, 'h, 'e, 't> {
 impl<'i, 'h, 'e, 't> From<HtmlContext<'i, 'h, 'e, 't>> for HtmlOutput {
     #[inline]
     fn from(ctx: HtmlContext<'i, 'h, 'e, 't>) -> HtmlOutput {
+        let outline = ctx.outline();
         let HtmlContext {
             body,
             meta,
             backlinks,
+            html_blocks,
+            code_blocks,
+            warnings,
             ..
         } = ctx;
 
@@ -352,6 +506,10 @@ impl<'i, 'h, 'e, 't> From<HtmlContext<'i, 'h, 'e, 't>> for HtmlOutput {
             body,
             meta,
             backlinks,
+            html_blocks,
+            code_blocks,
+            outline,
+            warnings,
         }
     }
 }
@@ -363,9 +521,46 @@ impl Write for HtmlContext<'_, '_, '_, '_> {
     }
 }
 
-impl NextIndex<TableOfContentsIndex> for HtmlContext<'_, '_, '_, '_> {
-    #[inline]
-    fn next(&mut self) -> Option<usize> {
-        self.next_table_of_contents_index()
+impl NextHeadingId for HtmlContext<'_, '_, '_, '_> {
+    fn next_heading_id(&mut self) -> Option<String> {
+        // The table of contents is the source of truth for heading IDs,
+        // so that its links always agree with the rendered headings.
+        let index = self.next_table_of_contents_index()?;
+        Some(self.heading_id(index))
     }
 }
+
+/// Gets the anchor IDs from table of contents links, in document order.
+///
+/// Entries without an anchor (such as when IDs were disabled
+/// during parsing) are represented as `None`.
+fn collect_heading_ids(table_of_contents: &[Element]) -> Vec<Option<String>> {
+    fn collect(ids: &mut Vec<Option<String>>, elements: &[Element]) {
+        for element in elements {
+            match element {
+                Element::List { items, .. } => {
+                    for item in items {
+                        match item {
+                            ListItem::Elements { elements, .. } => collect(ids, elements),
+                            ListItem::SubList { element } => {
+                                collect(ids, std::slice::from_ref(element))
+                            }
+                        }
+                    }
+                }
+                Element::Link {
+                    link: LinkLocation::Url(url),
+                    ..
+                } => {
+                    let id = url.strip_prefix('#').map(String::from);
+                    ids.push(id);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let mut ids = Vec::new();
+    collect(&mut ids, table_of_contents);
+    ids
+}
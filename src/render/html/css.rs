@@ -0,0 +1,897 @@
+/*
+ * render/html/css.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Enforcement of [`CssSettings`] on parsed stylesheets and inline styles.
+//!
+//! This rewrites the rules of a stylesheet in-place, so that the
+//! normal serialization process produces the restricted output.
+
+use crate::settings::CssSettings;
+use lightningcss::declaration::DeclarationBlock;
+use lightningcss::properties::Property;
+use lightningcss::properties::custom::{CustomPropertyName, TokenList, TokenOrValue};
+use lightningcss::properties::position::Position;
+use lightningcss::rules::font_face::{FontFaceProperty, Source};
+use lightningcss::rules::{CssRule, CssRuleList};
+use lightningcss::selector::{Combinator, Component, Selector, SelectorList};
+use lightningcss::stylesheet::{ParserOptions, PrinterOptions};
+use lightningcss::traits::{ParseWithOptions, ToCss};
+use std::borrow::Cow;
+
+/// Applies the given policy to a list of rules.
+///
+/// Returns `false` if the policy could not be applied, in which case
+/// the stylesheet should not be emitted.
+pub fn apply_css_policy<'i>(
+    rules: &mut CssRuleList<'i>,
+    policy: &'i CssSettings,
+) -> bool {
+    let scope = match &policy.scope {
+        None => None,
+        Some(scope) => {
+            match Selector::parse_string_with_options(scope, ParserOptions::default()) {
+                Ok(selector) => Some(selector),
+                Err(error) => {
+                    error!("Invalid CSS scope selector '{scope}': {error}");
+                    return false;
+                }
+            }
+        }
+    };
+
+    filter_rules(rules, policy, scope.as_ref());
+    true
+}
+
+/// Filters a list of rules, rewriting selectors if a scope is passed.
+///
+/// Rules nested within style rules have their selectors left as-is,
+/// since they are already relative to the (rewritten) parent.
+fn filter_rules<'i>(
+    rules: &mut CssRuleList<'i>,
+    policy: &CssSettings,
+    scope: Option<&Selector<'i>>,
+) {
+    rules.0.retain_mut(|rule| match rule {
+        CssRule::Import(import) => {
+            if scope.is_some() {
+                debug!(
+                    "Removing @import rule for '{}', cannot be scoped",
+                    import.url
+                );
+                return false;
+            }
+
+            if !policy.allow_import {
+                debug!("Removing @import rule for '{}'", import.url);
+                return false;
+            }
+
+            if !policy.allow_external_urls && is_external_url(&import.url) {
+                debug!("Removing @import rule for external URL '{}'", import.url);
+                return false;
+            }
+
+            true
+        }
+        CssRule::MozDocument(_) if scope.is_some() => {
+            debug!("Removing @-moz-document rule, cannot be scoped");
+            false
+        }
+        CssRule::MozDocument(document) => {
+            filter_rules(&mut document.rules, policy, None);
+            true
+        }
+        CssRule::Style(style) => {
+            if let Some(scope) = scope {
+                scope_selectors(&mut style.selectors, scope);
+            }
+
+            filter_declarations(&mut style.declarations, policy);
+            filter_rules(&mut style.rules, policy, None);
+            true
+        }
+        CssRule::Nesting(nesting) => {
+            if let Some(scope) = scope {
+                scope_selectors(&mut nesting.style.selectors, scope);
+            }
+
+            filter_declarations(&mut nesting.style.declarations, policy);
+            filter_rules(&mut nesting.style.rules, policy, None);
+            true
+        }
+        CssRule::Scope(scope_rule) => {
+            // Rules within @scope are limited to the scoping root,
+            // so only the root selector itself needs to be rewritten.
+            if let Some(scope) = scope
+                && let Some(selectors) = &mut scope_rule.scope_start
+            {
+                scope_selectors(selectors, scope);
+            }
+
+            filter_rules(&mut scope_rule.rules, policy, None);
+            true
+        }
+        CssRule::Media(media) => {
+            filter_rules(&mut media.rules, policy, scope);
+            true
+        }
+        CssRule::Supports(supports) => {
+            filter_rules(&mut supports.rules, policy, scope);
+            true
+        }
+        CssRule::Container(container) => {
+            filter_rules(&mut container.rules, policy, scope);
+            true
+        }
+        CssRule::LayerBlock(layer) => {
+            filter_rules(&mut layer.rules, policy, scope);
+            true
+        }
+        CssRule::StartingStyle(starting) => {
+            filter_rules(&mut starting.rules, policy, scope);
+            true
+        }
+        CssRule::NestedDeclarations(nested) => {
+            filter_declarations(&mut nested.declarations, policy);
+            true
+        }
+        CssRule::Keyframes(keyframes) => {
+            for keyframe in &mut keyframes.keyframes {
+                filter_declarations(&mut keyframe.declarations, policy);
+            }
+
+            true
+        }
+        CssRule::Page(page) => {
+            filter_declarations(&mut page.declarations, policy);
+            true
+        }
+        CssRule::PositionTry(position_try) => {
+            filter_declarations(&mut position_try.declarations, policy);
+            true
+        }
+        CssRule::FontFace(font_face) => {
+            if !policy.allow_external_urls {
+                for property in &mut font_face.properties {
+                    if let FontFaceProperty::Source(sources) = property {
+                        sources.retain(|source| match source {
+                            Source::Url(source) => !is_external_url(&source.url.url),
+                            Source::Local(_) => true,
+                        });
+                    }
+                }
+            }
+
+            true
+        }
+        CssRule::Unknown(unknown) => {
+            debug!("Removing unknown at-rule '@{}'", unknown.name);
+            false
+        }
+        CssRule::Custom(_) => {
+            debug!("Removing custom at-rule");
+            false
+        }
+        // Other rules, such as @property or @counter-style, have no
+        // nested rules or declaration blocks, so check the whole rule.
+        _ => {
+            if policy.allow_external_urls {
+                return true;
+            }
+
+            match rule.to_css_string(PrinterOptions::default()) {
+                Ok(css) => {
+                    if css_urls(&css).into_iter().any(is_external_url) {
+                        debug!("Removing rule with external URL: {css}");
+                        return false;
+                    }
+
+                    true
+                }
+                Err(error) => {
+                    warn!("Unable to serialize CSS rule, removing: {error}");
+                    false
+                }
+            }
+        }
+    });
+}
+
+/// Removes any declarations not permitted by the policy.
+pub fn filter_declarations(declarations: &mut DeclarationBlock, policy: &CssSettings) {
+    let is_allowed = |property: &Property| allowed_property(property, policy);
+
+    declarations.declarations.retain(is_allowed);
+    declarations.important_declarations.retain(is_allowed);
+}
+
+/// Determines if a single declaration is permitted by the policy.
+pub fn allowed_property(property: &Property, policy: &CssSettings) -> bool {
+    // Variables are substituted by the browser, so their final
+    // values cannot be checked if anything is being filtered.
+    if (!policy.allow_fixed_position || !policy.allow_external_urls)
+        && uses_variable(property)
+    {
+        debug!("Removing declaration with variable substitution");
+        return false;
+    }
+
+    if !policy.allow_fixed_position
+        && matches!(property, Property::Position(Position::Fixed))
+    {
+        debug!("Removing 'position: fixed' declaration");
+        return false;
+    }
+
+    if !policy.allow_external_urls {
+        match property.value_to_css_string(PrinterOptions::default()) {
+            Ok(value) => {
+                if css_urls(&value).into_iter().any(is_external_url) {
+                    debug!("Removing declaration with external URL: {value}");
+                    return false;
+                }
+            }
+            Err(error) => {
+                warn!("Unable to serialize CSS property, removing: {error}");
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Sanitizes the value of an inline `style` attribute.
+///
+/// Each declaration is parsed and checked separately. Malformed declarations,
+/// unknown properties, and properties not permitted by the policy are removed,
+/// and the others are kept as written. If nothing is removed, the style is
+/// returned unchanged. The result may be empty.
+pub fn sanitize_inline_style<'a>(style: &'a str, policy: &CssSettings) -> Cow<'a, str> {
+    let mut kept = Vec::new();
+    let mut removed = false;
+
+    for declaration in split_declarations(style) {
+        let declaration = declaration.trim();
+        if declaration.is_empty() {
+            continue;
+        }
+
+        if allowed_inline_declaration(declaration, policy) {
+            kept.push(declaration);
+        } else {
+            removed = true;
+        }
+    }
+
+    if kept.is_empty() {
+        return Cow::Borrowed("");
+    }
+
+    if !removed {
+        return Cow::Borrowed(style);
+    }
+
+    Cow::Owned(kept.join("; "))
+}
+
+/// Determines if the text of a single inline declaration is permitted by the policy.
+fn allowed_inline_declaration(declaration: &str, policy: &CssSettings) -> bool {
+    let declarations =
+        match DeclarationBlock::parse_string(declaration, ParserOptions::default()) {
+            Ok(declarations) => declarations,
+            Err(error) => {
+                debug!("Unable to parse inline style declaration, removing: {error}");
+                return false;
+            }
+        };
+
+    // Only one declaration was split out, so anything else is malformed
+    let property = match (
+        declarations.declarations.as_slice(),
+        declarations.important_declarations.as_slice(),
+    ) {
+        ([property], []) | ([], [property]) => property,
+        _ => {
+            debug!("Inline style declaration is malformed, removing: {declaration}");
+            return false;
+        }
+    };
+
+    let name = property.property_id();
+    let name = name.name();
+
+    if !policy.inline_property_allowed(name) {
+        debug!("Removing disallowed inline style property '{name}'");
+        return false;
+    }
+
+    match property {
+        // Unknown property names are parsed as custom properties
+        Property::Custom(custom)
+            if matches!(custom.name, CustomPropertyName::Unknown(_)) =>
+        {
+            debug!("Removing unknown inline style property '{name}'");
+            return false;
+        }
+
+        // Values which failed to parse are only permitted
+        // if this is because of a variable substitution.
+        Property::Unparsed(unparsed) if !has_variable(&unparsed.value) => {
+            debug!("Removing invalid inline style value for '{name}'");
+            return false;
+        }
+
+        _ => (),
+    }
+
+    allowed_property(property, policy)
+}
+
+/// Splits an inline style at each semicolon which ends a declaration.
+///
+/// Semicolons within strings, comments, or brackets are skipped.
+fn split_declarations(style: &str) -> Vec<&str> {
+    let mut declarations = Vec::new();
+    let mut chars = style.char_indices().peekable();
+    let mut start = 0;
+    let mut depth = 0_usize;
+
+    while let Some((index, ch)) = chars.next() {
+        match ch {
+            '"' | '\'' => {
+                let mut escaped = false;
+                for (_, next) in chars.by_ref() {
+                    match next {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        _ if next == ch => break,
+                        _ => (),
+                    }
+                }
+            }
+            '\\' => {
+                chars.next();
+            }
+            '/' if chars.next_if(|&(_, next)| next == '*').is_some() => {
+                let mut previous = ' ';
+                for (_, next) in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+
+                    previous = next;
+                }
+            }
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => {
+                declarations.push(&style[start..index]);
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+
+    declarations.push(&style[start..]);
+    declarations
+}
+
+fn uses_variable(property: &Property) -> bool {
+    match property {
+        Property::Unparsed(unparsed) => has_variable(&unparsed.value),
+        Property::Custom(custom) => has_variable(&custom.value),
+        _ => false,
+    }
+}
+
+fn has_variable(tokens: &TokenList) -> bool {
+    tokens.0.iter().any(|token| match token {
+        TokenOrValue::Var(_) | TokenOrValue::Env(_) => true,
+        TokenOrValue::Function(function) => has_variable(&function.arguments),
+        _ => false,
+    })
+}
+
+/// Rewrites each selector in the list to be nested within the scope.
+fn scope_selectors<'i>(selectors: &mut SelectorList<'i>, scope: &Selector<'i>) {
+    for selector in selectors.0.iter_mut() {
+        *selector = scope_selector(selector, scope);
+    }
+}
+
+/// Nests a selector within the scope.
+///
+/// If the first compound selector refers to the document root (via `:root`,
+/// `html` or `body`), then those parts are replaced with the scope instead.
+fn scope_selector<'i>(selector: &Selector<'i>, scope: &Selector<'i>) -> Selector<'i> {
+    let components = selector
+        .iter_raw_parse_order_from(0)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let first_compound_len = components
+        .iter()
+        .position(|component| matches!(component, Component::Combinator(_)))
+        .unwrap_or(components.len());
+
+    let (first_compound, rest) = components.split_at(first_compound_len);
+    let mut scoped = scope
+        .iter_raw_parse_order_from(0)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if first_compound.iter().any(is_root_component) {
+        // Merge the remaining parts of the compound into the scope,
+        // so that "body.dark" becomes "#page-content.dark".
+        scoped.extend(
+            first_compound
+                .iter()
+                .filter(|component| !is_root_component(component))
+                .cloned(),
+        );
+        scoped.extend(rest.iter().cloned());
+    } else {
+        scoped.push(Component::Combinator(Combinator::Descendant));
+        scoped.extend(components);
+    }
+
+    Selector::from(scoped)
+}
+
+fn is_root_component(component: &Component) -> bool {
+    match component {
+        Component::Root => true,
+        Component::LocalName(local) => {
+            let name: &str = &local.lower_name.0;
+            name == "html" || name == "body"
+        }
+        _ => false,
+    }
+}
+
+/// Gets the contents of all URL values in serialized CSS.
+///
+/// This includes `url()` values, as well as plain strings in `image-set()`,
+/// which are also interpreted as URLs.
+fn css_urls(css: &str) -> Vec<&str> {
+    let mut urls = Vec::new();
+    let mut remaining = css;
+
+    while let Some(start) = find_ascii_case_insensitive(remaining, "url(") {
+        let value = remaining[start + 4..].trim_start();
+        let (url, rest) = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => read_string(&value[1..], quote),
+            _ => {
+                let end = value.find(')').unwrap_or(value.len());
+                (value[..end].trim_end(), &value[end..])
+            }
+        };
+
+        urls.push(url);
+        remaining = rest;
+    }
+
+    let mut remaining = css;
+    while let Some(start) = find_ascii_case_insensitive(remaining, "image-set(") {
+        let mut value = &remaining[start + 10..];
+        let mut depth = 1;
+
+        while depth > 0 {
+            let Some(ch) = value.chars().next() else {
+                break;
+            };
+
+            value = &value[ch.len_utf8()..];
+            match ch {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                '"' | '\'' => {
+                    let (string, rest) = read_string(value, ch);
+                    if depth == 1 {
+                        urls.push(string);
+                    }
+
+                    value = rest;
+                }
+                _ => (),
+            }
+        }
+
+        remaining = value;
+    }
+
+    urls
+}
+
+/// Reads a string up to its closing quote.
+///
+/// Returns the string contents, and the input after the closing quote.
+fn read_string(input: &str, quote: char) -> (&str, &str) {
+    let mut escaped = false;
+
+    for (index, ch) in input.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if ch == quote => return (&input[..index], &input[index + 1..]),
+            _ => (),
+        }
+    }
+
+    (input, "")
+}
+
+fn find_ascii_case_insensitive(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Determines if a URL refers to another origin.
+///
+/// Relative and root-relative paths are permitted, as are `data:` URIs.
+/// Anything else with a scheme, or starting with two slashes
+/// (which browsers treat as protocol-relative), is external.
+pub fn is_external_url(url: &str) -> bool {
+    let url = url.trim();
+
+    // Protocol-relative, browsers treat backslashes as slashes here
+    let mut chars = url.chars();
+    if matches!(
+        (chars.next(), chars.next()),
+        (Some('/' | '\\'), Some('/' | '\\')),
+    ) {
+        return true;
+    }
+
+    // Check for a URL scheme
+    match url.find(':') {
+        None => false,
+        Some(index) => {
+            let scheme = &url[..index];
+            let is_scheme = !scheme.is_empty()
+                && scheme.chars().all(|ch| {
+                    ch.is_ascii_alphanumeric() || matches!(ch, '+' | '-' | '.')
+                });
+
+            is_scheme && !scheme.eq_ignore_ascii_case("data")
+        }
+    }
+}
+
+#[test]
+fn external_urls() {
+    macro_rules! test {
+        ($url:expr, $expected:expr $(,)?) => {
+            assert_eq!(
+                is_external_url($url),
+                $expected,
+                "External URL check for {:?} doesn't match expected",
+                $url,
+            );
+        };
+    }
+
+    test!("image.png", false);
+    test!("/local--files/page/image.png", false);
+    test!("../image.png", false);
+    test!("data:image/png;base64,AAAA", false);
+    test!("DATA:image/png;base64,AAAA", false);
+    test!("https://example.com/image.png", true);
+    test!("HTTP://example.com/image.png", true);
+    test!("//example.com/image.png", true);
+    test!("\\\\example.com/image.png", true);
+    test!("/\\example.com/image.png", true);
+    test!("javascript:alert(1)", true);
+}
+
+#[test]
+fn urls_in_css() {
+    macro_rules! test {
+        ($css:expr, $expected:expr $(,)?) => {
+            assert_eq!(
+                css_urls($css),
+                $expected as Vec<&str>,
+                "Extracted URLs for {:?} don't match expected",
+                $css,
+            );
+        };
+    }
+
+    test!("red", vec![]);
+    test!("url(a.png)", vec!["a.png"]);
+    test!("URL( a.png )", vec!["a.png"]);
+    test!(
+        r#"url("a).png") no-repeat, url('b.png')"#,
+        vec!["a).png", "b.png"]
+    );
+    test!(r#"url("a\".png")"#, vec![r#"a\".png"#]);
+    test!(
+        r#"image-set("a.png" 1x, url(b.png) 2x)"#,
+        vec!["b.png", "a.png"]
+    );
+    test!(
+        r#"-webkit-image-set(linear-gradient(red, "x"), 'c.png' 1x)"#,
+        vec!["c.png"],
+    );
+}
+
+#[test]
+fn css_policy() {
+    use lightningcss::stylesheet::StyleSheet;
+
+    let policy = CssSettings {
+        scope: Some(cow!("#page-content")),
+        ..CssSettings::RESTRICTED
+    };
+
+    macro_rules! test {
+        ($input:expr, $expected:expr $(,)?) => {{
+            let mut stylesheet = StyleSheet::parse($input, ParserOptions::default())
+                .expect("Unable to parse stylesheet");
+
+            assert!(
+                apply_css_policy(&mut stylesheet.rules, &policy),
+                "Unable to apply CSS policy",
+            );
+
+            let output = stylesheet
+                .to_css(PrinterOptions {
+                    minify: true,
+                    ..Default::default()
+                })
+                .expect("Unable to serialize stylesheet")
+                .code;
+
+            assert_eq!(
+                output, $expected,
+                "Scoped CSS output doesn't match expected"
+            );
+        }};
+    }
+
+    test!(".note { color: red; }", "#page-content .note{color:red}");
+    test!("* { margin: 0; }", "#page-content *{margin:0}");
+    test!(
+        "a, #header > b { color: red; }",
+        "#page-content a,#page-content #header>b{color:red}"
+    );
+    test!(":root { --accent: blue; }", "#page-content{--accent:blue}");
+    test!("body { color: red; }", "#page-content{color:red}");
+    test!(
+        "body.dark p { color: red; }",
+        "#page-content.dark p{color:red}"
+    );
+    test!(
+        "html:hover .x { color: red; }",
+        "#page-content:hover .x{color:red}"
+    );
+    test!(
+        "@media (width < 600px) { .x { color: red; } }",
+        "@media (width<600px){#page-content .x{color:red}}",
+    );
+    test!(
+        ".x { .y { color: red; } }",
+        "#page-content .x{& .y{color:red}}"
+    );
+    test!(
+        "@import url('https://example.com/evil.css'); .x { color: red; }",
+        "#page-content .x{color:red}"
+    );
+    test!(".x { position: fixed; top: 0; }", "#page-content .x{top:0}",);
+    test!(
+        ".x { position: absolute; }",
+        "#page-content .x{position:absolute}",
+    );
+    test!(
+        ".x { background: url('https://example.com/a.png'); color: red; }",
+        "#page-content .x{color:red}",
+    );
+    test!(
+        ".x { background: url('/local--files/page/a.png'); }",
+        "#page-content .x{background:url(/local--files/page/a.png)}",
+    );
+    test!(
+        "@font-face { font-family: X; src: url('//example.com/x.woff'), url('x.woff'); }",
+        "@font-face{font-family:X;src:url(x.woff)}",
+    );
+    test!(
+        "@property --x { syntax: '<url>'; inherits: false; initial-value: url(https://example.com/a.png); } .x { color: red; }",
+        "#page-content .x{color:red}",
+    );
+    test!(
+        "@property --x { syntax: '<color>'; inherits: false; initial-value: red; }",
+        "@property --x{syntax:\"<color>\";inherits:false;initial-value:red}",
+    );
+    test!(
+        "@counter-style x { system: cyclic; symbols: url(//example.com/a.png); }",
+        "",
+    );
+    test!(
+        "@unknown-rule { color: red; } .x { color: red; }",
+        "#page-content .x{color:red}"
+    );
+    test!(
+        ".x { --p: fixed; position: var(--p); color: var(--c, red); }",
+        "#page-content .x{--p:fixed}",
+    );
+    test!(
+        ".x { --u: 'https://example.com/a.png'; background-image: image-set(var(--u) 1x); }",
+        "#page-content .x{--u:\"https://example.com/a.png\"}",
+    );
+    test!(".x { --a: var(--b); }", "#page-content .x{}");
+}
+
+#[test]
+fn css_policy_import() {
+    use lightningcss::stylesheet::StyleSheet;
+
+    macro_rules! test {
+        ($policy:expr, $input:expr, $expected:expr $(,)?) => {{
+            let mut stylesheet = StyleSheet::parse($input, ParserOptions::default())
+                .expect("Unable to parse stylesheet");
+
+            assert!(
+                apply_css_policy(&mut stylesheet.rules, &$policy),
+                "Unable to apply CSS policy",
+            );
+
+            let output = stylesheet
+                .to_css(PrinterOptions {
+                    minify: true,
+                    ..Default::default()
+                })
+                .expect("Unable to serialize stylesheet")
+                .code;
+
+            assert_eq!(output, $expected, "CSS output doesn't match expected");
+        }};
+    }
+
+    let input = "@import url('theme.css'); .x { color: red; }";
+    test!(
+        CssSettings::PERMISSIVE,
+        input,
+        "@import \"theme.css\";.x{color:red}",
+    );
+
+    // Imported stylesheets cannot be scoped, so they are always removed
+    let scoped = CssSettings {
+        scope: Some(cow!("#page-content")),
+        ..CssSettings::PERMISSIVE
+    };
+    test!(scoped, input, "#page-content .x{color:red}");
+}
+
+#[test]
+fn css_policy_invalid_scope() {
+    use lightningcss::stylesheet::StyleSheet;
+
+    let policy = CssSettings {
+        scope: Some(cow!("{{ not a selector")),
+        ..CssSettings::PERMISSIVE
+    };
+
+    let mut stylesheet =
+        StyleSheet::parse(".x { color: red; }", ParserOptions::default())
+            .expect("Unable to parse stylesheet");
+
+    assert!(!apply_css_policy(&mut stylesheet.rules, &policy));
+}
+
+#[test]
+fn inline_styles() {
+    macro_rules! test {
+        ($policy:expr, $input:expr, $expected:expr $(,)?) => {
+            assert_eq!(
+                sanitize_inline_style($input, &$policy),
+                $expected,
+                "Sanitized inline style for {:?} doesn't match expected",
+                $input,
+            );
+        };
+    }
+
+    let permissive = CssSettings::PERMISSIVE;
+    test!(permissive, "color: red;", "color: red;");
+    test!(permissive, "COLOR: Red !important", "COLOR: Red !important");
+    test!(permissive, "color: blue; ", "color: blue; ");
+    test!(
+        permissive,
+        "font-size: 125%; garbage; width: 2em",
+        "font-size: 125%; width: 2em"
+    );
+    test!(
+        permissive,
+        "color: red; } body { display: none",
+        "color: red"
+    );
+    test!(
+        permissive,
+        "position: fixed; top: 0",
+        "position: fixed; top: 0"
+    );
+    test!(
+        permissive,
+        "width: expression(alert(1)); color: blue",
+        "color: blue"
+    );
+    test!(
+        permissive,
+        "font-family: 'a;b', serif; garbage",
+        "font-family: 'a;b', serif"
+    );
+    test!(
+        permissive,
+        "font-family: \"x\\\";y\"; garbage",
+        "font-family: \"x\\\";y\""
+    );
+    test!(
+        permissive,
+        "background: url(data:image/png;base64,AAAA) /* ; */; color: red; nonsense",
+        "background: url(data:image/png;base64,AAAA) /* ; */; color: red"
+    );
+    test!(permissive, "  ;  ", "");
+    test!(permissive, "behavior: url(x.htc); -moz-binding: url(x)", "");
+    test!(
+        permissive,
+        "color: var(--accent); --accent: red",
+        "color: var(--accent); --accent: red"
+    );
+    test!(permissive, "", "");
+
+    let restricted = CssSettings::RESTRICTED;
+    test!(restricted, "color: red; z-index: 9999", "color: red");
+    test!(restricted, "position: fixed; inset: 0", "");
+    test!(
+        restricted,
+        "background: url('https://example.com/track.png') red",
+        ""
+    );
+    test!(
+        restricted,
+        "background-image: image-set('//example.com/a.png' 1x)",
+        ""
+    );
+    test!(
+        restricted,
+        "background-image: url(/local--files/page/a.png)",
+        "background-image: url(/local--files/page/a.png)"
+    );
+    test!(restricted, "--tracker: url(https://example.com/a.png)", "");
+    test!(
+        restricted,
+        "--p: fixed; position: var(--p); color: var(--c)",
+        "--p: fixed"
+    );
+
+    let custom = CssSettings {
+        inline_properties: Some(Cow::Borrowed(&[Cow::Borrowed("Font-Size")])),
+        ..CssSettings::PERMISSIVE
+    };
+    test!(
+        custom,
+        "font-size: 2em; color: red; --x: 1",
+        "font-size: 2em; --x: 1"
+    );
+}
@@ -0,0 +1,81 @@
+/* Interactive behavior for ftml output, see INTERACTIVE_SCRIPT */
+
+(function () {
+  "use strict";
+
+  function tabButtons(button) {
+    return Array.prototype.filter.call(button.parentElement.children, function (child) {
+      return child.matches(".wj-tabs-button");
+    });
+  }
+
+  function selectTab(button) {
+    tabButtons(button).forEach(function (other) {
+      var selected = other === button;
+      var panel = document.getElementById(other.getAttribute("aria-controls"));
+
+      other.setAttribute("aria-selected", selected ? "true" : "false");
+      other.setAttribute("tabindex", selected ? "0" : "-1");
+
+      if (panel) {
+        panel.hidden = !selected;
+      }
+    });
+  }
+
+  document.addEventListener("click", function (event) {
+    if (!(event.target instanceof Element)) {
+      return;
+    }
+
+    // Switch tabs
+    var tab = event.target.closest(".wj-tabs-button");
+    if (tab) {
+      selectTab(tab);
+      return;
+    }
+
+    // Close a collapsible from its bottom button
+    var bottom = event.target.closest(".wj-collapsible-button-bottom");
+    if (bottom) {
+      var collapsible = bottom.closest(".wj-collapsible");
+      collapsible.open = false;
+      collapsible.scrollIntoView({ block: "nearest" });
+    }
+  });
+
+  document.addEventListener("keydown", function (event) {
+    if (!(event.target instanceof Element) || !event.target.matches(".wj-tabs-button")) {
+      return;
+    }
+
+    var buttons = tabButtons(event.target);
+    var index = buttons.indexOf(event.target);
+    var next;
+
+    switch (event.key) {
+      case "ArrowLeft":
+        next = buttons[(index + buttons.length - 1) % buttons.length];
+        break;
+      case "ArrowRight":
+        next = buttons[(index + 1) % buttons.length];
+        break;
+      case "Home":
+        next = buttons[0];
+        break;
+      case "End":
+        next = buttons[buttons.length - 1];
+        break;
+      case "Enter":
+      case " ":
+        next = event.target;
+        break;
+      default:
+        return;
+    }
+
+    event.preventDefault();
+    selectTab(next);
+    next.focus();
+  });
+})();
@@ -0,0 +1,154 @@
+/*
+ * render/html/document/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Rendering of standalone HTML documents, which can be opened directly.
+//!
+//! This wraps the rendered body with everything needed around it:
+//! the doctype, `lang` and `dir` attributes, the page title, and meta tags.
+//! Optionally, it can also inline a baseline stylesheet for the generated
+//! `wj-*` elements, and scripts for interactive elements like tabs.
+
+use super::escape::escape;
+use super::{HtmlOutput, HtmlRender};
+use crate::data::PageInfo;
+use crate::render::Render;
+use crate::settings::WikitextSettings;
+use crate::tree::SyntaxTree;
+use icu_locale::{LanguageIdentifier, LocaleDirectionality};
+
+/// A minimal stylesheet for elements generated by the HTML renderer.
+///
+/// This covers collapsibles, tab views, math and footnotes, so that they
+/// display and behave correctly without any other styling present.
+pub const BASELINE_STYLESHEET: &str = include_str!("baseline.css");
+
+/// A script implementing interactive behavior for collapsibles and tab views.
+pub const INTERACTIVE_SCRIPT: &str = include_str!("interactive.js");
+
+/// Renders syntax trees into complete HTML documents.
+///
+/// Used as a [`Render`], this renders the tree with [`HtmlRender`] and wraps the output.
+/// Existing HTML output can be wrapped with [`wrap()`](Self::wrap).
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct HtmlDocumentRender {
+    /// Whether to inline [`BASELINE_STYLESHEET`] in the document head.
+    pub stylesheet: bool,
+
+    /// Whether to inline [`INTERACTIVE_SCRIPT`] at the end of the document body.
+    pub scripts: bool,
+}
+
+impl HtmlDocumentRender {
+    /// Produces documents containing only the rendered output.
+    pub const BARE: Self = HtmlDocumentRender {
+        stylesheet: false,
+        scripts: false,
+    };
+
+    /// Produces self-contained documents, with the stylesheet and scripts inlined.
+    pub const STANDALONE: Self = HtmlDocumentRender {
+        stylesheet: true,
+        scripts: true,
+    };
+
+    /// Wraps rendered HTML output into a complete document.
+    pub fn wrap(&self, output: &HtmlOutput, page_info: &PageInfo) -> String {
+        let mut html = String::with_capacity(output.body.len() + 1024);
+
+        html.push_str("<!DOCTYPE html>\n<html");
+        if let Some(direction) = language_direction(&page_info.language) {
+            html.push_str(" lang=\"");
+            escape(&mut html, &page_info.language);
+            str_write!(html, "\" dir=\"{direction}\"");
+        }
+        html.push_str(">\n<head>\n");
+        html.push_str("<meta charset=\"utf-8\" />\n");
+        html.push_str(
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\" />\n",
+        );
+
+        html.push_str("<title>");
+        escape(&mut html, &page_info.title);
+        html.push_str("</title>\n");
+
+        for meta in &output.meta {
+            meta.render(&mut html);
+            html.push('\n');
+        }
+
+        if self.stylesheet {
+            html.push_str("<style>\n");
+            html.push_str(BASELINE_STYLESHEET);
+            html.push_str("</style>\n");
+        }
+
+        html.push_str("</head>\n<body>\n");
+        html.push_str(&output.body);
+        html.push('\n');
+
+        if self.scripts {
+            html.push_str("<script>\n");
+            html.push_str(INTERACTIVE_SCRIPT);
+            html.push_str("</script>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+impl Render for HtmlDocumentRender {
+    type Output = String;
+
+    fn render(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+    ) -> String {
+        let output = HtmlRender.render(tree, page_info, settings);
+        self.wrap(&output, page_info)
+    }
+}
+
+/// Gets the text direction for a language, or `None` if it is unspecified or invalid.
+fn language_direction(language: &str) -> Option<&'static str> {
+    // The "default" language is a placeholder, not a language tag
+    if language == "default" {
+        return None;
+    }
+
+    let language = LanguageIdentifier::try_from_str(language).ok()?;
+    if LocaleDirectionality::new_common().is_right_to_left(&language) {
+        Some("rtl")
+    } else {
+        Some("ltr")
+    }
+}
+
+#[test]
+fn language_directions() {
+    assert_eq!(language_direction("default"), None);
+    assert_eq!(language_direction("not a language"), None);
+    assert_eq!(language_direction("en"), Some("ltr"));
+    assert_eq!(language_direction("zh-Hant"), Some("ltr"));
+    assert_eq!(language_direction("ar"), Some("rtl"));
+    assert_eq!(language_direction("he-IL"), Some("rtl"));
+}
@@ -19,19 +19,22 @@
  */
 
 use super::prelude::*;
-use crate::tree::Bibliography;
+use crate::tree::{Bibliography, BibliographyContents, CitationStyle, ReferencePart};
+use crate::url::normalize_href;
 
 pub fn render_bibcite(ctx: &mut HtmlContext, label: &str, brackets: bool) {
     debug!("Rendering bibliography citation (label {label}, brackets {brackets})");
 
     match ctx.get_bibliography_ref(label) {
         // Valid bibliography reference, render it
-        Some((index, contents)) => {
+        Some((style, index, contents)) => {
             // TODO make this into a locale template string
             let reference_string = ctx
                 .handle()
                 .get_message(ctx.language(), "bibliography-reference");
-            let label = format!("{reference_string} {index}.");
+            let cite_label = format!("{reference_string} {index}.");
+            let cite_text = style.cite_text(index, label, contents);
+            let brackets = brackets && style.numbered();
 
             // TODO: For now, copied from footnotes
             ctx.html()
@@ -50,10 +53,10 @@ pub fn render_bibcite(ctx: &mut HtmlContext, label: &str, brackets: bool) {
                         .attr(attr!(
                             "class" => "wj-bibliography-ref-marker",
                             "role" => "link",
-                            "aria-label" => &label,
+                            "aria-label" => &cite_label,
                             "data-id" => &id,
                         ))
-                        .contents(&id);
+                        .contents(&cite_text);
 
                     if brackets {
                         ctx.push_raw(']');
@@ -76,13 +79,15 @@ pub fn render_bibcite(ctx: &mut HtmlContext, label: &str, brackets: bool) {
                                 .attr(
                                     attr!("class" => "wj-bibliography-ref-tooltip-label"),
                                 )
-                                .contents(&label);
+                                .contents(&cite_label);
 
                             // Actual tooltip contents
                             ctx.html()
                                 .span()
                                 .attr(attr!("class" => "wj-bibliography-ref-contents"))
-                                .contents(contents);
+                                .inner(|ctx| {
+                                    render_bibliography_contents(ctx, style, contents)
+                                });
                         });
                 });
         }
@@ -126,15 +131,19 @@ pub fn render_bibliography(
 
     ctx.html()
         .div()
-        .attr(attr!("class" => "wj-bibliography bibitems"))
+        .attr(attr!(
+            "class" => "wj-bibliography bibitems",
+            "data-citation-style" => bibliography.style().name(),
+        ))
         .inner(|ctx| {
             ctx.html()
                 .div()
                 .attr(attr!("class" => "wj-bibliography-title title"))
                 .contents(title);
 
+            let style = bibliography.style();
             let mut id = String::new();
-            for (entry_index, (_, elements)) in bibliography.slice().iter().enumerate() {
+            for (entry_index, (_, contents)) in bibliography.slice().iter().enumerate() {
                 // Convert to 1-indexing
                 let bibliography_index = bibliography_index + 1;
                 let entry_index = entry_index + 1;
@@ -155,26 +164,63 @@ pub fn render_bibliography(
                     .div()
                     .attr(attr!("class" => "wj-bibliography-item bibitem", "id" => &id))
                     .inner(|ctx| {
-                        // Number and clickable anchor
-                        ctx.html()
-                            .element("wj-bibliography-item-marker")
-                            .attr(attr!(
-                                "class" => "wj-bibliography-item-marker",
-                                "type" => "button",
-                                "role" => "link",
-                            ))
-                            .inner(|ctx| {
-                                str_write!(ctx, "{entry_index}");
-
-                                // Period after entry number. Has special class to permit styling.
-                                ctx.html()
-                                    .span()
-                                    .attr(attr!("class" => "wj-bibliography-sep"))
-                                    .contents(".");
-                            });
-
-                        render_elements(ctx, elements);
+                        // Number and clickable anchor, if references are numbered
+                        if style.numbered() {
+                            ctx.html()
+                                .element("wj-bibliography-item-marker")
+                                .attr(attr!(
+                                    "class" => "wj-bibliography-item-marker",
+                                    "type" => "button",
+                                    "role" => "link",
+                                ))
+                                .inner(|ctx| {
+                                    str_write!(ctx, "{entry_index}");
+
+                                    // Period after entry number. Has special class to permit styling.
+                                    ctx.html()
+                                        .span()
+                                        .attr(attr!("class" => "wj-bibliography-sep"))
+                                        .contents(".");
+                                });
+                        }
+
+                        render_bibliography_contents(ctx, style, contents);
                     });
             }
         });
 }
+
+fn render_bibliography_contents(
+    ctx: &mut HtmlContext,
+    style: CitationStyle,
+    contents: &BibliographyContents,
+) {
+    let reference = match contents {
+        BibliographyContents::Elements(elements) => {
+            render_elements(ctx, elements);
+            return;
+        }
+        BibliographyContents::Reference(reference) => reference,
+    };
+
+    for part in style.format_reference(reference) {
+        match part {
+            ReferencePart::Text(text) => ctx.html().text(&text),
+            ReferencePart::Title(title) => {
+                ctx.html()
+                    .em()
+                    .attr(attr!("class" => "wj-bibliography-reference-title"))
+                    .contents(title);
+            }
+            ReferencePart::Link(link) => {
+                ctx.html()
+                    .a()
+                    .attr(attr!(
+                        "class" => "wj-bibliography-reference-link",
+                        "href" => &normalize_href(&link, None),
+                    ))
+                    .contents(link.as_ref());
+            }
+        }
+    }
+}
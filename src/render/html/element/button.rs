@@ -0,0 +1,66 @@
+/*
+ * render/html/element/button.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::tree::{AttributeMap, PageAction};
+
+pub fn render_button(
+    ctx: &mut HtmlContext,
+    action: PageAction,
+    label: Option<&str>,
+    attributes: &AttributeMap,
+) {
+    debug!(
+        "Rendering button (action {}, label {})",
+        action.name(),
+        label.unwrap_or("<default>"),
+    );
+
+    let label = match label {
+        Some(label) => label,
+        None => ctx.handle().get_message(ctx.language(), action.message()),
+    };
+
+    // The host binds behavior to buttons using the action attribute
+    match ctx.layout() {
+        Layout::Wikidot => {
+            ctx.html()
+                .a()
+                .attr(attr!(
+                    "href" => "javascript:;",
+                    "class" => "btn btn-default",
+                    "data-page-action" => action.name();;
+                    attributes
+                ))
+                .contents(label);
+        }
+        Layout::Wikijump => {
+            ctx.html()
+                .tag("button")
+                .attr(attr!(
+                    "type" => "button",
+                    "class" => "wj-button",
+                    "data-page-action" => action.name();;
+                    attributes
+                ))
+                .contents(label);
+        }
+    }
+}
@@ -19,6 +19,7 @@
  */
 
 use super::prelude::*;
+use crate::render::html::css::sanitize_inline_style;
 use crate::tree::{Container, ContainerType, HtmlTag};
 
 pub fn render_container(ctx: &mut HtmlContext, container: &Container) {
@@ -45,6 +46,9 @@ pub fn render_container_internal(ctx: &mut HtmlContext, container: &Container) {
     // Get correct ID, based on the render setting
     let random_id = choose_id(ctx, &tag_spec);
 
+    // Get the link for the heading permalink, if one should be added
+    let permalink = heading_permalink(ctx, container, &tag_spec, random_id.as_deref());
+
     // Build the tag
     let mut tag = ctx.html().tag(tag_spec.tag());
 
@@ -69,16 +73,33 @@ pub fn render_container_internal(ctx: &mut HtmlContext, container: &Container) {
     };
 
     // Add container internals
-    tag.contents(container.elements());
+    match permalink {
+        None => tag.contents(container.elements()),
+        Some(href) => tag.inner(|ctx| {
+            render_elements(ctx, container.elements());
+            ctx.html()
+                .a()
+                .attr(attr!(
+                    "class" => "wj-heading-permalink",
+                    "href" => &href,
+                    "aria-hidden" => "true",
+                ))
+                .contents("¶");
+        }),
+    };
 }
 
 pub fn render_color(ctx: &mut HtmlContext, color: &str, elements: &[Element]) {
     debug!("Rendering color container (color '{color}')");
 
+    // The color is user-provided, so it must be sanitized like any other style
+    let style = format!("color: {color};");
+    let style = sanitize_inline_style(&style, &ctx.settings().css);
+
     ctx.html()
         .span()
         .attr(attr!(
-            "style" => "color: " color ";",
+            "style" => &style; if !style.is_empty(),
         ))
         .contents(elements);
 }
@@ -91,3 +112,21 @@ fn choose_id(ctx: &mut HtmlContext, tag_spec: &HtmlTag) -> Option<String> {
         None
     }
 }
+
+fn heading_permalink(
+    ctx: &HtmlContext,
+    container: &Container,
+    tag_spec: &HtmlTag,
+    random_id: Option<&str>,
+) -> Option<String> {
+    if !ctx.settings().heading_permalinks || ctx.layout() != Layout::Wikijump {
+        return None;
+    }
+
+    match (container.ctype(), tag_spec) {
+        (ContainerType::Header(_), HtmlTag::TagAndId { id, .. }) => {
+            Some(format!("#{}", random_id.unwrap_or(id)))
+        }
+        _ => None,
+    }
+}
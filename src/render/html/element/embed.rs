@@ -19,54 +19,47 @@
  */
 
 use super::prelude::*;
+use crate::settings::EmbedKind;
 use crate::tree::Embed;
 
 pub fn render_embed(ctx: &mut HtmlContext, embed: &Embed) {
     debug!(
-        "Rendering embed (variant '{}', url '{}')",
+        "Rendering embed (variant '{}', provider '{}')",
         embed.name(),
-        embed.direct_url(),
+        embed.provider(),
     );
 
+    let provider = match ctx.settings().embed.get(embed.provider()) {
+        Some(provider) => provider.clone(),
+        None => {
+            // This can happen if a syntax tree is rendered
+            // with different settings than it was parsed with.
+            warn!("No embed provider '{}' registered", embed.provider());
+            return;
+        }
+    };
+
+    let arguments = embed.arguments();
+    let url = provider.build_src(arguments.iter().copied());
+
     ctx.html()
         .div()
         .attr(attr!(
             "class" => "wj-embed",
         ))
-        .inner(|ctx| match embed {
-            Embed::Youtube { video_id } => {
-                let url = format!("https://www.youtube.com/embed/{video_id}");
-
-                ctx.html().iframe().attr(attr!(
-                    "src" => &url,
-                    "frameborder" => "0",
-                    "allow" => "accelerometer; autoplay; "
-                               "clipboard-write; encrypted-media; "
-                               "gyroscope; picture-in-picture",
-                    "allowfullscreen",
-                ));
-            }
-
-            Embed::Vimeo { video_id } => {
-                let url = format!("https://player.vimeo.com/video/{video_id}");
-
+        .inner(|ctx| match provider.kind {
+            EmbedKind::Iframe => {
                 ctx.html().iframe().attr(attr!(
                     "src" => &url,
                     "frameborder" => "0",
-                    "allow" => "autoplay; fullscreen; picture-inpicture",
-                    "allowfullscreen",
+                    "allow" => provider.allow.as_deref().unwrap_or_default();
+                        if provider.allow.is_some(),
+                    "sandbox" => provider.sandbox.as_deref().unwrap_or_default();
+                        if provider.sandbox.is_some(),
+                    "allowfullscreen"; if provider.allow_fullscreen,
                 ));
             }
-
-            Embed::GithubGist { username, hash } => {
-                let url = format!("https://gist.github.com/{username}/{hash}.js");
-
-                ctx.html().script().attr(attr!("src" => &url));
-            }
-
-            Embed::GitlabSnippet { snippet_id } => {
-                let url = format!("https://gitlab.com/-/snippets/{snippet_id}.js");
-
+            EmbedKind::Script => {
                 ctx.html().script().attr(attr!("src" => &url));
             }
         });
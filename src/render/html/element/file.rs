@@ -0,0 +1,91 @@
+/*
+ * render/html/element/file.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::tree::FileSource;
+
+pub fn render_file(ctx: &mut HtmlContext, source: &FileSource, label: Option<&str>) {
+    debug!(
+        "Rendering file link (source '{}', label {})",
+        source.name(),
+        label.unwrap_or("<default>"),
+    );
+
+    let label = label.unwrap_or(source.file_name());
+    let url = ctx
+        .handle()
+        .get_file_link(source, ctx.info(), ctx.settings());
+
+    let url = match url {
+        Some(url) => url,
+        None => {
+            trace!("File URL unresolved, missing or error");
+
+            ctx.html()
+                .span()
+                .attr(attr!("class" => "wj-error-inline"))
+                .contents(label);
+
+            return;
+        }
+    };
+
+    // Use size and type hints from the host, if any
+    let info = ctx
+        .handle()
+        .get_file_info(source, ctx.info())
+        .unwrap_or_default();
+
+    let mime_type = info.mime_type.as_deref().unwrap_or("");
+    let size = info.size.map(|size| size.to_string()).unwrap_or_default();
+
+    match ctx.layout() {
+        Layout::Wikidot => {
+            ctx.html()
+                .a()
+                .attr(attr!(
+                    "href" => &url,
+                    "type" => mime_type; if !mime_type.is_empty(),
+                ))
+                .contents(label);
+        }
+        Layout::Wikijump => {
+            ctx.html()
+                .a()
+                .attr(attr!(
+                    "class" => "wj-file",
+                    "href" => &url,
+                    "type" => mime_type; if !mime_type.is_empty(),
+                    "data-file-size" => &size; if !size.is_empty(),
+                ))
+                .inner(|ctx| {
+                    ctx.push_escaped(label);
+
+                    if let Some(size_text) = info.size_text() {
+                        ctx.push_raw(' ');
+                        ctx.html()
+                            .span()
+                            .attr(attr!("class" => "wj-file-size"))
+                            .contents(format!("({size_text})"));
+                    }
+                });
+        }
+    }
+}
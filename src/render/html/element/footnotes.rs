@@ -19,13 +19,31 @@
  */
 
 use super::prelude::*;
+use crate::tree::footnote_citation_letter;
+use std::num::NonZeroUsize;
 
 pub fn render_footnote(ctx: &mut HtmlContext) {
     debug!("Rendering footnote reference");
 
     let index = ctx.next_footnote_index();
+    render_footnote_marker(ctx, index);
+}
+
+pub fn render_footnote_ref(ctx: &mut HtmlContext, index: NonZeroUsize) {
+    debug!("Rendering repeated footnote reference (index {index})");
+
+    render_footnote_marker(ctx, index);
+}
+
+fn render_footnote_marker(ctx: &mut HtmlContext, index: NonZeroUsize) {
     let id = str!(index);
 
+    // If this footnote is cited more than once, then each
+    // citation gets a letter, matching its back-link.
+    let citations = ctx.footnote_citation_total(index);
+    let occurrence = ctx.next_footnote_citation(index);
+    let letter = footnote_citation_letter(occurrence);
+
     // TODO make this into a locale template string
     let footnote_string = ctx.handle().get_message(ctx.language(), "footnote");
     let label = format!("{footnote_string} {index}.");
@@ -46,6 +64,7 @@ pub fn render_footnote(ctx: &mut HtmlContext) {
                     "role" => "link",
                     "aria-label" => &label,
                     "data-id" => &id,
+                    "data-ref" => &letter; if citations > 1,
                 ))
                 .contents(&id);
 
@@ -105,8 +124,9 @@ pub fn render_footnote_block(ctx: &mut HtmlContext, title: Option<&str>) {
             ctx.html().ol().inner(|ctx| {
                 // TODO make this into a footnote helper method
                 for (index, contents) in ctx.footnotes().iter().enumerate() {
-                    let index = index + 1;
-                    let id = &format!("{index}");
+                    let index = NonZeroUsize::new(index + 1).unwrap();
+                    let id = &str!(index);
+                    let citations = ctx.footnote_citation_total(index);
 
                     // Build actual footnote item
                     ctx.html()
@@ -134,6 +154,11 @@ pub fn render_footnote_block(ctx: &mut HtmlContext, title: Option<&str>) {
                                         .contents(".");
                                 });
 
+                            // Back-links to each citation, if there are several
+                            if citations > 1 {
+                                render_footnote_backlinks(ctx, id, citations);
+                            }
+
                             // Footnote contents
                             ctx.html()
                                 .span()
@@ -144,3 +169,24 @@ pub fn render_footnote_block(ctx: &mut HtmlContext, title: Option<&str>) {
             });
         });
 }
+
+fn render_footnote_backlinks(ctx: &mut HtmlContext, id: &str, citations: usize) {
+    ctx.html()
+        .span()
+        .attr(attr!("class" => "wj-footnote-backlinks"))
+        .inner(|ctx| {
+            for occurrence in 0..citations {
+                let letter = footnote_citation_letter(occurrence);
+
+                ctx.html()
+                    .element("wj-footnote-backlink")
+                    .attr(attr!(
+                        "class" => "wj-footnote-backlink",
+                        "role" => "link",
+                        "data-id" => id,
+                        "data-ref" => &letter,
+                    ))
+                    .contents(&letter);
+            }
+        });
+}
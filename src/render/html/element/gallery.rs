@@ -0,0 +1,185 @@
+/*
+ * render/html/element/gallery.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::prelude::*;
+use crate::tree::{
+    AttributeMap, GalleryImage, GalleryOrder, GallerySize, gallery_images,
+};
+use std::borrow::Cow;
+
+pub fn render_gallery(
+    ctx: &mut HtmlContext,
+    images: Option<&[GalleryImage]>,
+    size: GallerySize,
+    order: Option<GalleryOrder>,
+    attributes: &AttributeMap,
+) {
+    debug!(
+        "Rendering gallery (images {}, size {}, order {:?})",
+        match images {
+            Some(images) => images.len().to_string(),
+            None => str!("<attached>"),
+        },
+        size.name(),
+        order,
+    );
+
+    let images = gallery_images(images, order, || {
+        ctx.handle()
+            .get_page_files(&ctx.info().site, &ctx.info().full_name())
+    });
+
+    // Resolve the URL for each image, skipping any which are missing
+    let images = images
+        .iter()
+        .filter_map(|image| {
+            let url =
+                ctx.handle()
+                    .get_file_link(&image.source, ctx.info(), ctx.settings());
+
+            if url.is_none() {
+                warn!(
+                    "Unable to get URL for gallery image '{}'",
+                    image.file_name()
+                );
+            }
+
+            url.map(|url| (image, url))
+        })
+        .collect::<Vec<_>>();
+
+    match ctx.layout() {
+        Layout::Wikidot => render_gallery_wikidot(ctx, &images, size, attributes),
+        Layout::Wikijump => render_gallery_wikijump(ctx, &images, size, attributes),
+    }
+}
+
+/// Render a gallery with a Wikidot-compatible DOM.
+///
+/// Each image is a floating box containing a table, which
+/// centers the thumbnail within it.
+fn render_gallery_wikidot(
+    ctx: &mut HtmlContext,
+    images: &[(&GalleryImage, Cow<str>)],
+    size: GallerySize,
+    attributes: &AttributeMap,
+) {
+    ctx.html()
+        .div()
+        .attr(attr!("class" => "gallery-box";; attributes))
+        .inner(|ctx| {
+            for (image, url) in images {
+                let link_url = image
+                    .link
+                    .as_ref()
+                    .map(|link| ctx.handle().get_link_url(&ctx.info().site, link));
+                let href = link_url.as_deref().unwrap_or(url);
+                let title = image.caption.as_deref().unwrap_or("");
+
+                ctx.html()
+                    .div()
+                    .attr(attr!("class" => "gallery-item " size.name()))
+                    .inner(|ctx| {
+                        ctx.html().table().inner(|ctx| {
+                            ctx.html().tr().inner(|ctx| {
+                                ctx.html().tag("td").inner(|ctx| {
+                                    ctx.html()
+                                        .a()
+                                        .attr(attr!(
+                                            "href" => href,
+                                            "title" => title; if !title.is_empty(),
+                                        ))
+                                        .inner(|ctx| {
+                                            ctx.html().img().attr(attr!(
+                                                "class" => "image",
+                                                "src" => url,
+                                                "alt" => image.file_name(),
+                                                "crossorigin",
+                                            ));
+                                        });
+                                });
+                            });
+                        });
+                    });
+            }
+
+            ctx.html()
+                .div()
+                .attr(attr!("style" => "clear:both; height: 0px; font-size: 1px"));
+        });
+}
+
+/// Render a gallery as a responsive grid of figures.
+///
+/// Images without a link point to the full image, and are marked
+/// with their position in the gallery for use by an image viewer.
+fn render_gallery_wikijump(
+    ctx: &mut HtmlContext,
+    images: &[(&GalleryImage, Cow<str>)],
+    size: GallerySize,
+    attributes: &AttributeMap,
+) {
+    ctx.html()
+        .div()
+        .attr(attr!(
+            "class" => "wj-gallery wj-gallery-" size.name();;
+            attributes
+        ))
+        .inner(|ctx| {
+            for (index, (image, url)) in images.iter().enumerate() {
+                let index = index.to_string();
+                let link_url = image
+                    .link
+                    .as_ref()
+                    .map(|link| ctx.handle().get_link_url(&ctx.info().site, link));
+                let href = link_url.as_deref().unwrap_or(url);
+                let alt = image.caption.as_deref().unwrap_or(image.file_name());
+
+                ctx.html()
+                    .tag("figure")
+                    .attr(attr!("class" => "wj-gallery-item"))
+                    .inner(|ctx| {
+                        ctx.html()
+                            .a()
+                            .attr(attr!(
+                                "class" => "wj-gallery-link",
+                                "href" => href,
+                                "data-gallery-index" => &index; if link_url.is_none(),
+                            ))
+                            .inner(|ctx| {
+                                ctx.html().img().attr(attr!(
+                                    "class" => "wj-gallery-image",
+                                    "src" => url,
+                                    "alt" => alt,
+                                    "loading" => "lazy",
+                                    "crossorigin",
+                                ));
+                            });
+
+                        if let Some(caption) = &image.caption {
+                            ctx.html()
+                                .tag("figcaption")
+                                .attr(attr!("class" => "wj-gallery-caption"))
+                                .contents(caption);
+                        }
+                    });
+            }
+        });
+}
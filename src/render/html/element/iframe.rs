@@ -19,26 +19,123 @@
  */
 
 use super::prelude::*;
+use crate::parsing::{ParseError, ParseErrorKind};
+use crate::settings::IframeSettings;
 use crate::tree::AttributeMap;
+use std::ops::Range;
 
 pub fn render_iframe(ctx: &mut HtmlContext, url: &str, attributes: &AttributeMap) {
     debug!("Rendering iframe block (url '{url}')");
 
+    render_iframe_element(ctx, url, attributes);
+}
+
+pub fn render_html(
+    ctx: &mut HtmlContext,
+    contents: &str,
+    attributes: &AttributeMap,
+    span: &Range<usize>,
+) {
+    match ctx.html_block_store() {
+        Some(store) => {
+            debug!("Rendering html block (submitting to store for iframe)");
+
+            let url = match ctx.store_html_block(store, contents) {
+                Some(url) => url,
+                None => {
+                    render_html_missing(ctx);
+                    return;
+                }
+            };
+
+            // The parser can only check whether iframes are enabled,
+            // since the URL is only known now.
+            let policy = &ctx.settings().iframe;
+            if policy.enabled && !policy.allows(&url) {
+                warn!("HTML block stored at disallowed URL: {url}");
+                ctx.add_warning(ParseError::new_rendered(
+                    ParseErrorKind::IframeDisallowed,
+                    "block-html",
+                    Range::clone(span),
+                ));
+            }
+
+            render_iframe_element(ctx, &url, attributes);
+        }
+        None => {
+            debug!("Rendering html block (inlining as srcdoc)");
+            render_html_srcdoc(ctx, contents, attributes);
+        }
+    }
+}
+
+fn render_html_srcdoc(ctx: &mut HtmlContext, contents: &str, attributes: &AttributeMap) {
+    let policy = IframeSettings::clone(&ctx.settings().iframe);
+    if !policy.enabled {
+        render_iframe_disallowed(ctx, "about:srcdoc");
+        return;
+    }
+
+    // Inline documents share our origin unless sandboxed,
+    // so they must never be given "allow-same-origin".
+    let sandbox = match &policy.sandbox {
+        None => str!("allow-scripts"),
+        Some(tokens) => tokens
+            .split_ascii_whitespace()
+            .filter(|token| !token.eq_ignore_ascii_case("allow-same-origin"))
+            .collect::<Vec<_>>()
+            .join(" "),
+    };
+
     ctx.html().iframe().attr(attr!(
-        "src" => url,
-        "crossorigin";;
+        "srcdoc" => contents,
+        "sandbox" => &sandbox,
+        "referrerpolicy" => policy.referrer_policy.as_deref().unwrap_or_default();
+            if policy.referrer_policy.is_some(),
+        "loading" => "lazy"; if policy.lazy_loading;;
         attributes
     ));
 }
 
-pub fn render_html(ctx: &mut HtmlContext, contents: &str, attributes: &AttributeMap) {
-    debug!("Rendering html block (submitting to remote for iframe)");
+fn render_iframe_element(ctx: &mut HtmlContext, url: &str, attributes: &AttributeMap) {
+    let policy = IframeSettings::clone(&ctx.settings().iframe);
+    if !policy.allows(url) {
+        render_iframe_disallowed(ctx, url);
+        return;
+    }
 
-    // Submit HTML to be hosted on wjfiles, then get back its URL for the iframe.
-    let iframe_url = ctx.handle().post_html(ctx.info(), contents);
     ctx.html().iframe().attr(attr!(
-        "src" => &iframe_url,
-        "crossorigin";;
+        "src" => url,
+        "crossorigin",
+        "sandbox" => policy.sandbox.as_deref().unwrap_or_default();
+            if policy.sandbox.is_some(),
+        "referrerpolicy" => policy.referrer_policy.as_deref().unwrap_or_default();
+            if policy.referrer_policy.is_some(),
+        "loading" => "lazy"; if policy.lazy_loading;;
         attributes
     ));
 }
+
+fn render_iframe_disallowed(ctx: &mut HtmlContext, url: &str) {
+    debug!("Iframe not permitted by policy (url '{url}')");
+
+    let message = ctx
+        .handle()
+        .get_message(ctx.language(), "iframe-disallowed");
+
+    ctx.html()
+        .div()
+        .attr(attr!("class" => "wj-error-block wj-iframe-disallowed"))
+        .contents(message);
+}
+
+fn render_html_missing(ctx: &mut HtmlContext) {
+    trace!("HTML block could not be stored");
+
+    let message = ctx.handle().get_message(ctx.language(), "html-block-error");
+
+    ctx.html()
+        .div()
+        .attr(attr!("class" => "wj-error-block"))
+        .contents(message);
+}
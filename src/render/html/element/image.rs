@@ -20,7 +20,6 @@
 
 use super::prelude::*;
 use crate::tree::{AttributeMap, FileSource, FloatAlignment, LinkLocation};
-use crate::url::normalize_link;
 
 pub fn render_image(
     ctx: &mut HtmlContext,
@@ -103,7 +102,7 @@ fn render_image_element_wikidot(
     let build_link = |ctx: &mut HtmlContext| match link {
         None => build_image(ctx),
         Some(link) => {
-            let url = normalize_link(link, ctx.handle());
+            let url = ctx.handle().get_link_url(&ctx.info().site, link);
             ctx.html()
                 .a()
                 .attr(attr!("href" => &url))
@@ -151,7 +150,7 @@ fn render_image_element_wikijump(
 
             match link {
                 Some(link) => {
-                    let url = normalize_link(link, ctx.handle());
+                    let url = ctx.handle().get_link_url(&ctx.info().site, link);
                     ctx.html()
                         .a()
                         .attr(attr!("href" => &url))
@@ -22,7 +22,6 @@ use super::prelude::*;
 use crate::tree::{
     AnchorTarget, AttributeMap, Element, LinkLabel, LinkLocation, LinkType,
 };
-use crate::url::normalize_link;
 
 pub fn render_anchor(
     ctx: &mut HtmlContext,
@@ -79,7 +78,7 @@ pub fn render_link(
     ctx.add_link(link);
 
     let site = ctx.info().site.as_ref().to_string();
-    let url = normalize_link(link, ctx.handle());
+    let url = handle.get_link_url(&site, link);
 
     let target_value = match target {
         Some(target) => target.html_attr(),
@@ -22,13 +22,16 @@
 
 mod audio;
 mod bibliography;
+mod button;
 mod clear_float;
 mod collapsible;
 mod container;
 mod date;
 mod definition_list;
 mod embed;
+mod file;
 mod footnotes;
+mod gallery;
 mod iframe;
 mod image;
 mod include;
@@ -55,13 +58,16 @@ mod prelude {
 
 use self::audio::render_audio;
 use self::bibliography::{render_bibcite, render_bibliography};
+use self::button::render_button;
 use self::clear_float::render_clear_float;
 use self::collapsible::{Collapsible, render_collapsible};
 use self::container::{render_color, render_container};
 use self::date::render_date;
 use self::definition_list::render_definition_list;
 use self::embed::render_embed;
-use self::footnotes::{render_footnote, render_footnote_block};
+use self::file::render_file;
+use self::footnotes::{render_footnote, render_footnote_block, render_footnote_ref};
+use self::gallery::render_gallery;
 use self::iframe::{render_html, render_iframe};
 use self::image::render_image;
 use self::include::{render_include, render_variable};
@@ -77,7 +83,7 @@ use self::toc::render_table_of_contents;
 use self::user::render_user;
 use self::video::render_video;
 use super::HtmlContext;
-use crate::tree::{CodeBlock, Element};
+use crate::tree::Element;
 use ref_map::*;
 
 pub fn render_elements(ctx: &mut HtmlContext, elements: &[Element]) {
@@ -134,6 +140,18 @@ pub fn render_element(ctx: &mut HtmlContext, element: &Element) {
             alignment,
             attributes,
         } => render_video(ctx, source, *alignment, attributes),
+        Element::Gallery {
+            images,
+            size,
+            order,
+            attributes,
+        } => render_gallery(ctx, images.as_deref(), *size, *order, attributes),
+        Element::File { source, label } => render_file(ctx, source, ref_cow!(label)),
+        Element::Button {
+            action,
+            label,
+            attributes,
+        } => render_button(ctx, *action, ref_cow!(label), attributes),
         Element::List {
             ltype,
             items,
@@ -173,6 +191,7 @@ pub fn render_element(ctx: &mut HtmlContext, element: &Element) {
             render_table_of_contents(ctx, *align, attributes)
         }
         Element::Footnote => render_footnote(ctx),
+        Element::FootnoteRef { index, .. } => render_footnote_ref(ctx, *index),
         Element::FootnoteBlock { title, hide } => {
             if !(*hide || ctx.footnotes().is_empty()) {
                 render_footnote_block(ctx, ref_cow!(title));
@@ -195,11 +214,7 @@ pub fn render_element(ctx: &mut HtmlContext, element: &Element) {
             hover,
         } => render_date(ctx, *value, ref_cow!(format), *hover),
         Element::Color { color, elements } => render_color(ctx, color, elements),
-        Element::Code(CodeBlock {
-            contents,
-            language,
-            name: _,
-        }) => render_code(ctx, ref_cow!(language), contents),
+        Element::Code(code_block) => render_code(ctx, code_block),
         Element::Math { name, latex_source } => {
             render_math_block(ctx, ref_cow!(name), latex_source)
         }
@@ -209,7 +224,8 @@ pub fn render_element(ctx: &mut HtmlContext, element: &Element) {
         Element::Html {
             contents,
             attributes,
-        } => render_html(ctx, contents, attributes),
+            span,
+        } => render_html(ctx, contents, attributes, span),
         Element::Iframe { url, attributes } => render_iframe(ctx, url, attributes),
         Element::Include {
             variables,
@@ -19,16 +19,18 @@
  */
 
 use super::prelude::*;
+use crate::render::html::css::apply_css_policy;
 use lightningcss::stylesheet::{ParserOptions, PrinterOptions, StyleSheet};
 
 /// Prevent CSS from terminating the HTML `<style>` raw-text element.
 ///
 /// This must run after CSS serialization: HTML parsing happens before the
 /// browser parses the CSS, so a CSS escape is needed rather than HTML
-/// escaping. The trailing space terminates the hexadecimal CSS escape.
+/// escaping. The trailing space terminates the hexadecimal CSS escape
+/// explicitly, so the escape never depends on the character after it.
 fn escape_style_end_tags(css: &mut String) {
     const HTML_END_TAG_START: &str = "</";
-    const CSS_ESCAPED_END_TAG_START: &str = r"\3c/";
+    const CSS_ESCAPED_END_TAG_START: &str = r"\3c /";
 
     let mut offset = 0;
     while let Some(relative_start) = css[offset..].find(HTML_END_TAG_START) {
@@ -43,6 +45,7 @@ fn escape_style_end_tags(css: &mut String) {
 
 pub fn render_style(ctx: &mut HtmlContext, input_css: &str) {
     let minify = ctx.settings().minify_css;
+    let policy = ctx.settings().css.clone();
 
     let parser_options = ParserOptions {
         error_recovery: true,
@@ -55,9 +58,16 @@ pub fn render_style(ctx: &mut HtmlContext, input_css: &str) {
     };
 
     debug!("Parsing input CSS ({} bytes)", input_css.len());
-    let stylesheet = StyleSheet::parse(input_css, parser_options)
+    let mut stylesheet = StyleSheet::parse(input_css, parser_options)
         .expect("Produced error with recovery enabled");
 
+    if !policy.is_permissive() {
+        trace!("Applying CSS policy to stylesheet");
+        if !apply_css_policy(&mut stylesheet.rules, &policy) {
+            return;
+        }
+    }
+
     trace!("Rendering CSS into HTML (minify: {minify})");
     let output_css = match stylesheet.to_css(print_options) {
         Ok(output) => output.code,
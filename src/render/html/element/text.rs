@@ -19,6 +19,8 @@
  */
 
 use super::prelude::*;
+use crate::render::html::HtmlCodeBlock;
+use crate::tree::CodeBlock;
 
 pub fn render_wikitext_raw(ctx: &mut HtmlContext, text: &str) {
     debug!("Escaping raw string '{text}'");
@@ -69,13 +71,30 @@ pub fn render_email(ctx: &mut HtmlContext, email: &str) {
     }
 }
 
-pub fn render_code(ctx: &mut HtmlContext, language: Option<&str>, contents: &str) {
+pub fn render_code(ctx: &mut HtmlContext, code_block: &CodeBlock) {
+    let CodeBlock {
+        contents,
+        language,
+        name,
+    } = code_block;
+
+    let language = language.as_deref();
     debug!(
         "Rendering code block (language {})",
         language.unwrap_or("<none>"),
     );
+
+    // Register the code block so the host can serve it as a file
     let index = ctx.next_code_snippet_index();
-    ctx.handle().post_code(index, contents);
+    let url = ctx.handle().get_code_url(ctx.info(), index);
+    ctx.add_code_block(HtmlCodeBlock {
+        index,
+        name: name.as_ref().map(|name| str!(name)),
+        language: language.map(|language| str!(language)),
+        mime_type: str!(code_block.mime_type()),
+        url: url.clone(),
+        contents: str!(contents),
+    });
 
     let class = {
         let mut class = format!("wj-code wj-language-{}", language.unwrap_or("none"));
@@ -112,6 +131,23 @@ pub fn render_code(ctx: &mut HtmlContext, language: Option<&str>, contents: &str
                             ctx.html().sprite("wj-clipboard-success");
                         });
 
+                    // Download link, for saving the raw code block
+                    let download_title = ctx
+                        .handle()
+                        .get_message(ctx.language(), "button-download-code");
+
+                    ctx.html()
+                        .a()
+                        .attr(attr!(
+                            "class" => "wj-code-download",
+                            "href" => &url,
+                            "title" => download_title,
+                            "download" => name.as_deref().unwrap_or_default(),
+                        ))
+                        .inner(|ctx| {
+                            ctx.html().sprite("wj-download");
+                        });
+
                     // Span showing name of language
                     ctx.html()
                         .span()
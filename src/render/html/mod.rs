@@ -22,34 +22,69 @@
 mod attributes;
 mod builder;
 mod context;
+pub(crate) mod css;
+mod document;
 mod element;
 mod escape;
 mod meta;
 mod output;
 mod random;
 mod render;
+mod store;
 
+pub use self::document::{BASELINE_STYLESHEET, HtmlDocumentRender, INTERACTIVE_SCRIPT};
 pub use self::meta::{HtmlMeta, HtmlMetaType};
-pub use self::output::HtmlOutput;
+pub use self::output::{HtmlBlock, HtmlCodeBlock, HtmlOutlineEntry, HtmlOutput};
+pub use self::store::{FilesystemStore, HtmlBlockKey, HtmlBlockStore};
 
 use self::context::HtmlContext;
 use self::element::{render_element, render_elements};
 use crate::data::PageInfo;
-use crate::render::{Handle, Render};
+use crate::render::{Handle, PageResolver, Render};
 use crate::settings::WikitextSettings;
 use crate::tree::{Element, SyntaxTree};
 
 #[derive(Debug)]
 pub struct HtmlRender;
 
-impl Render for HtmlRender {
-    type Output = HtmlOutput;
+impl HtmlRender {
+    /// Render an abstract syntax tree, storing `[[html]]` blocks in the given store.
+    ///
+    /// Instead of being inlined using `srcdoc`, the iframe for each block points
+    /// to the URL returned by the store. See [`HtmlBlockStore`] for more information.
+    pub fn render_with_store(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        store: &dyn HtmlBlockStore,
+    ) -> HtmlOutput {
+        self.render_internal(tree, page_info, settings, None, Some(store))
+    }
 
-    fn render(
+    /// Render an abstract syntax tree, using the given resolver for information about other pages.
+    ///
+    /// This determines whether linked pages exist, their titles, and the URLs links point to.
+    /// See [`PageResolver`] for more information. If a store is passed, `[[html]]` blocks are
+    /// stored as in [`render_with_store()`](Self::render_with_store).
+    pub fn render_with_resolver(
         &self,
         tree: &SyntaxTree,
         page_info: &PageInfo,
         settings: &WikitextSettings,
+        resolver: &dyn PageResolver,
+        store: Option<&dyn HtmlBlockStore>,
+    ) -> HtmlOutput {
+        self.render_internal(tree, page_info, settings, Some(resolver), store)
+    }
+
+    fn render_internal(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+        resolver: Option<&dyn PageResolver>,
+        html_block_store: Option<&dyn HtmlBlockStore>,
     ) -> HtmlOutput {
         info!(
             "Rendering HTML (site {}, page {}, category {})",
@@ -61,15 +96,9 @@ impl Render for HtmlRender {
             },
         );
 
-        let mut ctx = HtmlContext::new(
-            page_info,
-            &Handle,
-            settings,
-            &tree.table_of_contents,
-            &tree.footnotes,
-            &tree.bibliographies,
-            tree.wikitext_len,
-        );
+        let handle = Handle::new(resolver);
+        let mut ctx =
+            HtmlContext::new(page_info, &handle, html_block_store, settings, tree);
 
         // Crawl through elements and generate HTML
         render_contents(&mut ctx, tree);
@@ -79,6 +108,20 @@ impl Render for HtmlRender {
     }
 }
 
+impl Render for HtmlRender {
+    type Output = HtmlOutput;
+
+    #[inline]
+    fn render(
+        &self,
+        tree: &SyntaxTree,
+        page_info: &PageInfo,
+        settings: &WikitextSettings,
+    ) -> HtmlOutput {
+        self.render_internal(tree, page_info, settings, None, None)
+    }
+}
+
 fn render_contents(ctx: &mut HtmlContext, tree: &SyntaxTree) {
     render_elements(ctx, &tree.elements);
 
@@ -20,10 +20,98 @@
 
 use super::meta::HtmlMeta;
 use crate::data::Backlinks;
+use crate::parsing::ParseError;
+use crate::tree::HeadingLevel;
+use std::num::NonZeroUsize;
+use std::ops::Range;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct HtmlOutput {
     pub body: String,
     pub meta: Vec<HtmlMeta>,
     pub backlinks: Backlinks<'static>,
+    pub html_blocks: Vec<HtmlBlock>,
+    pub code_blocks: Vec<HtmlCodeBlock>,
+    pub outline: Vec<HtmlOutlineEntry>,
+
+    /// Warnings which could only be found while rendering.
+    ///
+    /// Currently this is `[[html]]` blocks whose stored URL is
+    /// not permitted by the iframe policy.
+    pub warnings: Vec<ParseError>,
+}
+
+/// An `[[html]]` block from the syntax tree, as rendered.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct HtmlBlock {
+    /// The raw HTML contents of the block.
+    pub contents: String,
+
+    /// The content hash of this block, see [`HtmlBlockKey`](super::HtmlBlockKey).
+    ///
+    /// This is only computed when rendering with an [`HtmlBlockStore`](super::HtmlBlockStore).
+    pub hash: Option<String>,
+
+    /// The URL assigned to this block by the [`HtmlBlockStore`](super::HtmlBlockStore).
+    ///
+    /// This is `None` if the block was inlined, or was not rendered.
+    pub url: Option<String>,
+}
+
+/// A `[[code]]` block, as rendered, which can be served as a file.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct HtmlCodeBlock {
+    /// The index of this code block, starting at 1.
+    pub index: NonZeroUsize,
+
+    /// The normalized name of this code block, if given.
+    ///
+    /// Names are not guaranteed to be unique within a page.
+    pub name: Option<String>,
+
+    /// The language of this code block, if given.
+    pub language: Option<String>,
+
+    /// The suggested MIME type when serving this code block.
+    pub mime_type: String,
+
+    /// The URL this code block is served at.
+    pub url: String,
+
+    /// The raw contents of this code block.
+    pub contents: String,
+}
+
+/// A heading in the document outline.
+///
+/// Headings are nested under the closest preceding heading
+/// of a higher level, as in the table of contents.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct HtmlOutlineEntry {
+    /// The level of this heading.
+    pub level: HeadingLevel,
+
+    /// The heading's name, as plain text.
+    pub name: String,
+
+    /// The anchor ID given to the rendered heading, if any.
+    ///
+    /// This is `None` if the heading has no table of contents entry,
+    /// or if IDs are disabled in the settings.
+    pub id: Option<String>,
+
+    /// Whether this heading has a table of contents entry.
+    pub has_toc: bool,
+
+    /// The byte range of this heading's section in the parsed text.
+    ///
+    /// As with [`HeadingSection::span`](crate::tree::HeadingSection::span),
+    /// this is the wikitext after includes and preprocessing.
+    pub span: Range<usize>,
+
+    /// The headings within this heading's section.
+    pub children: Vec<HtmlOutlineEntry>,
 }
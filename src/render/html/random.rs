@@ -18,13 +18,33 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+use crate::data::PageInfo;
+use crate::settings::HtmlIdStrategy;
 use cfg_if::cfg_if;
+use rand::SeedableRng;
 use rand::distr::{Alphanumeric, SampleString};
 use rand::rngs::SmallRng;
+use std::collections::HashSet;
+
+const HTML_ID_PREFIX: &str = "wj-id-";
+const HTML_ID_RANDOM_LENGTH: usize = 16;
 
 #[derive(Debug)]
 pub struct Random {
-    rng: SmallRng,
+    source: IdSource,
+}
+
+#[derive(Debug)]
+enum IdSource {
+    /// Random alphanumeric IDs, with a record of those already
+    /// emitted so that no ID is repeated within the document.
+    Rng {
+        rng: SmallRng,
+        issued: HashSet<String>,
+    },
+
+    /// Sequential IDs, with an optional prefix before the index.
+    Counter { prefix: Option<String>, next: usize },
 }
 
 impl Default for Random {
@@ -32,21 +52,82 @@ impl Default for Random {
     fn default() -> Self {
         cfg_if! {
             if #[cfg(test)] {
-                use rand::SeedableRng;
                 let rng = SmallRng::seed_from_u64(1);
             } else {
                 let rng = rand::make_rng();
             }
         }
 
-        Random { rng }
+        Random::from_rng(rng)
     }
 }
 
 impl Random {
+    pub fn new(strategy: &HtmlIdStrategy, info: &PageInfo) -> Self {
+        match strategy {
+            HtmlIdStrategy::Random => Random::default(),
+            HtmlIdStrategy::Seeded { revision } => {
+                let seed = page_seed(info, *revision);
+                Random::from_rng(SmallRng::seed_from_u64(seed))
+            }
+            HtmlIdStrategy::Counter => Random {
+                source: IdSource::Counter {
+                    prefix: None,
+                    next: 0,
+                },
+            },
+            HtmlIdStrategy::Prefixed { prefix } => Random {
+                source: IdSource::Counter {
+                    prefix: Some(sanitize_prefix(prefix)),
+                    next: 0,
+                },
+            },
+        }
+    }
+
+    fn from_rng(rng: SmallRng) -> Self {
+        Random {
+            source: IdSource::Rng {
+                rng,
+                issued: HashSet::new(),
+            },
+        }
+    }
+
     pub fn generate_html_id_into(&mut self, buffer: &mut String) {
-        buffer.push_str("wj-id-");
-        Alphanumeric.append_string(&mut self.rng, buffer, 16);
+        buffer.push_str(HTML_ID_PREFIX);
+
+        match self.source {
+            IdSource::Rng {
+                ref mut rng,
+                ref mut issued,
+            } => {
+                // Regenerate in the (very unlikely) case of a collision,
+                // so that IDs are guaranteed to be unique within the document.
+                let id = loop {
+                    let id = Alphanumeric.sample_string(rng, HTML_ID_RANDOM_LENGTH);
+                    if !issued.contains(&id) {
+                        break id;
+                    }
+
+                    warn!("Generated duplicate HTML ID '{id}', regenerating");
+                };
+
+                buffer.push_str(&id);
+                issued.insert(id);
+            }
+            IdSource::Counter {
+                ref prefix,
+                ref mut next,
+            } => {
+                if let Some(prefix) = prefix {
+                    str_write!(buffer, "{prefix}-");
+                }
+
+                str_write!(buffer, "{next}");
+                *next += 1;
+            }
+        }
     }
 
     pub fn generate_html_id(&mut self) -> String {
@@ -56,6 +137,48 @@ impl Random {
     }
 }
 
+/// Derives a stable seed from the page's identity and revision.
+///
+/// This uses 64-bit FNV-1a rather than the standard library's hasher,
+/// since the latter's output is not guaranteed to be stable between releases.
+fn page_seed(info: &PageInfo, revision: u64) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+
+    let category = info.category.as_deref().unwrap_or("_default");
+    let fields: [&[u8]; 4] = [
+        info.site.as_bytes(),
+        category.as_bytes(),
+        info.page.as_bytes(),
+        &revision.to_le_bytes(),
+    ];
+
+    let mut hash = FNV_OFFSET_BASIS;
+    for field in fields {
+        // Separate each field with a null byte,
+        // to avoid collisions from shifting boundaries.
+        for &byte in field.iter().chain(&[0]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    hash
+}
+
+fn sanitize_prefix(prefix: &str) -> String {
+    prefix
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
 #[test]
 fn html_id() {
     // Random output is deterministic in tests.
@@ -78,3 +201,63 @@ fn html_id() {
         "Generated HTML ID doesn't match expected",
     );
 }
+
+#[test]
+fn html_id_strategies() {
+    let page_info = PageInfo::dummy();
+
+    // Counter
+    let mut rand = Random::new(&HtmlIdStrategy::Counter, &page_info);
+    assert_eq!(rand.generate_html_id(), "wj-id-0");
+    assert_eq!(rand.generate_html_id(), "wj-id-1");
+    assert_eq!(rand.generate_html_id(), "wj-id-2");
+
+    // Prefixed
+    let strategy = HtmlIdStrategy::Prefixed {
+        prefix: cow!("post-12"),
+    };
+    let mut rand = Random::new(&strategy, &page_info);
+    assert_eq!(rand.generate_html_id(), "wj-id-post-12-0");
+    assert_eq!(rand.generate_html_id(), "wj-id-post-12-1");
+
+    let strategy = HtmlIdStrategy::Prefixed {
+        prefix: cow!("a b\"c"),
+    };
+    let mut rand = Random::new(&strategy, &page_info);
+    assert_eq!(rand.generate_html_id(), "wj-id-a-b-c-0");
+
+    // Seeded
+    let generate = |info: &PageInfo, revision| {
+        let strategy = HtmlIdStrategy::Seeded { revision };
+        let mut rand = Random::new(&strategy, info);
+        (0..4).map(|_| rand.generate_html_id()).collect::<Vec<_>>()
+    };
+
+    let ids = generate(&page_info, 4);
+    assert_eq!(
+        ids,
+        generate(&page_info, 4),
+        "Seeded IDs are not deterministic"
+    );
+    assert_ne!(
+        ids,
+        generate(&page_info, 5),
+        "Seeded IDs ignore the revision"
+    );
+
+    let mut other_page_info = PageInfo::dummy();
+    other_page_info.page = cow!("other-page");
+    assert_ne!(
+        ids,
+        generate(&other_page_info, 4),
+        "Seeded IDs ignore the page identity",
+    );
+
+    for id in &ids {
+        assert!(id.starts_with("wj-id-"), "Seeded ID has the wrong prefix");
+        assert_eq!(id.len(), 22, "Seeded ID has the wrong length");
+    }
+
+    let unique = ids.iter().collect::<HashSet<_>>();
+    assert_eq!(unique.len(), ids.len(), "Seeded IDs collided");
+}
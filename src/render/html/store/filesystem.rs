@@ -0,0 +1,109 @@
+/*
+ * render/html/store/filesystem.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{HtmlBlockKey, HtmlBlockStore};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// An [`HtmlBlockStore`] which writes each block to a directory.
+///
+/// Blocks are saved as `{hash}.html`, and the URL returned is the
+/// given base URL followed by that filename. This means the directory
+/// can be served as-is by any static file server.
+#[derive(Debug, Clone)]
+pub struct FilesystemStore {
+    directory: PathBuf,
+    base_url: String,
+}
+
+impl FilesystemStore {
+    pub fn new(directory: PathBuf, base_url: String) -> Self {
+        FilesystemStore {
+            directory,
+            base_url,
+        }
+    }
+
+    #[inline]
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns the path the block with the given key is stored at.
+    pub fn path(&self, key: &HtmlBlockKey) -> PathBuf {
+        self.directory.join(format!("{}.html", key.hash))
+    }
+}
+
+impl HtmlBlockStore for FilesystemStore {
+    fn store(&self, key: &HtmlBlockKey, html: &str) -> io::Result<String> {
+        let path = self.path(key);
+
+        // Since paths are content-addressed, an existing file is already correct.
+        if !path.exists() {
+            debug!("Writing HTML block to {}", path.display());
+            fs::create_dir_all(&self.directory)?;
+
+            // Write to a temporary file first so a partial block is never served.
+            let temp_path = self.directory.join(format!(".{}.tmp", key.hash));
+            fs::write(&temp_path, html)?;
+            fs::rename(&temp_path, &path)?;
+        }
+
+        Ok(format!(
+            "{}/{}.html",
+            self.base_url.trim_end_matches('/'),
+            key.hash,
+        ))
+    }
+}
+
+#[test]
+fn filesystem_store() {
+    use crate::data::PageInfo;
+    use crate::test::temp_dir::TempDir;
+
+    let directory = TempDir::new("html-blocks");
+    let store = FilesystemStore::new(
+        directory.path().to_path_buf(),
+        str!("https://files.example.com/html/"),
+    );
+
+    let info = PageInfo::dummy();
+    let key = HtmlBlockKey::new(&info, "<p>Stored</p>");
+    let url = store
+        .store(&key, "<p>Stored</p>")
+        .expect("Unable to store block");
+    assert_eq!(
+        url,
+        format!("https://files.example.com/html/{}.html", key.hash)
+    );
+    assert_eq!(
+        fs::read_to_string(store.path(&key)).expect("Unable to read block"),
+        "<p>Stored</p>",
+    );
+
+    // Storing again returns the same URL
+    let url_2 = store
+        .store(&key, "<p>Stored</p>")
+        .expect("Unable to store block");
+    assert_eq!(url, url_2);
+}
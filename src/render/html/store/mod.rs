@@ -0,0 +1,117 @@
+/*
+ * render/html/store/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Content-addressed storage for the contents of `[[html]]` blocks.
+//!
+//! By default, `[[html]]` blocks are inlined into the page using a sandboxed
+//! `<iframe srcdoc>`. Hosts which would rather serve these blocks as separate
+//! documents can provide an [`HtmlBlockStore`] to
+//! [`HtmlRender::render_with_store`](super::HtmlRender::render_with_store),
+//! which is given each block along with a key derived from its contents.
+
+mod filesystem;
+
+pub use self::filesystem::FilesystemStore;
+
+use crate::data::PageInfo;
+use sha2::{Digest, Sha256};
+use std::fmt::Debug;
+use std::io;
+
+/// The identity of an `[[html]]` block, as used by an [`HtmlBlockStore`].
+///
+/// The hash covers both the HTML and the page it appears on, so
+/// identical blocks on the same page are stored only once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlBlockKey<'a> {
+    pub site: &'a str,
+    pub category: Option<&'a str>,
+    pub page: &'a str,
+
+    /// The SHA-256 digest of the page identity and HTML, in lowercase hexadecimal.
+    pub hash: String,
+}
+
+impl<'a> HtmlBlockKey<'a> {
+    pub fn new(info: &'a PageInfo, html: &str) -> Self {
+        let site = info.site.as_ref();
+        let category = info.category.as_deref();
+        let page = info.page.as_ref();
+
+        // Each field is length-prefixed so their boundaries are unambiguous.
+        let mut hasher = Sha256::new();
+        for field in [site, category.unwrap_or("_default"), page, html] {
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field.as_bytes());
+        }
+
+        let mut hash = String::with_capacity(64);
+        for byte in hasher.finalize() {
+            str_write!(hash, "{byte:02x}");
+        }
+
+        HtmlBlockKey {
+            site,
+            category,
+            page,
+            hash,
+        }
+    }
+}
+
+/// A trait for hosts which store `[[html]]` blocks to be served separately.
+///
+/// The returned URL is used as the `src` of the block's iframe, and is
+/// subject to the iframe policy in [`WikitextSettings`]. These documents
+/// should be served from a separate origin to the wiki itself, since they
+/// contain arbitrary user-provided HTML and scripts.
+///
+/// [`WikitextSettings`]: crate::settings::WikitextSettings
+pub trait HtmlBlockStore: Debug {
+    /// Stores the given HTML under this key, returning the URL it is served from.
+    ///
+    /// This may be called more than once with the same key,
+    /// in which case the same URL should be returned.
+    fn store(&self, key: &HtmlBlockKey, html: &str) -> io::Result<String>;
+}
+
+#[test]
+fn html_block_key() {
+    let info = PageInfo::dummy();
+    let key_1 = HtmlBlockKey::new(&info, "<b>Hello</b>");
+    let key_2 = HtmlBlockKey::new(&info, "<b>Hello</b>");
+    let key_3 = HtmlBlockKey::new(&info, "<b>Goodbye</b>");
+
+    assert_eq!(key_1, key_2, "Identical blocks have different keys");
+    assert_ne!(key_1.hash, key_3.hash, "Different blocks have the same key");
+    assert_eq!(
+        key_1.hash.len(),
+        64,
+        "Hash isn't a hex-encoded SHA-256 digest"
+    );
+
+    let mut other_info = PageInfo::dummy();
+    other_info.page = cow!("other-page");
+    let key_4 = HtmlBlockKey::new(&other_info, "<b>Hello</b>");
+    assert_ne!(
+        key_1.hash, key_4.hash,
+        "Blocks on different pages have the same key"
+    );
+}
@@ -35,6 +35,9 @@ pub mod text;
 pub mod html;
 
 mod handle;
+mod resolver;
+
+pub use self::resolver::{FileInfo, PageResolver};
 
 use self::handle::Handle;
 use crate::data::PageInfo;
@@ -54,9 +54,9 @@ fn null() {
         vec![],
         vec![],
         (vec![], vec![]),
-        vec![],
-        (vec![], true),
-        BibliographyList::new(),
+        (vec![], vec![]),
+        (vec![], vec![], true),
+        (BibliographyList::new(), None),
         0,
     );
     let (tree, _) = result.into();
@@ -0,0 +1,124 @@
+/*
+ * render/resolver.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::fmt::Debug;
+
+/// Provides information about the pages and files on a site while rendering.
+///
+/// This is how links find out whether their target exists (to style them as
+/// "red links" if not), which title to show for `[[[page|]]]` links, and
+/// where pages and attached files are located.
+///
+/// Page names are normalized and include the category if present,
+/// such as `scp-001` or `component:thing`.
+pub trait PageResolver: Debug {
+    /// Returns whether the given page exists.
+    fn page_exists(&self, site: &str, page: &str) -> bool;
+
+    /// Returns the title of the given page, or `None` if it has none.
+    fn page_title(&self, site: &str, page: &str) -> Option<String>;
+
+    /// Returns the URL to link to for the given page.
+    ///
+    /// The `extra` argument is any trailing anchor or path, such as `#toc0`.
+    /// If `None` is returned, the default URL is used.
+    fn page_url(&self, _site: &str, _page: &str, _extra: Option<&str>) -> Option<String> {
+        None
+    }
+
+    /// Returns the URL of the given file attached to a page.
+    ///
+    /// If `None` is returned, the default URL is used.
+    fn file_url(&self, _site: &str, _page: &str, _file: &str) -> Option<String> {
+        None
+    }
+
+    /// Returns information about the given file attached to a page, if known.
+    ///
+    /// This is used as a hint when linking to the file with `[[file]]`.
+    fn file_info(&self, _site: &str, _page: &str, _file: &str) -> Option<FileInfo> {
+        None
+    }
+
+    /// Returns the names of all the files attached to the given page.
+    ///
+    /// Files should be listed in the order they were uploaded. This is used
+    /// by `[[gallery]]` blocks without an explicit list of images.
+    fn page_files(&self, _site: &str, _page: &str) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Information about a file attached to a page.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct FileInfo {
+    /// The size of the file, in bytes.
+    pub size: Option<u64>,
+
+    /// The MIME type of the file, such as `application/pdf`.
+    pub mime_type: Option<String>,
+}
+
+impl FileInfo {
+    /// Formats the size of this file for display, such as `1.5 MB`.
+    pub fn size_text(&self) -> Option<String> {
+        const UNITS: [&str; 4] = ["kB", "MB", "GB", "TB"];
+
+        let size = self.size?;
+        if size < 1024 {
+            return Some(format!("{size} bytes"));
+        }
+
+        let mut value = size as f64 / 1024.0;
+        let mut unit = UNITS[0];
+        for next_unit in &UNITS[1..] {
+            if value < 1024.0 {
+                break;
+            }
+
+            value /= 1024.0;
+            unit = next_unit;
+        }
+
+        Some(format!("{value:.1} {unit}"))
+    }
+}
+
+#[test]
+fn file_size_text() {
+    macro_rules! check {
+        ($size:expr, $expected:expr $(,)?) => {{
+            let info = FileInfo {
+                size: $size,
+                mime_type: None,
+            };
+
+            assert_eq!(info.size_text().as_deref(), $expected);
+        }};
+    }
+
+    check!(None, None);
+    check!(Some(0), Some("0 bytes"));
+    check!(Some(1023), Some("1023 bytes"));
+    check!(Some(1536), Some("1.5 kB"));
+    check!(Some(5 * 1024 * 1024), Some("5.0 MB"));
+    check!(Some(3 * 1024 * 1024 * 1024), Some("3.0 GB"));
+}
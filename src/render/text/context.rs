@@ -22,7 +22,10 @@ use crate::data::PageInfo;
 use crate::non_empty_vec::NonEmptyVec;
 use crate::render::Handle;
 use crate::settings::WikitextSettings;
-use crate::tree::{Bibliography, BibliographyList, Element, VariableScopes};
+use crate::tree::{
+    Bibliography, BibliographyContents, BibliographyList, CitationStyle, Element,
+    FootnoteCitations, VariableScopes,
+};
 use std::fmt::{self, Write};
 use std::num::NonZeroUsize;
 
@@ -33,7 +36,7 @@ where
 {
     output: String,
     info: &'i PageInfo<'i>,
-    handle: &'h Handle,
+    handle: &'h Handle<'h>,
     settings: &'e WikitextSettings,
 
     //
@@ -67,6 +70,9 @@ where
 
     /// The current footnote index, for rendering.
     footnote_index: NonZeroUsize,
+
+    /// How many times each footnote is cited, for rendering.
+    footnote_citations: FootnoteCitations,
 }
 
 impl<'i, 'h, 'e, 't> TextContext<'i, 'h, 'e, 't>
@@ -76,10 +82,10 @@ where
     #[inline]
     pub fn new(
         info: &'i PageInfo<'i>,
-        handle: &'h Handle,
+        handle: &'h Handle<'h>,
         settings: &'e WikitextSettings,
         table_of_contents: &'e [Element<'t>],
-        footnotes: &'e [Vec<Element<'t>>],
+        (footnotes, footnote_refs): (&'e [Vec<Element<'t>>], &[NonZeroUsize]),
         bibliographies: &'e BibliographyList<'t>,
         wikitext_len: usize,
     ) -> Self {
@@ -88,7 +94,7 @@ where
             info,
             handle,
             settings,
-            variables: VariableScopes::new(),
+            variables: VariableScopes::for_page(info, settings),
             table_of_contents,
             footnotes,
             bibliographies,
@@ -97,6 +103,7 @@ where
             invisible: 0,
             equation_index: NonZeroUsize::new(1).unwrap(),
             footnote_index: NonZeroUsize::new(1).unwrap(),
+            footnote_citations: FootnoteCitations::new(footnotes.len(), footnote_refs),
         }
     }
 
@@ -122,7 +129,7 @@ where
     }
 
     #[inline]
-    pub fn handle(&self) -> &'h Handle {
+    pub fn handle(&self) -> &'h Handle<'h> {
         self.handle
     }
 
@@ -154,7 +161,7 @@ where
     pub fn get_bibliography_ref(
         &self,
         label: &str,
-    ) -> Option<(usize, &'e [Element<'t>])> {
+    ) -> Option<(CitationStyle, usize, &'e BibliographyContents<'t>)> {
         self.bibliographies.get_reference(label)
     }
 
@@ -170,6 +177,23 @@ where
         index
     }
 
+    #[inline]
+    pub fn footnote_citation_total(&self, index: NonZeroUsize) -> usize {
+        self.footnote_citations.total(index)
+    }
+
+    #[inline]
+    pub fn next_footnote_citation(&mut self, index: NonZeroUsize) -> usize {
+        self.footnote_citations.next(index)
+    }
+
+    #[inline]
+    pub fn get_footnote(&self, index_one: NonZeroUsize) -> Option<&'e [Element<'t>]> {
+        self.footnotes
+            .get(usize::from(index_one) - 1)
+            .map(|elements| elements.as_slice())
+    }
+
     // Prefixes
     #[inline]
     pub fn push_prefix(&mut self, prefix: &'static str) {
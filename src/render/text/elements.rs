@@ -28,7 +28,11 @@
 //! Any formatting present must be directly justifiable.
 
 use super::TextContext;
-use crate::tree::{CodeBlock, ContainerType, DefinitionListItem, Element, ListItem, Tab};
+use crate::tree::{
+    BibliographyContents, CitationStyle, CodeBlock, ContainerType, DefinitionListItem,
+    Element, ListItem, ReferencePart, Tab, footnote_citation_letter, gallery_images,
+};
+use std::num::NonZeroUsize;
 
 pub fn render_elements(ctx: &mut TextContext, elements: &[Element]) {
     debug!("Rendering elements (length {})", elements.len());
@@ -161,6 +165,33 @@ pub fn render_element(ctx: &mut TextContext, element: &Element) {
         Element::Video { .. } => {
             // Text cannot render video, so we don't add anything
         }
+        Element::File { source, label } => match label {
+            Some(label) => ctx.push_str(label),
+            None => ctx.push_str(source.file_name()),
+        },
+        Element::Button { .. } => {
+            // Buttons only do something when interactive, so we don't add anything
+        }
+        Element::Gallery { images, order, .. } => {
+            // Text cannot render images, so list the captions instead
+            let images = gallery_images(images.as_deref(), *order, || {
+                ctx.handle()
+                    .get_page_files(&ctx.info().site, &ctx.info().full_name())
+            });
+
+            if !ctx.ends_with_newline() {
+                ctx.add_newline();
+            }
+
+            for image in &images {
+                match &image.caption {
+                    Some(caption) => ctx.push_str(caption),
+                    None => ctx.push_str(image.file_name()),
+                }
+
+                ctx.add_newline();
+            }
+        }
         Element::List { items, .. } => {
             if !ctx.ends_with_newline() {
                 ctx.add_newline();
@@ -209,12 +240,23 @@ pub fn render_element(ctx: &mut TextContext, element: &Element) {
         Element::TableOfContents { .. } => {
             // Doesn't make sense to have a textual table of contents, skip
         }
-        Element::Footnote
-        | Element::FootnoteBlock { .. }
-        | Element::BibliographyCite { .. }
-        | Element::BibliographyBlock { .. } => {
-            // Footnotes and bibliographies cannot be cleanly rendered in text mode,
-            // so they are skipped.
+        Element::Footnote => {
+            let index = ctx.next_footnote_index();
+            render_footnote_marker(ctx, index);
+        }
+        Element::FootnoteRef { index, .. } => render_footnote_marker(ctx, *index),
+        Element::FootnoteBlock { title, hide } => {
+            if !(*hide || ctx.footnotes().is_empty()) {
+                render_footnote_block(ctx, title.as_deref());
+            }
+        }
+        Element::BibliographyCite { label, brackets } => {
+            render_bibcite(ctx, label, *brackets);
+        }
+        Element::BibliographyBlock { index, title, hide } => {
+            if !hide {
+                render_bibliography(ctx, title.as_deref(), *index);
+            }
         }
         Element::User { name, .. } => ctx.push_str(name),
         Element::Date { value, format, .. } => {
@@ -232,7 +274,19 @@ pub fn render_element(ctx: &mut TextContext, element: &Element) {
         Element::EquationReference(name) => {
             str_write!(ctx, "[{name}]");
         }
-        Element::Embed(_) | Element::Html { .. } | Element::Iframe { .. } => {
+        Element::Embed(embed) => {
+            // Interactive content can't be shown in text, so we instead
+            // give a link to the content, if the provider offers one.
+            if let Some(url) = embed.fallback_url(&ctx.settings().embed) {
+                if !ctx.ends_with_newline() {
+                    ctx.add_newline();
+                }
+
+                ctx.push_str(&url);
+                ctx.add_newline();
+            }
+        }
+        Element::Html { .. } | Element::Iframe { .. } => {
             // Interactive or HTML elements like this don't make sense in
             // text mode, so we skip them.
         }
@@ -269,3 +323,120 @@ pub fn render_element(ctx: &mut TextContext, element: &Element) {
         Element::Partial(_) => panic!("Encountered partial element during parsing"),
     }
 }
+
+fn render_footnote_marker(ctx: &mut TextContext, index: NonZeroUsize) {
+    // Footnote contents are only available when rendering a full page,
+    // partial renders (such as table of contents entries) skip the marker.
+    if ctx.get_footnote(index).is_none() {
+        return;
+    }
+
+    // If this footnote is cited more than once, then each
+    // citation gets a letter, matching its back-reference.
+    let citations = ctx.footnote_citation_total(index);
+    let occurrence = ctx.next_footnote_citation(index);
+
+    if citations > 1 {
+        let letter = footnote_citation_letter(occurrence);
+        str_write!(ctx, "[{index}{letter}]");
+    } else {
+        str_write!(ctx, "[{index}]");
+    }
+}
+
+fn render_footnote_block(ctx: &mut TextContext, title: Option<&str>) {
+    let title = match title {
+        Some(title) => title,
+        None => ctx
+            .handle()
+            .get_message(ctx.language(), "footnote-block-title"),
+    };
+
+    ctx.add_newline();
+    ctx.push_str(title);
+    ctx.add_newline();
+
+    for (index, contents) in ctx.footnotes().iter().enumerate() {
+        let index = NonZeroUsize::new(index + 1).unwrap();
+        str_write!(ctx, "{index}. ");
+
+        // Back-references to each citation, matching the HTML back-links
+        let citations = ctx.footnote_citation_total(index);
+        if citations > 1 {
+            let letters = (0..citations)
+                .map(footnote_citation_letter)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            str_write!(ctx, "({letters}) ");
+        }
+
+        render_elements(ctx, contents);
+        ctx.add_newline();
+    }
+}
+
+fn render_bibcite(ctx: &mut TextContext, label: &str, brackets: bool) {
+    match ctx.get_bibliography_ref(label) {
+        Some((style, index, contents)) => {
+            let cite_text = style.cite_text(index, label, contents);
+
+            if brackets && style.numbered() {
+                str_write!(ctx, "[{cite_text}]");
+            } else {
+                ctx.push_str(&cite_text);
+            }
+        }
+        None => {
+            let message = ctx
+                .handle()
+                .get_message(ctx.language(), "bibliography-cite-not-found");
+
+            ctx.push_str(message);
+        }
+    }
+}
+
+fn render_bibliography(ctx: &mut TextContext, title: Option<&str>, index: usize) {
+    let bibliography = ctx.get_bibliography(index);
+    let style = bibliography.style();
+    let title = match title {
+        Some(title) => title,
+        None => ctx
+            .handle()
+            .get_message(ctx.language(), "bibliography-block-title"),
+    };
+
+    ctx.add_newline();
+    ctx.push_str(title);
+    ctx.add_newline();
+
+    for (entry_index, (_, contents)) in bibliography.slice().iter().enumerate() {
+        if style.numbered() {
+            str_write!(ctx, "{}. ", entry_index + 1);
+        }
+
+        render_bibliography_contents(ctx, style, contents);
+        ctx.add_newline();
+    }
+}
+
+fn render_bibliography_contents(
+    ctx: &mut TextContext,
+    style: CitationStyle,
+    contents: &BibliographyContents,
+) {
+    match contents {
+        BibliographyContents::Elements(elements) => render_elements(ctx, elements),
+        BibliographyContents::Reference(reference) => {
+            for part in style.format_reference(reference) {
+                match part {
+                    ReferencePart::Text(text) | ReferencePart::Link(text) => {
+                        ctx.push_str(&text)
+                    }
+                    ReferencePart::Title(title) => ctx.push_str(title),
+                }
+            }
+        }
+    }
+}
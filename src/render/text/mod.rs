@@ -22,11 +22,12 @@ mod context;
 mod elements;
 
 use self::context::TextContext;
-use self::elements::render_elements;
+use self::elements::{render_element, render_elements};
 use crate::data::PageInfo;
 use crate::render::{Handle, Render};
 use crate::settings::WikitextSettings;
 use crate::tree::{BibliographyList, Element, SyntaxTree};
+use std::num::NonZeroUsize;
 
 #[derive(Debug)]
 pub struct TextRender;
@@ -46,6 +47,8 @@ impl TextRender {
             settings,
             table_of_contents: &[],
             footnotes: &[],
+            footnote_refs: &[],
+            needs_footnote_block: false,
             bibliographies: &BibliographyList::new(),
             wikitext_len,
         })
@@ -59,6 +62,8 @@ impl TextRender {
             settings,
             table_of_contents,
             footnotes,
+            footnote_refs,
+            needs_footnote_block,
             bibliographies,
             wikitext_len,
         }: RenderPartial,
@@ -73,17 +78,29 @@ impl TextRender {
             },
         );
 
+        let handle = Handle::default();
         let mut ctx = TextContext::new(
             page_info,
-            &Handle,
+            &handle,
             settings,
             table_of_contents,
-            footnotes,
+            (footnotes, footnote_refs),
             bibliographies,
             wikitext_len,
         );
         render_elements(&mut ctx, elements);
 
+        if needs_footnote_block {
+            info!("Page needs footnote but one was not manually included, adding");
+            render_element(
+                &mut ctx,
+                &Element::FootnoteBlock {
+                    title: None,
+                    hide: false,
+                },
+            );
+        }
+
         // Remove leading and trailing newlines
         while ctx.buffer().starts_with('\n') {
             ctx.buffer().remove(0);
@@ -123,6 +140,8 @@ impl Render for TextRender {
             settings,
             table_of_contents: &tree.table_of_contents,
             footnotes: &tree.footnotes,
+            footnote_refs: &tree.footnote_refs,
+            needs_footnote_block: tree.needs_footnote_block,
             bibliographies: &tree.bibliographies,
             wikitext_len: tree.wikitext_len,
         })
@@ -140,6 +159,8 @@ struct RenderPartial<'a> {
     settings: &'a WikitextSettings,
     table_of_contents: &'a [Element<'a>],
     footnotes: &'a [Vec<Element<'a>>],
+    footnote_refs: &'a [NonZeroUsize],
+    needs_footnote_block: bool,
     bibliographies: &'a BibliographyList<'a>,
     wikitext_len: usize,
 }
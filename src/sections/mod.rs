@@ -0,0 +1,219 @@
+/*
+ * sections/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! This module implements section editing.
+//!
+//! Each heading the parser recognizes begins a section, which runs until the
+//! next heading of the same or a higher level. The spans of these sections
+//! are available as [`SyntaxTree.headings`](crate::tree::SyntaxTree::headings).
+//!
+//! Only headings at the top level of the page can be edited on their own.
+//! Blocks such as `[[div]]` may contain headings, and so a section within
+//! one cannot be changed without also affecting the rest of the block.
+//!
+//! The parser only sees the wikitext after includes and preprocessing have
+//! been applied, so its spans are for that text. [`map_headings()`] converts
+//! them to spans in the page source, using the source maps from
+//! [`include_with_map()`](crate::includes::include_with_map) and
+//! [`preprocess_with_map()`](crate::preproc::preprocess_with_map).
+
+#[cfg(test)]
+mod test;
+
+use crate::data::PageInfo;
+use crate::includes::{Includer, include_with_map};
+use crate::preproc::preprocess_with_map;
+use crate::settings::WikitextSettings;
+use crate::templates::SourceMap;
+use crate::tree::{ContainerType, Element, HeadingSection, SyntaxTree};
+
+/// The reason a section could not be replaced.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SectionEditError {
+    /// There is no heading with this index.
+    NoSuchSection,
+
+    /// The heading is within a block, or on an included page,
+    /// so its section cannot be edited alone.
+    NotEditable,
+
+    /// The new text changes the page outside of the section being replaced.
+    ///
+    /// For instance, it may have an unclosed block which consumes
+    /// the sections after it, or lack a trailing newline.
+    TreeChanged,
+}
+
+/// Maps the spans of headings from the parsed wikitext back to the page source.
+///
+/// The source map should cover every step between the two, as composed from
+/// [`include_with_map()`] and [`preprocess_with_map()`].
+///
+/// Headings which are not in the page source, such as those from an included
+/// page, are marked as not editable. Their spans begin where the text containing
+/// them was inserted, such as at the `[[include]]` block.
+pub fn map_headings(headings: &mut [HeadingSection], source_map: &SourceMap) {
+    for heading in headings {
+        let start = heading.span.start;
+        if source_map.get_source_span(start..start + 1).is_none() {
+            heading.editable = false;
+        }
+
+        heading.span = source_map.get_source_position(heading.span.start)
+            ..source_map.get_source_position(heading.span.end);
+    }
+}
+
+/// Replaces the text of a section, then checks that the rest of the page is unchanged.
+///
+/// The page source is prepared for parsing in the same way as when rendering, by
+/// substituting includes with the given includer and then preprocessing it.
+/// The section is given by its index in [`SyntaxTree.headings`](SyntaxTree::headings)
+/// for the resulting tree, and `replacement` replaces its text in the page source.
+///
+/// The new page source is returned if the elements before and after the section
+/// are the same as they were originally. Errors from the includer are returned
+/// in the outer result.
+pub fn replace_section<I, E, F>(
+    source: &str,
+    index: usize,
+    replacement: &str,
+    mut includer: I,
+    invalid_return: F,
+    page_info: &PageInfo,
+    settings: &WikitextSettings,
+) -> Result<Result<String, SectionEditError>, E>
+where
+    I: for<'t> Includer<'t, Error = E>,
+    F: Fn() -> E,
+{
+    info!(
+        "Replacing section {} with new text ({} bytes)",
+        index,
+        replacement.len(),
+    );
+
+    let (text, source_map) = prepare(source, &mut includer, &invalid_return, settings)?;
+    let tokens = crate::tokenize(&text);
+    let (tree, _) = crate::parse(&tokens, page_info, settings).into();
+    let Some(section) = tree.headings.get(index) else {
+        return Ok(Err(SectionEditError::NoSuchSection));
+    };
+
+    // Find where the section is in the page source
+    let mut mapped = [section.clone()];
+    map_headings(&mut mapped, &source_map);
+
+    let [mapped] = mapped;
+    if !mapped.editable {
+        return Ok(Err(SectionEditError::NotEditable));
+    }
+
+    // Find which top-level headings are replaced, by their position
+    // among all the editable headings on the page.
+    let span = section.span.clone();
+    let editable = tree
+        .headings
+        .iter()
+        .filter(|heading| heading.editable)
+        .collect::<Vec<_>>();
+
+    let first = editable
+        .iter()
+        .position(|heading| heading.span.start == span.start)
+        .expect("Editable heading not found");
+
+    let last = editable
+        .iter()
+        .rposition(|heading| heading.span.start < span.end)
+        .expect("Editable heading not found");
+
+    let old_chunks = split_chunks(&tree);
+    if old_chunks.len() != editable.len() + 1 {
+        warn!("Top-level headings in syntax tree do not match heading list");
+        return Ok(Err(SectionEditError::NotEditable));
+    }
+
+    // Build the new page source and parse it
+    let mut new_source = String::with_capacity(source.len() + replacement.len());
+    new_source.push_str(&source[..mapped.span.start]);
+    new_source.push_str(replacement);
+    new_source.push_str(&source[mapped.span.end..]);
+
+    let (new_text, _) = prepare(&new_source, &mut includer, &invalid_return, settings)?;
+    let new_tokens = crate::tokenize(&new_text);
+    let (new_tree, _) = crate::parse(&new_tokens, page_info, settings).into();
+    let new_chunks = split_chunks(&new_tree);
+
+    // The chunk before the section's heading is the page preamble,
+    // or the content of an earlier section, so it must be kept too.
+    let before = &old_chunks[..=first];
+    let after = &old_chunks[last + 2..];
+
+    if new_chunks.len() < before.len() + after.len()
+        || new_chunks[..before.len()] != *before
+        || new_chunks[new_chunks.len() - after.len()..] != *after
+    {
+        warn!("Section replacement changes other parts of the page");
+        return Ok(Err(SectionEditError::TreeChanged));
+    }
+
+    Ok(Ok(new_source))
+}
+
+/// Applies includes and preprocessing to the page source, as is done before parsing.
+fn prepare<I, E, F>(
+    source: &str,
+    includer: &mut I,
+    invalid_return: &F,
+    settings: &WikitextSettings,
+) -> Result<(String, SourceMap), E>
+where
+    I: for<'t> Includer<'t, Error = E>,
+    F: Fn() -> E,
+{
+    let (mut text, _, include_map) =
+        include_with_map(source, settings, includer, invalid_return)?;
+
+    let source_map = preprocess_with_map(&mut text).compose(&include_map);
+    Ok((text, source_map))
+}
+
+/// Splits the top-level elements of a tree at each top-level heading.
+///
+/// The first chunk is everything before the first heading,
+/// then each subsequent chunk starts with a heading.
+fn split_chunks<'a, 't>(tree: &'a SyntaxTree<'t>) -> Vec<&'a [Element<'t>]> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    for (index, element) in tree.elements.iter().enumerate() {
+        if let Element::Container(container) = element
+            && let ContainerType::Header(_) = container.ctype()
+        {
+            chunks.push(&tree.elements[start..index]);
+            start = index;
+        }
+    }
+
+    chunks.push(&tree.elements[start..]);
+    chunks
+}
@@ -0,0 +1,223 @@
+/*
+ * sections/test.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{SectionEditError, map_headings, replace_section};
+use crate::data::PageInfo;
+use crate::includes::{NullIncluder, include_with_map};
+use crate::layout::Layout;
+use crate::preproc::preprocess_with_map;
+use crate::settings::{WikitextMode, WikitextSettings};
+use crate::test::includer::TestIncluder;
+
+const INPUT: &str = "\
+Intro text.
+
++ Apple
+Red fruit.
+
+[[div]]
+++ Inner
+In a block.
+[[/div]]
+
+++ Banana
+Yellow fruit.
+
++ Cherry
+Small fruit.
+";
+
+#[test]
+fn sections() {
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let tokens = crate::tokenize(INPUT);
+    let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+
+    let actual = tree
+        .headings
+        .iter()
+        .map(|section| {
+            (
+                section.name.as_str(),
+                section.editable,
+                &INPUT[section.span.clone()],
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        actual,
+        [
+            (
+                "Apple",
+                true,
+                "+ Apple\nRed fruit.\n\n[[div]]\n++ Inner\nIn a block.\n[[/div]]\n\n++ Banana\nYellow fruit.\n\n",
+            ),
+            ("Inner", false, "++ Inner\nIn a block.\n[[/div]]\n\n"),
+            ("Banana", true, "++ Banana\nYellow fruit.\n\n"),
+            ("Cherry", true, "+ Cherry\nSmall fruit.\n"),
+        ],
+    );
+}
+
+#[test]
+fn replace() {
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    macro_rules! test {
+        ($index:expr, $replacement:expr, $expected:expr $(,)?) => {{
+            let actual = replace_section(
+                INPUT,
+                $index,
+                $replacement,
+                NullIncluder,
+                || unreachable!(),
+                &page_info,
+                &settings,
+            )
+            .unwrap();
+
+            assert_eq!(
+                actual, $expected,
+                "Replacing section {} with {:?} did not produce the expected result",
+                $index, $replacement,
+            );
+        }};
+    }
+
+    test!(
+        2,
+        "++ Blueberry\nBlue fruit.\n\n",
+        Ok(INPUT.replace("++ Banana\nYellow fruit.", "++ Blueberry\nBlue fruit.")),
+    );
+    test!(
+        3,
+        "+ Cherry\nSmall fruit.\n\n+ Durian\nSmelly fruit.\n",
+        Ok(format!("{INPUT}\n+ Durian\nSmelly fruit.\n")),
+    );
+    test!(
+        0,
+        "+ Apple\nGreen fruit.\n\n",
+        Ok(INPUT.replace(
+            "Red fruit.\n\n[[div]]\n++ Inner\nIn a block.\n[[/div]]\n\n++ Banana\nYellow fruit.\n\n",
+            "Green fruit.\n\n",
+        )),
+    );
+
+    test!(4, "+ Nothing\n", Err(SectionEditError::NoSuchSection));
+    test!(1, "++ Inner\n", Err(SectionEditError::NotEditable));
+
+    // Without a newline, the next heading is part of this section
+    test!(
+        2,
+        "++ Banana\nYellow fruit.",
+        Err(SectionEditError::TreeChanged)
+    );
+
+    // Losing the heading merges this section into the previous one
+    test!(2, "Yellow fruit.\n", Err(SectionEditError::TreeChanged));
+}
+
+const INCLUDE_INPUT: &str = "Intro text.\r\n\r\n+ Apple\r\nRed fruit.\r\n\r\n[[include fragment:heading]]\r\n\r\n+ Cherry\r\nSmall fruit.\r\n";
+
+#[test]
+fn sections_include() {
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let (mut text, _, include_map) =
+        include_with_map(INCLUDE_INPUT, &settings, TestIncluder, || unreachable!())
+            .unwrap();
+
+    let source_map = preprocess_with_map(&mut text).compose(&include_map);
+    let tokens = crate::tokenize(&text);
+    let (mut tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+    map_headings(&mut tree.headings, &source_map);
+
+    let actual = tree
+        .headings
+        .iter()
+        .map(|section| {
+            (
+                section.name.as_str(),
+                section.editable,
+                &INCLUDE_INPUT[section.span.clone()],
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        actual,
+        [
+            (
+                "Apple",
+                true,
+                "+ Apple\r\nRed fruit.\r\n\r\n[[include fragment:heading]]\r\n\r\n",
+            ),
+            ("Included", false, "[[include fragment:heading]]\r\n\r\n"),
+            ("Cherry", true, "+ Cherry\r\nSmall fruit.\r\n"),
+        ],
+    );
+}
+
+#[test]
+fn replace_include() {
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+
+    macro_rules! test {
+        ($index:expr, $replacement:expr, $expected:expr $(,)?) => {{
+            let actual = replace_section(
+                INCLUDE_INPUT,
+                $index,
+                $replacement,
+                TestIncluder,
+                || unreachable!(),
+                &page_info,
+                &settings,
+            )
+            .unwrap();
+
+            assert_eq!(
+                actual, $expected,
+                "Replacing section {} with {:?} did not produce the expected result",
+                $index, $replacement,
+            );
+        }};
+    }
+
+    // Indices count the heading on the included page
+    test!(
+        2,
+        "+ Durian\nSmelly fruit.\n",
+        Ok(INCLUDE_INPUT
+            .replace("+ Cherry\r\nSmall fruit.\r\n", "+ Durian\nSmelly fruit.\n")),
+    );
+    test!(1, "++ Other\n", Err(SectionEditError::NotEditable));
+    test!(3, "+ Nothing\n", Err(SectionEditError::NoSuchSection));
+
+    // The section contains the include, so it is kept in the source
+    test!(
+        0,
+        "+ Apple\r\nGreen fruit.\r\n\r\n[[include fragment:heading]]\r\n\r\n",
+        Ok(INCLUDE_INPUT.replace("Red fruit.", "Green fruit.")),
+    );
+}
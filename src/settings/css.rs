@@ -0,0 +1,208 @@
+/*
+ * settings/css.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Policy for user-provided stylesheets, such as from `[[module CSS]]`.
+
+use super::WikitextMode;
+use std::borrow::Cow;
+
+macro_rules! properties {
+    ($($name:expr),+ $(,)?) => {
+        &[$(Cow::Borrowed($name)),+]
+    };
+}
+
+/// The CSS properties permitted in inline styles under [`CssSettings::RESTRICTED`].
+///
+/// These cover typical text formatting and layout, but not
+/// properties which can place elements outside of their container.
+pub const DEFAULT_INLINE_PROPERTIES: &[Cow<'static, str>] = properties![
+    "background",
+    "background-color",
+    "background-image",
+    "background-position",
+    "background-repeat",
+    "background-size",
+    "border",
+    "border-bottom",
+    "border-collapse",
+    "border-color",
+    "border-left",
+    "border-radius",
+    "border-right",
+    "border-spacing",
+    "border-style",
+    "border-top",
+    "border-width",
+    "box-shadow",
+    "clear",
+    "color",
+    "display",
+    "float",
+    "font",
+    "font-family",
+    "font-size",
+    "font-style",
+    "font-variant",
+    "font-weight",
+    "height",
+    "letter-spacing",
+    "line-height",
+    "list-style",
+    "list-style-type",
+    "margin",
+    "margin-bottom",
+    "margin-left",
+    "margin-right",
+    "margin-top",
+    "max-height",
+    "max-width",
+    "min-height",
+    "min-width",
+    "opacity",
+    "overflow",
+    "overflow-wrap",
+    "overflow-x",
+    "overflow-y",
+    "padding",
+    "padding-bottom",
+    "padding-left",
+    "padding-right",
+    "padding-top",
+    "text-align",
+    "text-decoration",
+    "text-indent",
+    "text-shadow",
+    "text-transform",
+    "vertical-align",
+    "white-space",
+    "width",
+    "word-break",
+    "word-spacing",
+];
+
+/// Settings restricting what user-provided CSS is permitted to do.
+///
+/// By default stylesheets are emitted as-is (after being normalized by the
+/// CSS parser), which means they may restyle anything on the page, including
+/// site elements outside of the rendered wikitext.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct CssSettings {
+    /// The selector all user style rules are nested under, if any.
+    ///
+    /// For instance, with a scope of `#page-content`, the rule `.note { ... }`
+    /// becomes `#page-content .note { ... }`. Selectors beginning with `:root`,
+    /// `html` or `body` have that part replaced with the scope, so that
+    /// `body { ... }` styles the page container itself.
+    ///
+    /// If `None`, then selectors are not rewritten.
+    pub scope: Option<Cow<'static, str>>,
+
+    /// Whether `@import` rules are kept.
+    ///
+    /// If a scope is set, they are always removed,
+    /// since the imported stylesheets cannot be scoped.
+    pub allow_import: bool,
+
+    /// Whether `url()` values pointing to other origins are kept.
+    ///
+    /// If disabled, any declaration referencing an absolute URL
+    /// (except `data:` URIs) is removed. Since its value cannot be checked,
+    /// any declaration using `var()` is also removed.
+    pub allow_external_urls: bool,
+
+    /// Whether `position: fixed` declarations are kept.
+    ///
+    /// These permit elements to be placed anywhere in the viewport,
+    /// for instance to overlay site navigation. If disabled, declarations
+    /// using `var()` are removed, as with `allow_external_urls`.
+    pub allow_fixed_position: bool,
+
+    /// Which properties are permitted in inline `style` attributes.
+    ///
+    /// Inline styles are always checked one declaration at a time, removing
+    /// any malformed or unknown declarations, as well as any not allowed by
+    /// the other options here. The remaining declarations are kept as written. If this is `Some`, then only the listed
+    /// properties are kept. Custom properties (`--name`) are always kept.
+    pub inline_properties: Option<Cow<'static, [Cow<'static, str>]>>,
+}
+
+impl CssSettings {
+    /// Settings which impose no restrictions on user CSS.
+    pub const PERMISSIVE: Self = CssSettings {
+        scope: None,
+        allow_import: true,
+        allow_external_urls: true,
+        allow_fixed_position: true,
+        inline_properties: None,
+    };
+
+    /// Settings which remove all potentially dangerous constructs.
+    ///
+    /// This does not set a scope, since that depends on the host's markup.
+    pub const RESTRICTED: Self = CssSettings {
+        scope: None,
+        allow_import: false,
+        allow_external_urls: false,
+        allow_fixed_position: false,
+        inline_properties: Some(Cow::Borrowed(DEFAULT_INLINE_PROPERTIES)),
+    };
+
+    /// Returns the default CSS policy for the given [`WikitextMode`].
+    pub fn from_mode(mode: WikitextMode) -> Self {
+        match mode {
+            WikitextMode::Page
+            | WikitextMode::PageNav
+            | WikitextMode::Draft
+            | WikitextMode::List => CssSettings::PERMISSIVE,
+            WikitextMode::ForumPost | WikitextMode::DirectMessage => {
+                CssSettings::RESTRICTED
+            }
+        }
+    }
+
+    /// Whether this policy does not alter stylesheets at all.
+    #[inline]
+    pub fn is_permissive(&self) -> bool {
+        self.scope.is_none()
+            && self.allow_import
+            && self.allow_external_urls
+            && self.allow_fixed_position
+    }
+
+    /// Whether the given property may be used in inline styles.
+    pub fn inline_property_allowed(&self, name: &str) -> bool {
+        match &self.inline_properties {
+            None => true,
+            Some(_) if name.starts_with("--") => true,
+            Some(properties) => properties
+                .iter()
+                .any(|property| property.eq_ignore_ascii_case(name)),
+        }
+    }
+}
+
+impl Default for CssSettings {
+    #[inline]
+    fn default() -> Self {
+        CssSettings::PERMISSIVE
+    }
+}
@@ -0,0 +1,363 @@
+/*
+ * settings/embed.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! The registry of providers usable with `[[embed]]`.
+//!
+//! Each provider is described entirely by data: what arguments it takes,
+//! what those arguments may contain, and how to produce the embedded content
+//! from them. This permits hosts to add providers through settings.
+
+use std::borrow::Cow;
+
+/// The maximum length of any embed argument value.
+const MAX_ARGUMENT_LENGTH: usize = 128;
+
+/// The built-in embed providers.
+///
+/// These are always available, but may be overridden by
+/// registering a provider with the same name.
+pub const BUILTIN_EMBED_PROVIDERS: &[EmbedProvider] = &[
+    EmbedProvider {
+        name: Cow::Borrowed("youtube"),
+        arguments: Cow::Borrowed(&[EmbedArgument::required(
+            "video",
+            EmbedCharset::Identifier,
+        )]),
+        kind: EmbedKind::Iframe,
+        src: Cow::Borrowed("https://www.youtube.com/embed/{video}"),
+        allow: Some(Cow::Borrowed(
+            "accelerometer; autoplay; clipboard-write; encrypted-media; \
+             gyroscope; picture-in-picture",
+        )),
+        sandbox: None,
+        allow_fullscreen: true,
+        fallback: Cow::Borrowed("https://youtu.be/{video}"),
+    },
+    EmbedProvider {
+        name: Cow::Borrowed("vimeo"),
+        arguments: Cow::Borrowed(&[EmbedArgument::required(
+            "video",
+            EmbedCharset::Numeric,
+        )]),
+        kind: EmbedKind::Iframe,
+        src: Cow::Borrowed("https://player.vimeo.com/video/{video}"),
+        allow: Some(Cow::Borrowed("autoplay; fullscreen; picture-in-picture")),
+        sandbox: None,
+        allow_fullscreen: true,
+        fallback: Cow::Borrowed("https://vimeo.com/{video}"),
+    },
+    EmbedProvider {
+        name: Cow::Borrowed("github-gist"),
+        arguments: Cow::Borrowed(&[
+            EmbedArgument::required("username", EmbedCharset::Identifier),
+            EmbedArgument::required("hash", EmbedCharset::Hex),
+        ]),
+        kind: EmbedKind::Script,
+        src: Cow::Borrowed("https://gist.github.com/{username}/{hash}.js"),
+        allow: None,
+        sandbox: None,
+        allow_fullscreen: false,
+        fallback: Cow::Borrowed("https://gist.github.com/{username}/{hash}"),
+    },
+    EmbedProvider {
+        name: Cow::Borrowed("gitlab-snippet"),
+        arguments: Cow::Borrowed(&[EmbedArgument::required("id", EmbedCharset::Numeric)]),
+        kind: EmbedKind::Script,
+        src: Cow::Borrowed("https://gitlab.com/-/snippets/{id}.js"),
+        allow: None,
+        sandbox: None,
+        allow_fullscreen: false,
+        fallback: Cow::Borrowed("https://gitlab.com/-/snippets/{id}"),
+    },
+];
+
+/// Settings for which embed providers are available.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct EmbedSettings {
+    /// Additional providers, beyond the built-in ones.
+    ///
+    /// If any of these have the same name as a built-in provider,
+    /// then they take precedence.
+    pub providers: Vec<EmbedProvider>,
+}
+
+impl EmbedSettings {
+    /// Creates a new instance with only the built-in providers.
+    #[inline]
+    pub fn new() -> Self {
+        EmbedSettings::default()
+    }
+
+    /// Adds a provider, replacing any previously-registered one of the same name.
+    pub fn register(&mut self, provider: EmbedProvider) {
+        self.providers
+            .retain(|existing| !existing.name.eq_ignore_ascii_case(&provider.name));
+        self.providers.push(provider);
+    }
+
+    /// Looks up a provider by its name, case-insensitively.
+    pub fn get(&self, name: &str) -> Option<&EmbedProvider> {
+        self.providers
+            .iter()
+            .chain(BUILTIN_EMBED_PROVIDERS)
+            .find(|provider| provider.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// A definition for an external site which can be embedded.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct EmbedProvider {
+    /// The name of the provider, as used in `[[embed]]`.
+    ///
+    /// This is matched case-insensitively.
+    pub name: Cow<'static, str>,
+
+    /// What arguments the provider accepts.
+    pub arguments: Cow<'static, [EmbedArgument]>,
+
+    /// What kind of HTML element loads the embedded content.
+    pub kind: EmbedKind,
+
+    /// The template for the URL of the embedded content.
+    ///
+    /// Each instance of `{name}` is replaced with the value of that argument.
+    pub src: Cow<'static, str>,
+
+    /// The value of the iframe's `allow` attribute, if any.
+    #[serde(default)]
+    pub allow: Option<Cow<'static, str>>,
+
+    /// The value of the iframe's `sandbox` attribute, if any.
+    #[serde(default)]
+    pub sandbox: Option<Cow<'static, str>>,
+
+    /// Whether the iframe may be made fullscreen.
+    #[serde(default)]
+    pub allow_fullscreen: bool,
+
+    /// The template for a URL to the content, used when it cannot be embedded.
+    ///
+    /// For instance, this is what text rendering outputs.
+    /// This uses the same substitution as `src`.
+    pub fallback: Cow<'static, str>,
+}
+
+impl EmbedProvider {
+    /// Builds the URL of the embedded content from validated arguments.
+    #[inline]
+    pub fn build_src<'a, I>(&self, arguments: I) -> String
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        self.fill_template(&self.src, arguments)
+    }
+
+    /// Builds the fallback URL of the content from validated arguments.
+    #[inline]
+    pub fn build_fallback<'a, I>(&self, arguments: I) -> String
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        self.fill_template(&self.fallback, arguments)
+    }
+
+    /// Replaces each `{name}` in the template with the corresponding argument value.
+    ///
+    /// Optional arguments which were omitted are substituted as an empty string.
+    fn fill_template<'a, I>(&self, template: &str, arguments: I) -> String
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut output = str!(template);
+
+        for (name, value) in arguments {
+            output = output.replace(&template_pattern(name), value);
+        }
+
+        for argument in self.arguments.iter() {
+            output = output.replace(&template_pattern(&argument.name), "");
+        }
+
+        output
+    }
+}
+
+/// A single argument accepted by an embed provider.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct EmbedArgument {
+    /// The name of the argument, matched case-insensitively.
+    pub name: Cow<'static, str>,
+
+    /// What characters the value may contain.
+    pub charset: EmbedCharset,
+
+    /// Whether the argument may be omitted.
+    ///
+    /// If so, it is substituted as an empty string in templates.
+    #[serde(default)]
+    pub optional: bool,
+}
+
+impl EmbedArgument {
+    /// Creates a required argument with the given character set.
+    pub const fn required(name: &'static str, charset: EmbedCharset) -> Self {
+        EmbedArgument {
+            name: Cow::Borrowed(name),
+            charset,
+            optional: false,
+        }
+    }
+
+    /// Creates an optional argument with the given character set.
+    pub const fn optional(name: &'static str, charset: EmbedCharset) -> Self {
+        EmbedArgument {
+            name: Cow::Borrowed(name),
+            charset,
+            optional: true,
+        }
+    }
+
+    /// Determines if the given value is acceptable for this argument.
+    pub fn validate(&self, value: &str) -> bool {
+        !value.is_empty()
+            && value.len() <= MAX_ARGUMENT_LENGTH
+            && value.chars().all(|ch| self.charset.contains(ch))
+    }
+}
+
+/// The set of characters an embed argument may contain.
+///
+/// All of these sets are safe to substitute into a URL as-is.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EmbedCharset {
+    /// ASCII digits only.
+    Numeric,
+
+    /// Hexadecimal digits, of either case.
+    Hex,
+
+    /// ASCII letters and digits.
+    Alphanumeric,
+
+    /// ASCII letters and digits, as well as `-` and `_`.
+    Identifier,
+}
+
+impl EmbedCharset {
+    pub fn contains(self, ch: char) -> bool {
+        match self {
+            EmbedCharset::Numeric => ch.is_ascii_digit(),
+            EmbedCharset::Hex => ch.is_ascii_hexdigit(),
+            EmbedCharset::Alphanumeric => ch.is_ascii_alphanumeric(),
+            EmbedCharset::Identifier => {
+                ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_')
+            }
+        }
+    }
+}
+
+/// What HTML element is used to load embedded content.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum EmbedKind {
+    /// An `<iframe>`, with the `src` as its source.
+    Iframe,
+
+    /// A `<script>`, with the `src` as its source.
+    ///
+    /// The script is expected to insert the content itself.
+    Script,
+}
+
+/// Gets the placeholder for an argument in a template, such as `{video}`.
+#[inline]
+fn template_pattern(name: &str) -> String {
+    format!("{{{name}}}")
+}
+
+#[test]
+fn embed_registry() {
+    let mut settings = EmbedSettings::new();
+    assert!(settings.get("YouTube").is_some());
+    assert!(settings.get("GitLab-Snippet").is_some());
+    assert!(settings.get("peertube").is_none());
+
+    let peertube = EmbedProvider {
+        name: cow!("peertube"),
+        arguments: Cow::Owned(vec![EmbedArgument::required(
+            "video",
+            EmbedCharset::Identifier,
+        )]),
+        kind: EmbedKind::Iframe,
+        src: cow!("https://videos.example.com/videos/embed/{video}"),
+        allow: None,
+        sandbox: Some(cow!("allow-same-origin allow-scripts allow-popups")),
+        allow_fullscreen: true,
+        fallback: cow!("https://videos.example.com/w/{video}"),
+    };
+
+    settings.register(peertube.clone());
+    let provider = settings.get("PeerTube").expect("Provider not registered");
+    assert_eq!(provider, &peertube);
+    assert_eq!(
+        provider.build_src([("video", "abc-123")]),
+        "https://videos.example.com/videos/embed/abc-123",
+    );
+
+    // Overriding a built-in provider
+    let youtube = EmbedProvider {
+        name: cow!("youtube"),
+        src: cow!("https://www.youtube-nocookie.com/embed/{video}"),
+        ..BUILTIN_EMBED_PROVIDERS[0].clone()
+    };
+
+    settings.register(youtube);
+    assert_eq!(
+        settings
+            .get("youtube")
+            .expect("No YouTube provider")
+            .build_src([("video", "dQw4w9WgXcQ")]),
+        "https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ",
+    );
+}
+
+#[test]
+fn embed_argument_validation() {
+    let argument = EmbedArgument::required("id", EmbedCharset::Numeric);
+    assert!(argument.validate("12345"));
+    assert!(!argument.validate(""));
+    assert!(!argument.validate("12a"));
+    assert!(!argument.validate(&"1".repeat(MAX_ARGUMENT_LENGTH + 1)));
+
+    let argument = EmbedArgument::required("video", EmbedCharset::Identifier);
+    assert!(argument.validate("dQw4w9WgXcQ"));
+    assert!(argument.validate("a_b-c"));
+    assert!(!argument.validate("a/../b"));
+    assert!(!argument.validate("a\"><script>"));
+    assert!(!argument.validate("a?b=c"));
+
+    let argument = EmbedArgument::required("hash", EmbedCharset::Hex);
+    assert!(argument.validate("aa8e7fd1bc82d9d3c2b7a0b1"));
+    assert!(!argument.validate("xyz"));
+}
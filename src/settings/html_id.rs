@@ -0,0 +1,56 @@
+/*
+ * settings/html_id.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::borrow::Cow;
+
+/// How automatically generated HTML IDs (the `wj-id-*` values) are produced.
+///
+/// These IDs are needed by interactive elements such as tabviews, or by
+/// containers when `use_true_ids` is disabled. Whichever strategy is chosen,
+/// the IDs emitted within a single rendered document never collide.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum HtmlIdStrategy {
+    /// IDs are random, seeded from the operating system on each render.
+    ///
+    /// This means the same page will produce different HTML every time.
+    #[default]
+    Random,
+
+    /// IDs are pseudorandom, but seeded from the page's identity.
+    ///
+    /// The seed is derived from the page's site, category, and slug, combined
+    /// with the given revision number. Rendering the same revision of a page
+    /// always produces the same IDs, which keeps output cacheable.
+    Seeded { revision: u64 },
+
+    /// IDs are sequential, in the form `wj-id-0`, `wj-id-1`, etc.
+    Counter,
+
+    /// IDs are sequential, with a caller-provided prefix.
+    ///
+    /// These are in the form `wj-id-{prefix}-0`, `wj-id-{prefix}-1`, etc.
+    /// This is useful when several fragments (such as forum posts) are rendered
+    /// separately but placed in the same page, as each can be given its own prefix.
+    ///
+    /// Any characters which are not ASCII alphanumeric, `-`, or `_` are
+    /// replaced with `-`.
+    Prefixed { prefix: Cow<'static, str> },
+}
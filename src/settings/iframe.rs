@@ -0,0 +1,242 @@
+/*
+ * settings/iframe.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Policy for iframes, such as from `[[iframe]]` and `[[html]]`.
+
+use super::WikitextMode;
+use std::borrow::Cow;
+
+/// Settings restricting which iframes may be emitted, and how.
+///
+/// This applies both to `[[iframe]]` blocks and to the iframes generated for
+/// `[[html]]` blocks (using whatever URL the host returned for the contents).
+/// Iframes which are not permitted are replaced with a visible notice.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct IframeSettings {
+    /// Whether iframes are permitted at all.
+    pub enabled: bool,
+
+    /// Which origins iframes may point to, if restricted.
+    ///
+    /// Each entry is one of:
+    /// * A hostname, such as `example.com`, matching only that host.
+    /// * A wildcard, such as `*.example.com`, matching any subdomain
+    ///   of that host (but not the host itself).
+    /// * A full origin, such as `https://example.com:8080`, matching
+    ///   that scheme, host and port exactly.
+    ///
+    /// Entries are matched case-insensitively. If `None`, then iframes
+    /// to any origin are permitted.
+    pub allowed_origins: Option<Cow<'static, [Cow<'static, str>]>>,
+
+    /// The value of the `sandbox` attribute added to each iframe, if any.
+    ///
+    /// An empty string applies every sandbox restriction.
+    pub sandbox: Option<Cow<'static, str>>,
+
+    /// The value of the `referrerpolicy` attribute added to each iframe, if any.
+    pub referrer_policy: Option<Cow<'static, str>>,
+
+    /// Whether iframes are marked with `loading="lazy"`.
+    pub lazy_loading: bool,
+}
+
+impl IframeSettings {
+    /// Settings which permit all iframes, and add no attributes.
+    pub const PERMISSIVE: Self = IframeSettings {
+        enabled: true,
+        allowed_origins: None,
+        sandbox: None,
+        referrer_policy: None,
+        lazy_loading: false,
+    };
+
+    /// Settings which disable iframes entirely.
+    ///
+    /// The attribute options are set so that re-enabling iframes
+    /// (with an origin allowlist) results in a reasonable policy.
+    pub const RESTRICTED: Self = IframeSettings {
+        enabled: false,
+        allowed_origins: Some(Cow::Borrowed(&[])),
+        sandbox: Some(Cow::Borrowed("allow-scripts allow-popups")),
+        referrer_policy: Some(Cow::Borrowed("no-referrer")),
+        lazy_loading: true,
+    };
+
+    /// Returns the default iframe policy for the given [`WikitextMode`].
+    pub fn from_mode(mode: WikitextMode) -> Self {
+        match mode {
+            WikitextMode::Page
+            | WikitextMode::PageNav
+            | WikitextMode::Draft
+            | WikitextMode::List => IframeSettings::PERMISSIVE,
+            WikitextMode::ForumPost | WikitextMode::DirectMessage => {
+                IframeSettings::RESTRICTED
+            }
+        }
+    }
+
+    /// Whether an iframe pointing to the given URL may be emitted.
+    pub fn allows(&self, url: &str) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        let patterns = match &self.allowed_origins {
+            None => return true,
+            Some(patterns) => patterns,
+        };
+
+        let Some(origin) = Origin::parse(url) else {
+            return false;
+        };
+
+        patterns.iter().any(|pattern| origin.matches(pattern))
+    }
+}
+
+impl Default for IframeSettings {
+    #[inline]
+    fn default() -> Self {
+        IframeSettings::PERMISSIVE
+    }
+}
+
+/// The origin of an absolute URL, as used for allowlist matching.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Origin<'a> {
+    scheme: &'a str,
+    host: &'a str,
+    port: Option<&'a str>,
+}
+
+impl<'a> Origin<'a> {
+    fn parse(url: &'a str) -> Option<Self> {
+        let (scheme, rest) = url.split_once("://")?;
+        if scheme.is_empty()
+            || !scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        {
+            return None;
+        }
+
+        // Authority ends at the first path, query, or fragment delimiter
+        let authority = match rest.find(['/', '?', '#', '\\']) {
+            Some(index) => &rest[..index],
+            None => rest,
+        };
+
+        // Strip any userinfo, which could otherwise disguise the host
+        let authority = match authority.rfind('@') {
+            Some(index) => &authority[index + 1..],
+            None => authority,
+        };
+
+        // IPv6 addresses contain colons, but are bracketed
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !authority.ends_with(']') => (host, Some(port)),
+            _ => (authority, None),
+        };
+
+        // Trailing dots refer to the same host
+        let host = host.trim_end_matches('.');
+        if host.is_empty() {
+            return None;
+        }
+
+        Some(Origin { scheme, host, port })
+    }
+
+    fn matches(&self, pattern: &str) -> bool {
+        if pattern.contains("://") {
+            return match Origin::parse(pattern) {
+                Some(other) => {
+                    self.scheme.eq_ignore_ascii_case(other.scheme)
+                        && self.host.eq_ignore_ascii_case(other.host)
+                        && self.port == other.port
+                }
+                None => false,
+            };
+        }
+
+        match pattern.strip_prefix("*.") {
+            Some(suffix) => {
+                let host = self.host.as_bytes();
+                let suffix = suffix.as_bytes();
+
+                host.len() > suffix.len() + 1
+                    && host[host.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+                    && host[host.len() - suffix.len() - 1] == b'.'
+            }
+            None => self.host.eq_ignore_ascii_case(pattern),
+        }
+    }
+}
+
+#[test]
+fn iframe_origins() {
+    let settings = IframeSettings {
+        allowed_origins: Some(Cow::Owned(vec![
+            Cow::Borrowed("example.com"),
+            Cow::Borrowed("*.wikijump.net"),
+            Cow::Borrowed("https://apps.example.org:8443"),
+        ])),
+        ..IframeSettings::PERMISSIVE
+    };
+
+    macro_rules! check {
+        ($url:expr, $expected:expr $(,)?) => {
+            assert_eq!(
+                settings.allows($url),
+                $expected,
+                "Iframe origin check for {:?} didn't match expected",
+                $url,
+            );
+        };
+    }
+
+    check!("https://example.com/", true);
+    check!("http://EXAMPLE.com/page?x=1", true);
+    check!("https://example.com.", true);
+    check!("https://example.com:8080/", true);
+    check!("https://sub.example.com/", false);
+    check!("https://example.com.evil.net/", false);
+    check!("https://example.com@evil.net/", false);
+    check!("https://evil.net/?example.com", false);
+    check!("https://evil.net\\@example.com/", false);
+    check!("https://scp.wikijump.net/", true);
+    check!("https://a.b.wikijump.net/", true);
+    check!("https://wikijump.net/", false);
+    check!("https://notwikijump.net/", false);
+    check!("https://apps.example.org:8443/x", true);
+    check!("https://apps.example.org/x", false);
+    check!("http://apps.example.org:8443/x", false);
+    check!("/local/path", false);
+    check!("javascript:alert(1)", false);
+
+    let disabled = IframeSettings {
+        enabled: false,
+        ..IframeSettings::PERMISSIVE
+    };
+    assert!(!disabled.allows("https://example.com/"));
+    assert!(IframeSettings::PERMISSIVE.allows("/local/path"));
+}
@@ -18,11 +18,25 @@
  * along with this program. If not, see <http://www.gnu.org/licenses/>.
  */
 
+mod css;
+mod embed;
+mod html_id;
+mod iframe;
 mod interwiki;
 
 use crate::layout::Layout;
 use crate::next_index::Incrementer;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::str::FromStr;
 
+pub use self::css::{CssSettings, DEFAULT_INLINE_PROPERTIES};
+pub use self::embed::{
+    BUILTIN_EMBED_PROVIDERS, EmbedArgument, EmbedCharset, EmbedKind, EmbedProvider,
+    EmbedSettings,
+};
+pub use self::html_id::HtmlIdStrategy;
+pub use self::iframe::IframeSettings;
 pub use self::interwiki::{DEFAULT_INTERWIKI, EMPTY_INTERWIKI, InterwikiSettings};
 
 const DEFAULT_MINIFY_CSS: bool = true;
@@ -55,6 +69,21 @@ pub struct WikitextSettings {
     /// context where more than one instance of rendered wikitext could be emitted.
     pub use_true_ids: bool,
 
+    /// How automatically generated HTML IDs are produced.
+    ///
+    /// These are the `wj-id-*` values used by tabviews, as well as any
+    /// elements which would otherwise have a true ID when `use_true_ids`
+    /// is disabled. See [`HtmlIdStrategy`] for the available options.
+    #[serde(default)]
+    pub id_strategy: HtmlIdStrategy,
+
+    /// Whether headings get a permalink anchor.
+    ///
+    /// When enabled, the Wikijump layout adds a `¶` link within each
+    /// heading which has an ID, for linking directly to that section.
+    #[serde(default)]
+    pub heading_permalinks: bool,
+
     /// Whether to prefix user IDs with `u-`.
     ///
     /// This is a behavior found in Wikidot (although implemented incompletely)
@@ -65,6 +94,12 @@ pub struct WikitextSettings {
     /// Whether to minify CSS in `<style>` blocks.
     pub minify_css: bool,
 
+    /// What restrictions are applied to CSS in `<style>` blocks.
+    ///
+    /// See [`CssSettings`] for the available options.
+    #[serde(default)]
+    pub css: CssSettings,
+
     /// Whether local paths are permitted.
     ///
     /// This should be disabled in contexts where there is no "local context"
@@ -90,6 +125,27 @@ pub struct WikitextSettings {
     ///   any beyond that are considered part of the link.
     /// * By convention, prefixes should be all-lowercase.
     pub interwiki: InterwikiSettings,
+
+    /// What embed providers are available, in addition to the built-in ones.
+    ///
+    /// See [`EmbedSettings`] for more information.
+    #[serde(default)]
+    pub embed: EmbedSettings,
+
+    /// What restrictions are applied to iframes.
+    ///
+    /// See [`IframeSettings`] for the available options.
+    #[serde(default)]
+    pub iframe: IframeSettings,
+
+    /// Additional variables available to `{$name}` substitutions.
+    ///
+    /// These are available in addition to the built-in variables (see
+    /// [`BUILTIN_VARIABLES`](crate::tree::BUILTIN_VARIABLES)), and override
+    /// them if the names overlap. Variables passed to includes take precedence
+    /// over both.
+    #[serde(default)]
+    pub variables: HashMap<Cow<'static, str>, Cow<'static, str>>,
 }
 
 impl WikitextSettings {
@@ -103,50 +159,80 @@ impl WikitextSettings {
                 layout,
                 enable_page_syntax: true,
                 use_true_ids: true,
+                id_strategy: HtmlIdStrategy::Random,
+                heading_permalinks: false,
                 isolate_user_ids: false,
                 minify_css: DEFAULT_MINIFY_CSS,
+                css: CssSettings::from_mode(mode),
                 allow_local_paths: true,
                 interwiki,
+                embed: EmbedSettings::new(),
+                iframe: IframeSettings::from_mode(mode),
+                variables: HashMap::new(),
             },
             WikitextMode::PageNav => WikitextSettings {
                 mode,
                 layout,
                 enable_page_syntax: true,
                 use_true_ids: false,
+                id_strategy: HtmlIdStrategy::Random,
+                heading_permalinks: false,
                 isolate_user_ids: false,
                 minify_css: DEFAULT_MINIFY_CSS,
+                css: CssSettings::from_mode(mode),
                 allow_local_paths: true,
                 interwiki,
+                embed: EmbedSettings::new(),
+                iframe: IframeSettings::from_mode(mode),
+                variables: HashMap::new(),
             },
             WikitextMode::Draft => WikitextSettings {
                 mode,
                 layout,
                 enable_page_syntax: true,
                 use_true_ids: false,
+                id_strategy: HtmlIdStrategy::Random,
+                heading_permalinks: false,
                 isolate_user_ids: false,
                 minify_css: DEFAULT_MINIFY_CSS,
+                css: CssSettings::from_mode(mode),
                 allow_local_paths: true,
                 interwiki,
+                embed: EmbedSettings::new(),
+                iframe: IframeSettings::from_mode(mode),
+                variables: HashMap::new(),
             },
             WikitextMode::ForumPost | WikitextMode::DirectMessage => WikitextSettings {
                 mode,
                 layout,
                 enable_page_syntax: false,
                 use_true_ids: false,
+                id_strategy: HtmlIdStrategy::Random,
+                heading_permalinks: false,
                 isolate_user_ids: false,
                 minify_css: DEFAULT_MINIFY_CSS,
+                css: CssSettings::from_mode(mode),
                 allow_local_paths: false,
                 interwiki,
+                embed: EmbedSettings::new(),
+                iframe: IframeSettings::from_mode(mode),
+                variables: HashMap::new(),
             },
             WikitextMode::List => WikitextSettings {
                 mode,
                 layout,
                 enable_page_syntax: true,
                 use_true_ids: false,
+                id_strategy: HtmlIdStrategy::Random,
+                heading_permalinks: false,
                 isolate_user_ids: false,
                 minify_css: DEFAULT_MINIFY_CSS,
+                css: CssSettings::from_mode(mode),
                 allow_local_paths: true,
                 interwiki,
+                embed: EmbedSettings::new(),
+                iframe: IframeSettings::from_mode(mode),
+                variables: HashMap::new(),
             },
         }
     }
@@ -194,4 +280,56 @@ impl WikitextMode {
     pub fn is_nav_content(self) -> bool {
         matches!(self, WikitextMode::PageNav)
     }
+
+    #[inline]
+    pub fn value(self) -> &'static str {
+        match self {
+            WikitextMode::Page => "page",
+            WikitextMode::PageNav => "page-nav",
+            WikitextMode::Draft => "draft",
+            WikitextMode::ForumPost => "forum-post",
+            WikitextMode::DirectMessage => "direct-message",
+            WikitextMode::List => "list",
+        }
+    }
+}
+
+impl FromStr for WikitextMode {
+    type Err = WikitextModeParseError;
+
+    fn from_str(s: &str) -> Result<Self, WikitextModeParseError> {
+        const MODES: [WikitextMode; 6] = [
+            WikitextMode::Page,
+            WikitextMode::PageNav,
+            WikitextMode::Draft,
+            WikitextMode::ForumPost,
+            WikitextMode::DirectMessage,
+            WikitextMode::List,
+        ];
+
+        MODES
+            .into_iter()
+            .find(|mode| mode.value().eq_ignore_ascii_case(s))
+            .ok_or(WikitextModeParseError)
+    }
+}
+
+#[derive(Debug)]
+pub struct WikitextModeParseError;
+
+#[test]
+fn test_mode_parse() {
+    macro_rules! test {
+        ($input:expr, $expected:expr $(,)?) => {{
+            let actual: Option<WikitextMode> = $input.parse().ok();
+            assert_eq!(actual, $expected, "Parsed mode doesn't match expected");
+        }};
+    }
+
+    test!("page", Some(WikitextMode::Page));
+    test!("Page-Nav", Some(WikitextMode::PageNav));
+    test!("forum-post", Some(WikitextMode::ForumPost));
+    test!("DIRECT-MESSAGE", Some(WikitextMode::DirectMessage));
+    test!("forum_post", None);
+    test!("invalid", None);
 }
@@ -0,0 +1,85 @@
+/*
+ * site/backlinks.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::resolver::page_name;
+use crate::data::PageRef;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The pages which link to or include a particular page.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct PageBacklinks {
+    pub linked_from: BTreeSet<String>,
+    pub included_in: BTreeSet<String>,
+}
+
+/// A reverse index of links, mapping each page to the pages referencing it.
+///
+/// Pages on the generated site are keyed by name, such as `scp-001`,
+/// and pages on other sites as `:site:page`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct BacklinkIndex {
+    pages: BTreeMap<String, PageBacklinks>,
+}
+
+impl BacklinkIndex {
+    #[inline]
+    pub fn new() -> Self {
+        BacklinkIndex::default()
+    }
+
+    #[inline]
+    pub fn get(&self, page: &str) -> Option<&PageBacklinks> {
+        self.pages.get(page)
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &PageBacklinks)> {
+        self.pages
+            .iter()
+            .map(|(page, backlinks)| (page.as_str(), backlinks))
+    }
+
+    /// Records that `source` links to each of the given pages.
+    pub fn add_links(&mut self, site: &str, source: &str, links: &[PageRef]) {
+        for link in links {
+            self.entry(site, link).linked_from.insert(str!(source));
+        }
+    }
+
+    /// Records that `source` includes each of the given pages.
+    pub fn add_includes(&mut self, site: &str, source: &str, includes: &[PageRef]) {
+        for include in includes {
+            self.entry(site, include).included_in.insert(str!(source));
+        }
+    }
+
+    fn entry(&mut self, site: &str, page_ref: &PageRef) -> &mut PageBacklinks {
+        let key = match page_ref.site() {
+            Some(other) if other != site => {
+                format!(":{other}:{}", page_name(page_ref.page()))
+            }
+            _ => page_name(page_ref.page()),
+        };
+
+        self.pages.entry(key).or_default()
+    }
+}
@@ -0,0 +1,75 @@
+/*
+ * site/metadata.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::data::{PageInfo, ScoreValue};
+use std::borrow::Cow;
+
+/// Optional metadata for a page, read from `{page}.json` next to its wikitext.
+///
+/// All fields may be omitted. If there is no title, the page name is used.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct PageMetadata {
+    pub title: Option<String>,
+    pub alt_title: Option<String>,
+    pub score: Option<ScoreValue>,
+    pub tags: Vec<String>,
+    pub language: Option<String>,
+}
+
+/// A page found in the input directory.
+#[derive(Debug, Clone)]
+pub struct SitePage {
+    /// The normalized page name, including its category, e.g. `component:thing`.
+    pub name: String,
+    pub metadata: PageMetadata,
+}
+
+impl SitePage {
+    pub fn title(&self) -> &str {
+        self.metadata.title.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn page_info<'a>(&'a self, site: &'a str) -> PageInfo<'a> {
+        let (category, page) = match self.name.split_once(':') {
+            Some((category, page)) => (Some(Cow::Borrowed(category)), page),
+            None => (None, self.name.as_str()),
+        };
+
+        PageInfo {
+            page: Cow::Borrowed(page),
+            category,
+            site: Cow::Borrowed(site),
+            title: Cow::Borrowed(self.title()),
+            alt_title: self.metadata.alt_title.as_deref().map(Cow::Borrowed),
+            score: self.metadata.score.unwrap_or(ScoreValue::Integer(0)),
+            tags: self
+                .metadata
+                .tags
+                .iter()
+                .map(|tag| Cow::Borrowed(tag.as_str()))
+                .collect(),
+            language: Cow::Borrowed(
+                self.metadata.language.as_deref().unwrap_or("default"),
+            ),
+            render_timestamp: None,
+        }
+    }
+}
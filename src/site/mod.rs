@@ -0,0 +1,470 @@
+/*
+ * site/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Static site generation, rendering a directory of pages into linked HTML files.
+//!
+//! This is intended for publishing read-only archives of wikis. The input
+//! directory uses the same layout as [`DirectoryIncluder`], with one
+//! subdirectory per site:
+//!
+//! * `{input}/{site}/{page}.ftml` &mdash; The wikitext for each page, such as `scp-001.ftml` or `component:thing.ftml`.
+//! * `{input}/{site}/{page}.json` &mdash; Optional metadata for that page, see [`PageMetadata`].
+//! * `{input}/{site}/files/{page}/` &mdash; Optional files attached to that page.
+//!
+//! Pages on other sites in the input directory may be included, but only
+//! the pages of the chosen site are rendered. The output directory contains:
+//!
+//! * `{output}/{page}.html` &mdash; A standalone HTML document for each page, see [`HtmlDocumentRender`].
+//! * `{output}/local--files/{page}/` &mdash; The files attached to each page.
+//! * `{output}/backlinks.json` &mdash; The [`BacklinkIndex`] for the site.
+//!
+//! Links between pages point to their generated files by relative path, links
+//! to pages without wikitext are rendered as missing, and page title links use
+//! the titles from the metadata.
+
+mod backlinks;
+mod metadata;
+mod resolver;
+
+pub use self::backlinks::{BacklinkIndex, PageBacklinks};
+pub use self::metadata::{PageMetadata, SitePage};
+pub use self::resolver::SiteResolver;
+
+use crate::data::PageRef;
+use crate::includes::DirectoryIncluder;
+use crate::parsing::ParseError;
+use crate::render::html::{HtmlDocumentRender, HtmlRender};
+use crate::settings::WikitextSettings;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use wikidot_normalize::normalize;
+
+/// Generates a static HTML site from a directory of pages.
+#[derive(Debug, Clone)]
+pub struct SiteGenerator {
+    input: PathBuf,
+    output: PathBuf,
+    site: String,
+    settings: WikitextSettings,
+}
+
+/// The result of generating a site.
+#[derive(Debug, Clone)]
+pub struct SiteOutput {
+    pub pages: Vec<GeneratedPage>,
+    pub files: usize,
+    pub backlinks: BacklinkIndex,
+}
+
+/// A page which was rendered while generating a site.
+#[derive(Debug, Clone)]
+pub struct GeneratedPage {
+    pub name: String,
+    pub path: PathBuf,
+
+    /// Warnings from parsing the page, with spans in its source.
+    ///
+    /// Warnings within text from elsewhere, such as its template or
+    /// an included page, are left out.
+    pub errors: Vec<ParseError>,
+}
+
+impl SiteGenerator {
+    pub fn new(
+        input: PathBuf,
+        output: PathBuf,
+        mut site: String,
+        settings: WikitextSettings,
+    ) -> Self {
+        normalize(&mut site);
+
+        SiteGenerator {
+            input,
+            output,
+            site,
+            settings,
+        }
+    }
+
+    #[inline]
+    pub fn site(&self) -> &str {
+        &self.site
+    }
+
+    #[inline]
+    pub fn site_directory(&self) -> PathBuf {
+        self.input.join(&self.site)
+    }
+
+    #[inline]
+    pub fn output_directory(&self) -> &Path {
+        &self.output
+    }
+
+    /// Renders every page on the site, writing the output directory.
+    pub fn generate(&self) -> io::Result<SiteOutput> {
+        info!(
+            "Generating static site for {} ({} -> {})",
+            self.site,
+            self.input.display(),
+            self.output.display(),
+        );
+
+        let pages = self.load_pages()?;
+        let mut resolver = SiteResolver::new(&self.site, &pages);
+        for (page, files) in self.list_files()? {
+            resolver.set_files(&page, files);
+        }
+
+        let mut includer = DirectoryIncluder::new(self.input.clone(), self.site.clone());
+        let mut backlinks = BacklinkIndex::new();
+        let mut generated = Vec::with_capacity(pages.len());
+
+        fs::create_dir_all(&self.output)?;
+
+        for page in &pages {
+            let generated_page =
+                self.generate_page(page, &resolver, &mut includer, &mut backlinks)?;
+
+            generated.push(generated_page);
+        }
+
+        let files = self.copy_files()?;
+        let index = serde_json::to_string_pretty(&backlinks).map_err(io::Error::other)?;
+        fs::write(self.output.join("backlinks.json"), index)?;
+
+        Ok(SiteOutput {
+            pages: generated,
+            files,
+            backlinks,
+        })
+    }
+
+    /// Finds all the pages on the site, and their metadata.
+    pub fn load_pages(&self) -> io::Result<Vec<SitePage>> {
+        let directory = self.site_directory();
+        let mut pages = Vec::new();
+
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            if !path.is_file() || path.extension().is_none_or(|ext| ext != "ftml") {
+                continue;
+            }
+
+            let name = match path.file_stem().and_then(|stem| stem.to_str()) {
+                Some(name) => str!(name),
+                None => {
+                    warn!("Skipping page with invalid name: {}", path.display());
+                    continue;
+                }
+            };
+
+            let metadata_path = path.with_extension("json");
+            let metadata = match fs::read_to_string(&metadata_path) {
+                Ok(json) => serde_json::from_str(&json).map_err(|error| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "invalid metadata in {}: {error}",
+                            metadata_path.display()
+                        ),
+                    )
+                })?,
+                Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                    PageMetadata::default()
+                }
+                Err(error) => return Err(error),
+            };
+
+            pages.push(SitePage { name, metadata });
+        }
+
+        pages.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(pages)
+    }
+
+    fn generate_page(
+        &self,
+        page: &SitePage,
+        resolver: &SiteResolver,
+        includer: &mut DirectoryIncluder,
+        backlinks: &mut BacklinkIndex,
+    ) -> io::Result<GeneratedPage> {
+        debug!("Generating page {}", page.name);
+
+        let info = page.page_info(&self.site);
+        let source_path = self.site_directory().join(format!("{}.ftml", page.name));
+        let mut input = fs::read_to_string(&source_path)?;
+
+        // Wrap the page in its category's template, if there is one
+        let template = match template_name(&page.name) {
+            Some(name) => includer.read(&PageRef::page_only(name))?.map(String::from),
+            None => None,
+        };
+
+        let mut template_map = None;
+        if let Some(template) = template {
+            let output = crate::apply_template(&template, &input, &info, &self.settings);
+            input = output.text;
+            template_map = Some(output.source_map);
+        }
+
+        let (mut text, included_pages, include_map) =
+            crate::includes::include_with_map(&input, &self.settings, includer, || {
+                io::Error::other("includer returned mismatched pages")
+            })?;
+
+        // Keep track of where the parsed text came from, to map warnings back
+        let mut source_map =
+            crate::preproc::preprocess_with_map(&mut text).compose(&include_map);
+        if let Some(template_map) = template_map {
+            source_map = source_map.compose(&template_map);
+        }
+
+        let tokens = crate::tokenize(&text);
+        let (tree, errors) = crate::parse(&tokens, &info, &self.settings).into();
+        let errors = errors
+            .iter()
+            .filter_map(|error| source_map.map_error(error))
+            .collect();
+        let output =
+            HtmlRender.render_with_resolver(&tree, &info, &self.settings, resolver, None);
+
+        backlinks.add_links(&self.site, &page.name, &output.backlinks.internal_links);
+        backlinks.add_includes(&self.site, &page.name, &included_pages);
+
+        let path = self.output.join(format!("{}.html", page.name));
+        fs::write(&path, HtmlDocumentRender::STANDALONE.wrap(&output, &info))?;
+
+        Ok(GeneratedPage {
+            name: page.name.clone(),
+            path,
+            errors,
+        })
+    }
+
+    /// Lists the files attached to each page, sorted by name.
+    fn list_files(&self) -> io::Result<Vec<(String, Vec<String>)>> {
+        let files_directory = self.site_directory().join("files");
+        if !files_directory.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut pages = Vec::new();
+        for page_entry in fs::read_dir(files_directory)? {
+            let page_entry = page_entry?;
+            if !page_entry.file_type()?.is_dir() {
+                continue;
+            }
+
+            let mut files = Vec::new();
+            for file_entry in fs::read_dir(page_entry.path())? {
+                let file_entry = file_entry?;
+                if file_entry.file_type()?.is_file() {
+                    files.push(file_entry.file_name().to_string_lossy().into_owned());
+                }
+            }
+
+            files.sort();
+            let page = page_entry.file_name().to_string_lossy().into_owned();
+            pages.push((page, files));
+        }
+
+        Ok(pages)
+    }
+
+    /// Copies attached files to the output directory, returning how many there were.
+    fn copy_files(&self) -> io::Result<usize> {
+        let files_directory = self.site_directory().join("files");
+        if !files_directory.is_dir() {
+            return Ok(0);
+        }
+
+        let mut count = 0;
+        for page_entry in fs::read_dir(files_directory)? {
+            let page_entry = page_entry?;
+            if !page_entry.file_type()?.is_dir() {
+                continue;
+            }
+
+            let output_directory = self
+                .output
+                .join("local--files")
+                .join(page_entry.file_name());
+
+            fs::create_dir_all(&output_directory)?;
+
+            for file_entry in fs::read_dir(page_entry.path())? {
+                let file_entry = file_entry?;
+                if file_entry.file_type()?.is_file() {
+                    fs::copy(
+                        file_entry.path(),
+                        output_directory.join(file_entry.file_name()),
+                    )?;
+
+                    count += 1;
+                }
+            }
+        }
+
+        Ok(count)
+    }
+}
+
+/// Gets the name of the template page for the given page's category.
+///
+/// Returns `None` for template pages themselves, which are rendered as-is.
+fn template_name(page: &str) -> Option<String> {
+    match page.split_once(':') {
+        Some((_, "_template")) => None,
+        Some((category, _)) => Some(format!("{category}:_template")),
+        None if page == "_template" => None,
+        None => Some(str!("_template")),
+    }
+}
+
+#[test]
+fn site_generator() {
+    use crate::layout::Layout;
+    use crate::settings::WikitextMode;
+    use crate::test::temp_dir::TempDir;
+
+    let temp_dir = TempDir::new("site");
+    let directory = temp_dir.path();
+    let input = directory.join("input");
+    let output = directory.join("output");
+    let site_directory = input.join("test");
+
+    fs::create_dir_all(site_directory.join("files/scp-001")).unwrap();
+    fs::write(
+        site_directory.join("scp-001.ftml"),
+        "[[include component:notice]]\n[[[component:notice]]] [[[scp-002]]] [[[scp-001|]]] [[image photo.png]]\n\n[[gallery]]",
+    )
+    .unwrap();
+    fs::write(
+        site_directory.join("scp-001.json"),
+        r#"{"title": "SCP-001 <Gate>"}"#,
+    )
+    .unwrap();
+    fs::write(site_directory.join("component:notice.ftml"), "Notice").unwrap();
+    fs::write(
+        site_directory.join("component:_template.ftml"),
+        "**%%content%%**",
+    )
+    .unwrap();
+    fs::write(site_directory.join("files/scp-001/photo.png"), "PNG").unwrap();
+
+    // A page in a category, with its own attachment
+    fs::create_dir_all(site_directory.join("files/fragment:info")).unwrap();
+    fs::write(
+        site_directory.join("fragment:info.ftml"),
+        "[[image icon.png]]\r\n\r\n[[gallery]]\r\n\r\n[[span]]",
+    )
+    .unwrap();
+    fs::write(site_directory.join("files/fragment:info/icon.png"), "ICON").unwrap();
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump);
+    let generator = SiteGenerator::new(input, output.clone(), str!("Test"), settings);
+    let result = generator.generate().expect("Unable to generate site");
+
+    let names = result
+        .pages
+        .iter()
+        .map(|page| page.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "component:_template",
+            "component:notice",
+            "fragment:info",
+            "scp-001",
+        ]
+    );
+    assert_eq!(result.files, 2);
+
+    let html = fs::read_to_string(output.join("scp-001.html")).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>SCP-001 &lt;Gate&gt;</title>"));
+    assert!(
+        html.contains("<p>Notice<br>"),
+        "Included page not found in {html}"
+    );
+    assert!(html.contains(r#"href="./component:notice.html""#));
+    assert!(
+        html.contains(r#"wj-link-missing" data-link-type="page" href="./scp-002.html""#)
+    );
+    assert!(html.contains(">SCP-001 &lt;Gate&gt;</a>"));
+    assert!(html.contains(r#"src="./local--files/scp-001/photo.png""#));
+    assert!(
+        html.contains(
+            r#"<a class="wj-gallery-link" href="./local--files/scp-001/photo.png" data-gallery-index="0">"#,
+        ),
+        "Gallery image not found in {html}",
+    );
+    assert!(
+        fs::read_to_string(output.join("component:notice.html"))
+            .unwrap()
+            .contains("<strong>Notice</strong>"),
+    );
+    assert_eq!(
+        fs::read_to_string(output.join("local--files/scp-001/photo.png")).unwrap(),
+        "PNG",
+    );
+
+    // Warnings are mapped back to the page source
+    let source = fs::read_to_string(site_directory.join("fragment:info.ftml")).unwrap();
+    let errors = &result.pages[2].errors;
+    assert!(!errors.is_empty(), "No warnings produced");
+    let spans = errors
+        .iter()
+        .map(|error| &source[error.span()])
+        .collect::<Vec<_>>();
+    assert_eq!(spans, ["", "[[", "]]"]);
+
+    let start = source.find("[[span]]").unwrap();
+    assert_eq!(errors[1].span(), start..start + 2);
+
+    let html = fs::read_to_string(output.join("fragment:info.html")).unwrap();
+    assert!(
+        html.contains(r#"src="./local--files/fragment:info/icon.png""#),
+        "Category page image not found in {html}",
+    );
+    assert!(
+        html.contains(
+            r#"<a class="wj-gallery-link" href="./local--files/fragment:info/icon.png" data-gallery-index="0">"#,
+        ),
+        "Category page gallery image not found in {html}",
+    );
+
+    let notice = result.backlinks.get("component:notice").unwrap();
+    assert!(notice.linked_from.contains("scp-001"));
+    assert!(notice.included_in.contains("scp-001"));
+    assert!(
+        result
+            .backlinks
+            .get("scp-002")
+            .unwrap()
+            .linked_from
+            .contains("scp-001")
+    );
+    assert!(output.join("backlinks.json").is_file());
+}
@@ -0,0 +1,182 @@
+/*
+ * site/resolver.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::metadata::SitePage;
+use crate::render::PageResolver;
+use std::collections::HashMap;
+use std::fmt::Write;
+use wikidot_normalize::normalize;
+
+/// Resolves links between the pages of a generated site.
+///
+/// Pages on this site link to their generated files by relative path.
+/// Pages on other sites are assumed to exist, and keep their usual URLs.
+#[derive(Debug)]
+pub struct SiteResolver {
+    site: String,
+    titles: HashMap<String, String>,
+    files: HashMap<String, Vec<String>>,
+}
+
+impl SiteResolver {
+    pub fn new(site: &str, pages: &[SitePage]) -> Self {
+        let titles = pages
+            .iter()
+            .map(|page| (page.name.clone(), str!(page.title())))
+            .collect();
+
+        SiteResolver {
+            site: str!(site),
+            titles,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Sets the list of files attached to a page.
+    pub fn set_files(&mut self, page: &str, files: Vec<String>) {
+        self.files.insert(page_name(page), files);
+    }
+
+    /// Returns the normalized name of a page if it is on this site.
+    fn local_page(&self, site: &str, page: &str) -> Option<String> {
+        if site != self.site {
+            return None;
+        }
+
+        Some(page_name(page))
+    }
+}
+
+impl PageResolver for SiteResolver {
+    fn page_exists(&self, site: &str, page: &str) -> bool {
+        match self.local_page(site, page) {
+            Some(page) => self.titles.contains_key(&page),
+            None => true,
+        }
+    }
+
+    fn page_title(&self, site: &str, page: &str) -> Option<String> {
+        let page = self.local_page(site, page)?;
+        self.titles.get(&page).cloned()
+    }
+
+    fn page_url(&self, site: &str, page: &str, extra: Option<&str>) -> Option<String> {
+        let page = self.local_page(site, page)?;
+
+        // Only anchors can be kept, other extras like "/edit" have no static equivalent
+        let anchor = extra.filter(|extra| extra.starts_with('#')).unwrap_or("");
+
+        // The "./" prefix stops the category from being read as a URL scheme
+        Some(format!("./{page}.html{anchor}"))
+    }
+
+    fn file_url(&self, site: &str, page: &str, file: &str) -> Option<String> {
+        let page = self.local_page(site, page)?;
+        let mut url = format!("./local--files/{page}/");
+        encode_path_segment(&mut url, file);
+        Some(url)
+    }
+
+    fn page_files(&self, site: &str, page: &str) -> Vec<String> {
+        self.local_page(site, page)
+            .and_then(|page| self.files.get(&page))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Normalizes a page name, removing the default category if present.
+pub fn page_name(page: &str) -> String {
+    let mut page = str!(page);
+    normalize(&mut page);
+
+    match page.strip_prefix("_default:") {
+        Some(stripped) => str!(stripped),
+        None => page,
+    }
+}
+
+/// Percent-encodes a string for use as part of a URL path.
+fn encode_path_segment(buffer: &mut String, segment: &str) {
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                buffer.push(char::from(byte));
+            }
+            _ => write!(buffer, "%{byte:02X}").unwrap(),
+        }
+    }
+}
+
+#[test]
+fn site_resolver() {
+    use super::metadata::PageMetadata;
+
+    let pages = [
+        SitePage {
+            name: str!("scp-001"),
+            metadata: PageMetadata {
+                title: Some(str!("SCP-001")),
+                ..Default::default()
+            },
+        },
+        SitePage {
+            name: str!("component:thing"),
+            metadata: PageMetadata::default(),
+        },
+    ];
+    let mut resolver = SiteResolver::new("test", &pages);
+    resolver.set_files("SCP-001", vec![str!("a.png"), str!("b.png")]);
+
+    assert!(resolver.page_exists("test", "scp-001"));
+    assert!(resolver.page_exists("test", "_default:scp-001"));
+    assert!(resolver.page_exists("test", "Component:Thing"));
+    assert!(!resolver.page_exists("test", "scp-002"));
+    assert!(resolver.page_exists("other", "scp-002"));
+
+    assert_eq!(
+        resolver.page_title("test", "scp-001"),
+        Some(str!("SCP-001"))
+    );
+    assert_eq!(
+        resolver.page_title("test", "component:thing"),
+        Some(str!("component:thing")),
+    );
+    assert_eq!(resolver.page_title("other", "scp-001"), None);
+
+    assert_eq!(
+        resolver.page_url("test", "component:thing", Some("#toc0")),
+        Some(str!("./component:thing.html#toc0")),
+    );
+    assert_eq!(
+        resolver.page_url("test", "scp-001", Some("/edit")),
+        Some(str!("./scp-001.html")),
+    );
+    assert_eq!(resolver.page_url("other", "scp-001", None), None);
+
+    assert_eq!(
+        resolver.file_url("test", "scp-001", "my image.png"),
+        Some(str!("./local--files/scp-001/my%20image.png")),
+    );
+
+    assert_eq!(resolver.page_files("test", "scp-001"), ["a.png", "b.png"]);
+    assert!(resolver.page_files("test", "component:thing").is_empty());
+    assert!(resolver.page_files("other", "scp-001").is_empty());
+}
@@ -0,0 +1,343 @@
+/*
+ * templates/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! This module implements Wikidot "live templates".
+//!
+//! A category may contain a `_template` page, which wraps the source of every
+//! other page in that category. Like [`include()`](crate::include), this is a
+//! textual substitution performed before parsing.
+//!
+//! The template may use these variables:
+//! * `%%content%%` &mdash; The entire source of the page.
+//! * `%%content{n}%%` &mdash; The `n`th section of the page, counting from 1.
+//!   Sections are separated by lines consisting of only `====`.
+//! * `%%title%%` &mdash; The title of the page.
+//! * `%%page_unix_name%%` &mdash; The full name of the page, such as `component:thing`.
+//!
+//! Read <https://www.wikidot.com/doc:templates> for more details.
+//!
+//! Categories using data forms instead have a `_template` page starting with a
+//! `[[form]]` block, followed by a `====` line and the template used to display
+//! each page. Pages then only hold the values for the fields in the form, which
+//! are substituted using [`apply_form_template()`].
+
+#[cfg(test)]
+mod test;
+
+mod source_map;
+
+pub use self::source_map::{SourceMap, SourceSegment};
+
+pub(crate) use self::source_map::SourceMapBuilder;
+
+use crate::data::PageInfo;
+use crate::settings::WikitextSettings;
+use crate::tree::{Form, FormFieldType};
+use regex::{Captures, Regex, RegexBuilder};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+static VARIABLE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(concat!(
+        r"%%(?:",
+        r"content(?:\{(?P<section>[0-9]+)\})?|title|page_unix_name|",
+        r"form_(?P<form>data|raw|label)\{(?P<field>[^{}%\s]+)\}",
+        r")%%",
+    ))
+    .unwrap()
+});
+
+/// The values for each field of a data form, as stored for a page.
+pub type FormData<'a> = HashMap<Cow<'a, str>, Cow<'a, str>>;
+
+/// The result of applying a template to a page.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TemplateOutput {
+    /// The composed wikitext, ready to be parsed.
+    pub text: String,
+
+    /// Maps the parts of the composed wikitext which came from the page
+    /// back to their positions in its source.
+    pub source_map: SourceMap,
+}
+
+/// Applies a `_template` page to the source of a page.
+pub fn apply_template(
+    template: &str,
+    page: &str,
+    page_info: &PageInfo,
+    settings: &WikitextSettings,
+) -> TemplateOutput {
+    if !settings.enable_page_syntax {
+        debug!("Templates are disabled for this input, skipping");
+
+        let mut source_map = SourceMap::new();
+        source_map.push(0..page.len(), 0..page.len());
+
+        return TemplateOutput {
+            text: str!(page),
+            source_map,
+        };
+    }
+
+    info!(
+        "Applying template ({} bytes) to page ({} bytes)",
+        template.len(),
+        page.len(),
+    );
+
+    let sections = split_sections(page);
+    let mut text = String::with_capacity(template.len() + page.len());
+    let mut source_map = SourceMap::new();
+    let mut last = 0;
+
+    for capture in VARIABLE_REGEX.captures_iter(template) {
+        let mtch = capture.get(0).unwrap();
+
+        // Copy template text before this variable
+        text.push_str(&template[last..mtch.start()]);
+        last = mtch.end();
+
+        // Substitute page contents, recording where they came from
+        let mut push_source = |range: Range<usize>| {
+            let start = text.len();
+            text.push_str(&page[range.clone()]);
+            source_map.push(start..text.len(), range);
+        };
+
+        match mtch.as_str() {
+            "%%content%%" => push_source(0..page.len()),
+            _ if capture.name("form").is_some() => text.push_str(mtch.as_str()),
+            _ if capture.name("section").is_none() => {
+                push_page_variable(&mut text, &capture, page_info);
+            }
+            _ => {
+                // Sections count from 1, out of range sections are empty
+                let section = capture["section"]
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| index.checked_sub(1))
+                    .and_then(|index| sections.get(index));
+
+                match section {
+                    Some(range) => push_source(Range::clone(range)),
+                    None => {
+                        trace!("Template section {} does not exist", &capture["section"])
+                    }
+                }
+            }
+        }
+    }
+
+    text.push_str(&template[last..]);
+
+    TemplateOutput { text, source_map }
+}
+
+/// Applies a data form `_template` page to the field values of a page.
+///
+/// Field values are formatted according to their type in the form definition.
+/// Wiki fields are inserted as wikitext, while other values are escaped.
+/// Page and date values which would break out of their link or block
+/// are displayed as text instead.
+/// Fields without a value use their default, if any.
+pub fn apply_form_template(
+    template: &str,
+    data: &FormData,
+    page_info: &PageInfo,
+) -> String {
+    static FORM_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+        RegexBuilder::new(r"\[\[\s*form\s*\]\]\n?(?P<definition>.*?)\[\[/\s*form\s*\]\]")
+            .case_insensitive(true)
+            .dot_matches_new_line(true)
+            .build()
+            .unwrap()
+    });
+
+    info!(
+        "Applying form template ({} bytes) to page ({} fields)",
+        template.len(),
+        data.len(),
+    );
+
+    // Separate form definition from the display template.
+    //
+    // Malformed forms are reported when parsing the template page,
+    // here all values are treated as text fields.
+    let (form, template) = match FORM_REGEX.captures(template) {
+        Some(capture) => {
+            let form = Form::parse(capture.name("definition").unwrap().as_str())
+                .unwrap_or_default();
+
+            let rest = &template[capture.get(0).unwrap().end()..];
+            let sections = split_sections(rest);
+            let display = match sections.get(1) {
+                Some(range) => &rest[range.start..],
+                None => rest,
+            };
+
+            (form, display)
+        }
+        None => {
+            warn!("No form definition found in form template");
+            (Form::default(), template)
+        }
+    };
+
+    let mut text = String::with_capacity(template.len());
+    let mut last = 0;
+
+    for capture in VARIABLE_REGEX.captures_iter(template) {
+        let mtch = capture.get(0).unwrap();
+        text.push_str(&template[last..mtch.start()]);
+        last = mtch.end();
+
+        let (kind, name) = match (capture.name("form"), capture.name("field")) {
+            (Some(kind), Some(name)) => (kind.as_str(), name.as_str()),
+            _ if capture.name("section").is_none() && mtch.as_str() != "%%content%%" => {
+                push_page_variable(&mut text, &capture, page_info);
+                continue;
+            }
+            _ => {
+                // Pages using forms do not have any content
+                trace!("Ignoring content variable in form template");
+                continue;
+            }
+        };
+
+        let field = form.get(name);
+        let value = match (data.get(name), field) {
+            (Some(value), _) => value,
+            (None, Some(field)) => field.default.as_deref().unwrap_or(""),
+            (None, None) => "",
+        };
+
+        match (kind, field) {
+            ("label", Some(field)) => push_escaped(&mut text, &field.label),
+            ("label", None) => push_escaped(&mut text, name),
+            ("data", Some(field)) => push_form_value(&mut text, &field.field_type, value),
+            _ => push_escaped(&mut text, value),
+        }
+    }
+
+    text.push_str(&template[last..]);
+    text
+}
+
+/// Substitutes `%%title%%` or `%%page_unix_name%%`.
+fn push_page_variable(text: &mut String, capture: &Captures, page_info: &PageInfo) {
+    match &capture[0] {
+        "%%title%%" => text.push_str(&page_info.title),
+        "%%page_unix_name%%" => match &page_info.category {
+            Some(category) => str_write!(text, "{category}:{}", page_info.page),
+            None => text.push_str(&page_info.page),
+        },
+        _ => unreachable!("Not a page variable: {}", &capture[0]),
+    }
+}
+
+/// Formats a field value as wikitext, according to its type.
+fn push_form_value(text: &mut String, field_type: &FormFieldType, value: &str) {
+    if value.is_empty() {
+        return;
+    }
+
+    match field_type {
+        FormFieldType::Wiki => text.push_str(value),
+        FormFieldType::Text => push_escaped(text, value),
+        FormFieldType::Select { options } => {
+            let label = options
+                .iter()
+                .find(|option| option.value == value)
+                .map(|option| option.label.as_ref())
+                .unwrap_or(value);
+
+            push_escaped(text, label);
+        }
+        FormFieldType::Page { .. } | FormFieldType::Date if !is_plain_argument(value) => {
+            // Inserting this would change the syntax around it, so show it as text
+            push_escaped(text, value);
+        }
+        FormFieldType::Page { category } => match category {
+            Some(category) if !value.contains(':') => {
+                str_write!(text, "[[[{category}:{value}]]]");
+            }
+            _ => str_write!(text, "[[[{value}]]]"),
+        },
+        FormFieldType::Date => str_write!(text, "[[date {value}]]"),
+    }
+}
+
+/// Checks if a value can be placed within a link or block without ending it early.
+fn is_plain_argument(value: &str) -> bool {
+    !value.contains(['[', ']', '|', '\n'])
+}
+
+/// Escapes a value so it is displayed as-is, rather than parsed as wikitext.
+///
+/// All wikitext syntax begins with ASCII punctuation, so each of those characters
+/// is written as a `[[char]]` block. Raw text (`@@`) is not used, since the lexer
+/// may join its delimiters with the text around them, such as into an email address.
+fn push_escaped(text: &mut String, value: &str) {
+    for (index, line) in value.lines().enumerate() {
+        if index > 0 {
+            text.push('\n');
+        }
+
+        for ch in line.chars() {
+            if ch.is_ascii_punctuation() {
+                str_write!(text, "[[char #{}]]", u32::from(ch));
+            } else {
+                text.push(ch);
+            }
+        }
+    }
+}
+
+/// Splits a page into sections separated by `====` lines, returning their byte ranges.
+fn split_sections(page: &str) -> Vec<Range<usize>> {
+    let mut sections = Vec::new();
+    let mut section_start = 0;
+    let mut line_start = 0;
+
+    for line in page.split_inclusive('\n') {
+        let line_end = line_start + line.len();
+
+        if line.trim_end() == "====" {
+            // The newline before the separator is not part of the section
+            let section_end = if line_start > section_start {
+                line_start - 1
+            } else {
+                line_start
+            };
+
+            sections.push(section_start..section_end);
+            section_start = line_end;
+        }
+
+        line_start = line_end;
+    }
+
+    sections.push(section_start..page.len());
+    sections
+}
@@ -0,0 +1,231 @@
+/*
+ * templates/source_map.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use crate::parsing::ParseError;
+use std::ops::Range;
+
+/// A part of the composed wikitext which was copied from the page source.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct SourceSegment {
+    /// The byte range in the composed wikitext.
+    pub output: Range<usize>,
+
+    /// The byte range in the page source.
+    pub source: Range<usize>,
+}
+
+/// Maps byte ranges in composed wikitext back to the page source.
+///
+/// Any text not covered by a segment came from the template itself.
+///
+/// Maps are also produced by [`include_with_map()`](crate::includes::include_with_map)
+/// and [`preprocess_with_map()`](crate::preproc::preprocess_with_map), in which case
+/// uncovered text was inserted or replaced by those steps. Maps for consecutive
+/// steps are joined with [`compose()`](Self::compose).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct SourceMap {
+    segments: Vec<SourceSegment>,
+}
+
+impl SourceMap {
+    #[inline]
+    pub fn new() -> Self {
+        SourceMap::default()
+    }
+
+    #[inline]
+    pub fn segments(&self) -> &[SourceSegment] {
+        &self.segments
+    }
+
+    pub(crate) fn push(&mut self, output: Range<usize>, source: Range<usize>) {
+        debug_assert_eq!(output.len(), source.len(), "Segment lengths differ");
+        self.segments.push(SourceSegment { output, source });
+    }
+
+    /// Maps a span in the composed wikitext to the page source.
+    ///
+    /// Returns `None` if the span is not entirely within text from the page.
+    pub fn get_source_span(&self, span: Range<usize>) -> Option<Range<usize>> {
+        self.segments.iter().find_map(|segment| {
+            let output = &segment.output;
+            if span.start < output.start || span.end > output.end {
+                return None;
+            }
+
+            let start = segment.source.start + (span.start - output.start);
+            let end = segment.source.start + (span.end - output.start);
+            Some(start..end)
+        })
+    }
+
+    /// Maps a position in the composed wikitext to the page source.
+    ///
+    /// Positions within or at the edge of copied text are mapped exactly.
+    /// Other positions map to the end of the copied text before them,
+    /// that is, to where the inserted text begins in the source.
+    pub fn get_source_position(&self, position: usize) -> usize {
+        let index = self
+            .segments
+            .partition_point(|segment| segment.output.start <= position);
+
+        match index.checked_sub(1).map(|index| &self.segments[index]) {
+            Some(segment) if position <= segment.output.end => {
+                segment.source.start + (position - segment.output.start)
+            }
+            Some(segment) => segment.source.end,
+            None => 0,
+        }
+    }
+
+    /// Joins this map with the map for the step which produced its source.
+    ///
+    /// That is, if `self` maps from text C to text B, and `previous`
+    /// maps from text B to text A, the result maps from text C to text A.
+    pub fn compose(&self, previous: &SourceMap) -> SourceMap {
+        let mut map = SourceMap::new();
+
+        for segment in &self.segments {
+            let source = &segment.source;
+
+            // Empty segments mark positions, such as the end of the text
+            if source.is_empty() {
+                let position = segment.output.start;
+                let mapped = previous.get_source_position(source.start);
+                map.push(position..position, mapped..mapped);
+                continue;
+            }
+
+            // Otherwise, copy each part of the segment which was also copied before,
+            // and mark where each part which was inserted before begins.
+            let first = previous
+                .segments
+                .partition_point(|previous| previous.output.end <= source.start);
+
+            let mut position = source.start;
+            let mark = |map: &mut SourceMap, position: usize| {
+                let output = segment.output.start + (position - source.start);
+                let mapped = previous.get_source_position(position);
+                map.push(output..output, mapped..mapped);
+            };
+
+            for previous in &previous.segments[first..] {
+                if previous.output.start >= source.end {
+                    break;
+                }
+
+                let start = source.start.max(previous.output.start);
+                let end = source.end.min(previous.output.end);
+                if start == end {
+                    continue;
+                }
+
+                if start > position {
+                    mark(&mut map, position);
+                }
+
+                let output = segment.output.start + (start - source.start);
+                let mapped = previous.source.start + (start - previous.output.start);
+                map.push(
+                    output..output + (end - start),
+                    mapped..mapped + (end - start),
+                );
+                position = end;
+            }
+
+            if position < source.end {
+                mark(&mut map, position);
+            }
+        }
+
+        // Keep the end of the text mapped, even if the segment at the end
+        // of the previous map was not copied.
+        if let Some(last) = self.segments.last()
+            && !last.source.is_empty()
+        {
+            let position = last.output.end;
+            let mapped = previous.get_source_position(last.source.end);
+            map.push(position..position, mapped..mapped);
+        }
+
+        map
+    }
+
+    /// Maps the span of a parser warning to the page source.
+    ///
+    /// Returns `None` if the warning is not entirely within text from the page.
+    pub fn map_error(&self, error: &ParseError) -> Option<ParseError> {
+        let span = self.get_source_span(error.span())?;
+        Some(error.with_span(span))
+    }
+}
+
+/// Builds a [`SourceMap`] from a series of replacements made to some text.
+///
+/// Replacements must be recorded in order, none of them overlapping.
+#[derive(Debug, Default)]
+pub(crate) struct SourceMapBuilder {
+    map: SourceMap,
+    output: usize,
+    source: usize,
+}
+
+impl SourceMapBuilder {
+    #[inline]
+    pub fn new() -> Self {
+        SourceMapBuilder::default()
+    }
+
+    /// Converts a position in the text, with all replacements so far applied, to the original.
+    ///
+    /// The position must be after the last replacement.
+    #[inline]
+    pub fn source_position(&self, position: usize) -> usize {
+        debug_assert!(position >= self.output, "Position before last replacement");
+        self.source + (position - self.output)
+    }
+
+    /// Records that the given range of the original text was replaced by `length` bytes.
+    pub fn replace(&mut self, source: Range<usize>, length: usize) {
+        debug_assert!(source.start >= self.source, "Replacements out of order");
+
+        let copied = source.start - self.source;
+        if copied > 0 {
+            self.map
+                .push(self.output..self.output + copied, self.source..source.start);
+        }
+
+        self.output += copied + length;
+        self.source = source.end;
+    }
+
+    /// Finishes the map, given the length of the original text.
+    ///
+    /// The map always ends with a segment at the end of both texts,
+    /// so that the end of the output maps to the end of the source.
+    pub fn finish(mut self, source_len: usize) -> SourceMap {
+        let copied = source_len - self.source;
+        self.map
+            .push(self.output..self.output + copied, self.source..source_len);
+        self.map
+    }
+}
@@ -0,0 +1,280 @@
+/*
+ * templates/test.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{FormData, apply_form_template, apply_template, split_sections};
+use crate::data::PageInfo;
+use crate::layout::Layout;
+use crate::render::{Render, text::TextRender};
+use crate::settings::{WikitextMode, WikitextSettings};
+use std::borrow::Cow;
+
+#[test]
+fn sections() {
+    macro_rules! test {
+        ($page:expr, $expected:expr $(,)?) => {{
+            let page = $page;
+            let actual = split_sections(page)
+                .into_iter()
+                .map(|range| &page[range])
+                .collect::<Vec<_>>();
+
+            let expected: &[&str] = &$expected;
+            assert_eq!(actual, expected, "Sections for {page:?} do not match");
+        }};
+    }
+
+    test!("", [""]);
+    test!("apple", ["apple"]);
+    test!("apple\n====\nbanana", ["apple", "banana"]);
+    test!(
+        "apple\n====  \nbanana\n====\ncherry\n",
+        ["apple", "banana", "cherry\n"]
+    );
+    test!("====\napple", ["", "apple"]);
+    test!("apple\n=====\nbanana", ["apple\n=====\nbanana"]);
+}
+
+#[test]
+fn templates() {
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+    let mut page_info = PageInfo::dummy();
+    page_info.title = Cow::Borrowed("Some Thing");
+    page_info.category = Some(Cow::Borrowed("component"));
+    page_info.page = Cow::Borrowed("thing");
+
+    macro_rules! test {
+        ($template:expr, $page:expr, $expected:expr $(,)?) => {{
+            let output = apply_template($template, $page, &page_info, &settings);
+            assert_eq!(output.text, $expected, "Composed text does not match");
+
+            // Every mapped segment must contain the same text as the source
+            for segment in output.source_map.segments() {
+                assert_eq!(
+                    &output.text[segment.output.clone()],
+                    &$page[segment.source.clone()],
+                    "Source map segment does not match page source",
+                );
+            }
+
+            output
+        }};
+    }
+
+    test!("no variables", "apple", "no variables");
+    test!(
+        "[[div]]\n%%content%%\n[[/div]]",
+        "apple",
+        "[[div]]\napple\n[[/div]]"
+    );
+    test!(
+        "%%title%% (%%page_unix_name%%)",
+        "",
+        "Some Thing (component:thing)"
+    );
+    test!(
+        "A: %%content{2}%%, B: %%content{1}%%, C: %%content{3}%%, D: %%content{0}%%",
+        "apple\n====\nbanana",
+        "A: banana, B: apple, C: , D: ",
+    );
+    test!(
+        "%%content{x}%% %%unknown%%",
+        "apple",
+        "%%content{x}%% %%unknown%%"
+    );
+    test!(
+        "%%form_data{class}%% %%content%%",
+        "apple",
+        "%%form_data{class}%% apple"
+    );
+
+    let output = test!(
+        "**%%title%%**\n%%content{1}%%\n----\n%%content{2}%%",
+        "apple\n====\nbanana [[span]]",
+        "**Some Thing**\napple\n----\nbanana [[span]]",
+    );
+
+    // Spans map back to the page only if they are entirely within it
+    assert_eq!(output.source_map.get_source_span(15..20), Some(0..5));
+    assert_eq!(output.source_map.get_source_span(33..41), Some(18..26));
+    assert_eq!(output.source_map.get_source_span(2..12), None);
+    assert_eq!(output.source_map.get_source_span(18..28), None);
+
+    // Check that warnings are mapped
+    let tokens = crate::tokenize(&output.text);
+    let (_, errors) = crate::parse(&tokens, &page_info, &settings).into();
+    assert!(!errors.is_empty(), "No warnings produced");
+
+    for error in errors {
+        let span = error.span();
+        let mapped = output
+            .source_map
+            .map_error(&error)
+            .expect("Warning not within page");
+        let page_span = mapped.span();
+        assert_eq!(mapped.kind(), error.kind());
+        assert_eq!(
+            &output.text[span],
+            &"apple\n====\nbanana [[span]]"[page_span],
+        );
+    }
+
+    // Templates are not applied when page syntax is disabled
+    let settings = WikitextSettings::from_mode(WikitextMode::ForumPost, Layout::Wikidot);
+    let output = apply_template(
+        "[[div]]\n%%content%%\n[[/div]]",
+        "apple",
+        &page_info,
+        &settings,
+    );
+    assert_eq!(output.text, "apple");
+    assert_eq!(output.source_map.get_source_span(1..3), Some(1..3));
+}
+
+#[test]
+fn form_templates() {
+    let mut page_info = PageInfo::dummy();
+    page_info.title = Cow::Borrowed("SCP-173");
+
+    let template = "\
+[[form]]
+fields:
+  class:
+    label: Object Class
+    type: select
+    values:
+      safe: Safe
+      euclid: Euclid
+    default: safe
+  item:
+    label: Item #
+  author:
+    type: page
+    category: user
+  created:
+    type: date
+  description:
+    type: wiki
+[[/form]]
+====
++ %%title%%
+**%%form_label{item}%%:** %%form_data{item}%%
+**%%form_label{class}%%:** %%form_data{class}%% (%%form_raw{class}%%)
+By %%form_data{author}%% on %%form_data{created}%%
+%%form_data{description}%%
+%%form_data{unknown}%%%%content%%";
+
+    macro_rules! test {
+        ($data:expr, $expected:expr $(,)?) => {{
+            let data: FormData = $data
+                .into_iter()
+                .map(|(key, value)| (Cow::Borrowed(key), Cow::Borrowed(value)))
+                .collect();
+
+            let output = apply_form_template(template, &data, &page_info);
+            assert_eq!(output, $expected, "Form template output does not match");
+        }};
+    }
+
+    test!(
+        [
+            ("class", "euclid"),
+            ("item", "SCP-173"),
+            ("author", "moto42"),
+            ("created", "1214092800"),
+            (
+                "description",
+                "**Moved** to [[[site-19]]].\n\nIt is //hostile//."
+            ),
+        ],
+        "\
++ SCP-173
+**Item [[char #35]]:** SCP[[char #45]]173
+**Object Class:** Euclid (euclid)
+By [[[user:moto42]]] on [[date 1214092800]]
+**Moved** to [[[site-19]]].
+
+It is //hostile//.
+",
+    );
+
+    test!(
+        [
+            ("item", "@@ [[span]] **not bold**"),
+            ("author", "system:admin"),
+            ("unknown", "value"),
+        ],
+        "\
++ SCP-173
+**Item [[char #35]]:** [[char #64]][[char #64]] [[char #91]][[char #91]]span[[char #93]][[char #93]] [[char #42]][[char #42]]not bold[[char #42]][[char #42]]
+**Object Class:** Safe (safe)
+By [[[system:admin]]] on 
+
+value",
+    );
+
+    // Page and date values cannot end their link or block early
+    test!(
+        [
+            ("author", "x]]] **bold** [[[y"),
+            ("created", "1 | 2]] [[span"),
+        ],
+        "\
++ SCP-173
+**Item [[char #35]]:** 
+**Object Class:** Safe (safe)
+By x[[char #93]][[char #93]][[char #93]] [[char #42]][[char #42]]bold[[char #42]][[char #42]] [[char #91]][[char #91]][[char #91]]y on 1 [[char #124]] 2[[char #93]][[char #93]] [[char #91]][[char #91]]span
+
+",
+    );
+
+    // Values containing wikitext syntax are shown as text, even next to other text
+    for value in [
+        r#"a >@ **bold** [[span style="color:red"]]x[[/span]] @@"#,
+        "@@ **not bold** y.z",
+        "[[include other-page]]",
+        "{$variable} -- \"quoted\"",
+        "+ Not a heading",
+        "@@x@@ >@ @<y>@",
+    ] {
+        let data = [(Cow::Borrowed("item"), Cow::Borrowed(value))]
+            .into_iter()
+            .collect();
+
+        let mut wikitext =
+            apply_form_template("Item:%%form_data{item}%%.com", &data, &page_info);
+
+        crate::preprocess(&mut wikitext);
+        let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikidot);
+        let tokens = crate::tokenize(&wikitext);
+        let (tree, _errors) = crate::parse(&tokens, &page_info, &settings).into();
+        let output = TextRender.render(&tree, &page_info, &settings);
+        assert_eq!(
+            output.trim_end(),
+            format!("Item:{value}.com"),
+            "Form value {value:?} was not rendered as text (wikitext {wikitext:?})",
+        );
+    }
+
+    // Templates without forms still substitute values as text
+    let data = FormData::new();
+    let output =
+        apply_form_template("%%form_data{a}%%/%%form_label{a}%%", &data, &page_info);
+    assert_eq!(output, "/a");
+}
@@ -257,11 +257,14 @@ impl Test {
                     SyntaxTree {
                         elements: Vec::new(),
                         table_of_contents: Vec::new(),
+                        headings: Vec::new(),
                         html_blocks: Vec::new(),
                         code_blocks: Vec::new(),
                         footnotes: Vec::new(),
+                        footnote_refs: Vec::new(),
                         needs_footnote_block: true,
                         bibliographies: BibliographyList::new(),
+                        form: None,
                         wikitext_len: 0,
                     }
                 }
@@ -98,6 +98,7 @@ impl Test {
                 Some(locale) => cow!(locale),
                 None => cow!("default"),
             },
+            render_timestamp: None,
         }
     }
 
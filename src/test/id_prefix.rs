@@ -20,11 +20,15 @@
 
 use crate::data::{PageInfo, ScoreValue};
 use crate::layout::Layout;
-use crate::settings::{EMPTY_INTERWIKI, WikitextMode, WikitextSettings};
+use crate::settings::{
+    CssSettings, EMPTY_INTERWIKI, EmbedSettings, HtmlIdStrategy, IframeSettings,
+    WikitextMode, WikitextSettings,
+};
 use crate::tree::{
     AttributeMap, Container, ContainerType, Element, FileSource, ListItem, ListType,
 };
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 #[test]
 fn isolate_user_ids() {
@@ -49,6 +53,7 @@ fn isolate_user_ids() {
         score: ScoreValue::Integer(0),
         tags: vec![],
         language: cow!("default"),
+        render_timestamp: None,
     };
 
     let settings = WikitextSettings {
@@ -56,10 +61,16 @@ fn isolate_user_ids() {
         layout: Layout::Wikidot,
         enable_page_syntax: true,
         use_true_ids: true,
+        id_strategy: HtmlIdStrategy::Random,
+        heading_permalinks: false,
         isolate_user_ids: true,
         minify_css: false,
+        css: CssSettings::PERMISSIVE,
         allow_local_paths: true,
         interwiki: EMPTY_INTERWIKI.clone(),
+        embed: EmbedSettings::new(),
+        iframe: IframeSettings::PERMISSIVE,
+        variables: HashMap::new(),
     };
 
     macro_rules! test {
@@ -95,6 +95,7 @@ fn get_page_source(page_ref: &PageRef) -> Option<Cow<'static, str>> {
         "component:basic" => Some(cow!(COMPONENT_BASIC_PAGE_SOURCE)),
         "component:fruit" => Some(cow!(COMPONENT_FRUIT_PAGE_SOURCE)),
         "fragment:page" => Some(cow!("INCLUDED FRAGMENT")),
+        "fragment:heading" => Some(cow!("++ Included\nFrom another page.\n")),
         "missing" => None,
         _ => Some(cow!("INCLUDED PAGE")),
     }
@@ -20,7 +20,10 @@
 
 mod ast;
 mod id_prefix;
-mod includer;
+pub mod includer;
 mod large;
+mod output;
 mod prop;
 mod settings;
+
+pub mod temp_dir;
@@ -0,0 +1,280 @@
+/*
+ * test/output.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Tests for the additional data returned in `HtmlOutput`.
+
+use crate::data::PageInfo;
+use crate::layout::Layout;
+use crate::render::Render;
+use crate::render::html::{
+    BASELINE_STYLESHEET, HtmlDocumentRender, HtmlRender, INTERACTIVE_SCRIPT,
+};
+use crate::settings::{WikitextMode, WikitextSettings};
+use std::borrow::Cow;
+use std::num::NonZeroUsize;
+
+#[test]
+fn code_blocks() {
+    let mut page_info = PageInfo::dummy();
+    page_info.page = Cow::Borrowed("theme");
+    page_info.category = Some(Cow::Borrowed("css"));
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump);
+    let input = "[[code type=\"CSS\" name=\"Main Theme\"]]\nbody { color: red; }\n[[/code]]\n\n[[code]]\nplain\n[[/code]]";
+    let tokens = crate::tokenize(input);
+    let result = crate::parse(&tokens, &page_info, &settings);
+    let (tree, _errors) = result.into();
+    let html_output = HtmlRender.render(&tree, &page_info, &settings);
+
+    let code_blocks = &html_output.code_blocks;
+    assert_eq!(code_blocks.len(), 2, "Wrong number of code blocks");
+
+    assert_eq!(code_blocks[0].index, NonZeroUsize::new(1).unwrap());
+    assert_eq!(code_blocks[0].name.as_deref(), Some("main-theme"));
+    assert_eq!(code_blocks[0].language.as_deref(), Some("css"));
+    assert_eq!(code_blocks[0].mime_type, "text/css");
+    assert_eq!(code_blocks[0].url, "/local--code/css:theme/1");
+    assert_eq!(code_blocks[0].contents, "body { color: red; }");
+
+    assert_eq!(code_blocks[1].index, NonZeroUsize::new(2).unwrap());
+    assert_eq!(code_blocks[1].name, None);
+    assert_eq!(code_blocks[1].language, None);
+    assert_eq!(code_blocks[1].mime_type, "text/plain");
+    assert_eq!(code_blocks[1].url, "/local--code/css:theme/2");
+
+    assert!(
+        html_output.body.contains(
+            r#"<a class="wj-code-download" href="/local--code/css:theme/1" title="Download" download="main-theme">"#,
+        ),
+        "Download link not found in HTML: {:?}",
+        html_output.body,
+    );
+}
+
+#[test]
+fn document() {
+    let mut page_info = PageInfo::dummy();
+    page_info.title = Cow::Borrowed("Fish & Chips");
+    page_info.language = Cow::Borrowed("ar");
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump);
+    let tokens = crate::tokenize("**Apple**");
+    let result = crate::parse(&tokens, &page_info, &settings);
+    let (tree, _errors) = result.into();
+
+    let html = HtmlDocumentRender::BARE.render(&tree, &page_info, &settings);
+    assert!(
+        html.starts_with("<!DOCTYPE html>\n<html lang=\"ar\" dir=\"rtl\">\n<head>\n")
+    );
+    assert!(html.contains("<title>Fish &amp; Chips</title>"));
+    assert!(html.contains(r#"<meta name="keywords" content="tale,_cc" />"#));
+    assert!(html.contains("<body>\n<p><strong>Apple</strong></p>\n</body>"));
+    assert!(!html.contains("<style>"));
+    assert!(!html.contains("<script>"));
+
+    let html = HtmlDocumentRender::STANDALONE.render(&tree, &page_info, &settings);
+    assert!(html.contains(BASELINE_STYLESHEET));
+    assert!(html.contains(INTERACTIVE_SCRIPT));
+    assert!(html.ends_with("</script>\n</body>\n</html>\n"));
+
+    // No language attributes for the placeholder language
+    let mut default_info = page_info.clone();
+    default_info.language = Cow::Borrowed("default");
+    let html = HtmlDocumentRender::BARE.render(&tree, &default_info, &settings);
+    assert!(html.starts_with("<!DOCTYPE html>\n<html>\n"));
+}
+
+#[test]
+fn variables() {
+    use crate::parsing::ParseErrorKind;
+    use crate::render::text::TextRender;
+    use crate::tree::{VariableMap, VariableScopes};
+
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump);
+    settings
+        .variables
+        .insert(Cow::Borrowed("branch"), Cow::Borrowed("Site-19"));
+    settings
+        .variables
+        .insert(Cow::Borrowed("site"), Cow::Borrowed("overridden"));
+
+    let tokens = crate::tokenize("{$title} {$branch} {$site} {$tags} {$missing}");
+    let result = crate::parse(&tokens, &page_info, &settings);
+    let (tree, errors) = result.into();
+
+    // Only the unknown variable produces a warning
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind(), ParseErrorKind::UnknownVariable);
+    assert_eq!(errors[0].span(), 35..45);
+
+    let expected = "A page for the age Site-19 overridden tale _cc {$missing}";
+    let html = HtmlRender.render(&tree, &page_info, &settings);
+    assert_eq!(html.body, format!("<p>{expected}</p>"));
+    let text = TextRender.render(&tree, &page_info, &settings);
+    assert_eq!(text, expected);
+
+    // Include variables shadow everything else
+    let mut scopes = VariableScopes::for_page(&page_info, &settings);
+    let mut include: VariableMap = VariableMap::new();
+    include.insert(Cow::Borrowed("title"), Cow::Borrowed("Included"));
+    scopes.push_scope(&include);
+    assert_eq!(scopes.get("title"), Some("Included"));
+    assert_eq!(scopes.get("branch"), Some("Site-19"));
+    scopes.pop_scope();
+    assert_eq!(scopes.get("title"), Some("A page for the age"));
+    assert_eq!(scopes.get("category"), Some("_default"));
+    assert_eq!(scopes.get("full_name"), Some("some-page"));
+    assert_eq!(scopes.get("score"), Some("69"));
+
+    // The render timestamp is only known if the host provides it
+    assert_eq!(scopes.get("render_timestamp"), Some(""));
+
+    let mut page_info = page_info;
+    page_info.render_timestamp = Some(1_700_000_000);
+    let scopes = VariableScopes::for_page(&page_info, &settings);
+    assert_eq!(scopes.get("render_timestamp"), Some("1700000000"));
+}
+
+#[test]
+fn gallery_text() {
+    use crate::render::text::TextRender;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump);
+    let input = "Photos:\n[[gallery order=\"name\"]]\n: b.png title=\"Second\"\n: a.png\n[[/gallery]]";
+    let tokens = crate::tokenize(input);
+    let result = crate::parse(&tokens, &page_info, &settings);
+    let (tree, errors) = result.into();
+    assert!(errors.is_empty(), "Unexpected errors: {errors:#?}");
+
+    let text = TextRender.render(&tree, &page_info, &settings);
+    assert_eq!(text, "Photos:\na.png\nSecond");
+}
+
+#[test]
+fn file_info() {
+    use crate::render::{FileInfo, PageResolver};
+
+    #[derive(Debug)]
+    struct TestResolver;
+
+    impl PageResolver for TestResolver {
+        fn page_exists(&self, _site: &str, _page: &str) -> bool {
+            true
+        }
+
+        fn page_title(&self, _site: &str, _page: &str) -> Option<String> {
+            None
+        }
+
+        fn file_info(&self, _site: &str, page: &str, file: &str) -> Option<FileInfo> {
+            (page == "scp-001" && file == "report.pdf").then(|| FileInfo {
+                size: Some(1536),
+                mime_type: Some(str!("application/pdf")),
+            })
+        }
+    }
+
+    let mut page_info = PageInfo::dummy();
+    page_info.page = Cow::Borrowed("scp-001");
+
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump);
+    let tokens = crate::tokenize("[[file report.pdf|Report]] [[file other.txt]]");
+    let result = crate::parse(&tokens, &page_info, &settings);
+    let (tree, _errors) = result.into();
+    let html_output = HtmlRender.render_with_resolver(
+        &tree,
+        &page_info,
+        &settings,
+        &TestResolver,
+        None,
+    );
+
+    assert!(
+        html_output.body.contains(
+            r#"<a class="wj-file" href="https://sandbox.wjfiles.com/local--files/scp-001/report.pdf" type="application/pdf" data-file-size="1536">Report <span class="wj-file-size">(1.5 kB)</span></a>"#,
+        ),
+        "File link with hints not found in HTML: {:?}",
+        html_output.body,
+    );
+    assert!(
+        html_output.body.contains(
+            r#"<a class="wj-file" href="https://sandbox.wjfiles.com/local--files/scp-001/other.txt">other.txt</a>"#,
+        ),
+        "File link without hints not found in HTML: {:?}",
+        html_output.body,
+    );
+}
+
+#[test]
+fn outline() {
+    use crate::tree::HeadingLevel;
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump);
+    let input = "+ Apple\nfruit\n+++ **Banana**\n+* Cherry\n++ Durian\nsmelly";
+    let tokens = crate::tokenize(input);
+    let result = crate::parse(&tokens, &page_info, &settings);
+    let (tree, errors) = result.into();
+    assert!(errors.is_empty(), "Unexpected errors: {errors:#?}");
+
+    let html_output = HtmlRender.render(&tree, &page_info, &settings);
+    let outline = &html_output.outline;
+    assert_eq!(outline.len(), 2, "Wrong number of top-level headings");
+
+    let apple = &outline[0];
+    assert_eq!(apple.level, HeadingLevel::One);
+    assert_eq!(apple.name, "Apple");
+    assert!(apple.has_toc);
+    assert_eq!(apple.span, 0..29);
+    assert_eq!(apple.children.len(), 1);
+
+    let banana = &apple.children[0];
+    assert_eq!(banana.level, HeadingLevel::Three);
+    assert_eq!(banana.name, "Banana");
+    assert_eq!(banana.span, 14..29);
+    assert!(banana.children.is_empty());
+
+    let cherry = &outline[1];
+    assert_eq!(cherry.name, "Cherry");
+    assert!(!cherry.has_toc);
+    assert_eq!(cherry.id, None);
+    assert_eq!(cherry.span, 29..input.len());
+    assert_eq!(cherry.children.len(), 1);
+    assert_eq!(cherry.children[0].name, "Durian");
+
+    // IDs are the same as the rendered heading anchors
+    for entry in [apple, banana, &cherry.children[0]] {
+        let id = entry.id.as_ref().expect("Heading has no ID");
+        assert!(
+            html_output.body.contains(&format!(r#" id="{id}""#)),
+            "Heading ID {id:?} not found in HTML: {:?}",
+            html_output.body,
+        );
+    }
+
+    // Without true IDs, no anchors are rendered
+    let mut settings = settings.clone();
+    settings.use_true_ids = false;
+    let html_output = HtmlRender.render(&tree, &page_info, &settings);
+    assert_eq!(html_output.outline[0].id, None);
+    assert_eq!(html_output.outline[0].children[0].id, None);
+}
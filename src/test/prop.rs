@@ -32,6 +32,7 @@ use proptest::option;
 use proptest::prelude::*;
 use std::borrow::Cow;
 use std::num::NonZeroU32;
+use std::ops::Range;
 use std::sync::LazyLock;
 
 // Constants
@@ -362,12 +363,13 @@ fn arb_element_leaf() -> impl Strategy<Value = Element<'static>> {
         arb_checkbox(),
         // TODO: Element::User
         arb_code(),
-        (cow!(".*"), arb_attribute_map()).prop_map(|(contents, attributes)| {
-            Element::Html {
+        (cow!(".*"), arb_attribute_map(), any::<Range<usize>>()).prop_map(
+            |(contents, attributes, span)| Element::Html {
                 contents,
                 attributes,
+                span,
             }
-        }),
+        ),
         // TODO: Element::Iframe
         Just(Element::LineBreak),
         (1..50_u32)
@@ -430,9 +432,12 @@ fn arb_tree() -> impl Strategy<Value = SyntaxTree<'static>> {
                     html_blocks: Vec::new(),
                     code_blocks: Vec::new(), // these two are derived fields
                     table_of_contents,
+                    headings: Vec::new(),
                     footnotes,
+                    footnote_refs: Vec::new(),
                     needs_footnote_block,
                     bibliographies: BibliographyList::new(), // not bothering right now
+                    form: None,
                     wikitext_len,
                 }
             },
@@ -462,6 +467,7 @@ fn arb_page_info() -> impl Strategy<Value = PageInfo<'static>> {
                 score: score.into(),
                 tags,
                 language,
+                render_timestamp: None,
             },
         )
 }
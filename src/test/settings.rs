@@ -101,3 +101,335 @@ fn settings() {
         [true, true, false, false, true],
     );
 }
+
+#[test]
+fn heading_anchors() {
+    let page_info = PageInfo::dummy();
+
+    macro_rules! test {
+        ($layout:expr, $permalinks:expr, $input:expr, $expected:expr $(,)?) => {{
+            let mut settings = WikitextSettings::from_mode(WikitextMode::Page, $layout);
+            settings.heading_permalinks = $permalinks;
+
+            let mut text = str!($input);
+            crate::preprocess(&mut text);
+
+            let tokens = crate::tokenize(&text);
+            let result = crate::parse(&tokens, &page_info, &settings);
+            let (tree, _errors) = result.into();
+            let html_output = HtmlRender.render(&tree, &page_info, &settings);
+
+            println!();
+            println!("Input:  {:?}", $input);
+            println!("Output: {:?}", html_output.body);
+
+            assert_eq!(
+                html_output.body, $expected,
+                "HTML output doesn't match expected",
+            );
+        }};
+    }
+
+    test!(
+        Layout::Wikijump,
+        false,
+        "+ Alpha\n+ Alpha",
+        r#"<h1 id="alpha">Alpha</h1><h1 id="alpha-1">Alpha</h1>"#,
+    );
+    test!(
+        Layout::Wikijump,
+        true,
+        "++ Part 1: Intro",
+        r##"<h2 id="part-1-intro">Part 1: Intro<a class="wj-heading-permalink" href="#part-1-intro" aria-hidden="true">¶</a></h2>"##,
+    );
+    test!(Layout::Wikijump, true, "++* No TOC", "<h2>No TOC</h2>",);
+    test!(
+        Layout::Wikidot,
+        true,
+        "+ Alpha\n+ Alpha",
+        r#"<h1 id="toc0">Alpha</h1><h1 id="toc1">Alpha</h1>"#,
+    );
+}
+
+#[test]
+fn embed_providers() {
+    use crate::render::text::TextRender;
+    use crate::settings::{EmbedArgument, EmbedCharset, EmbedKind, EmbedProvider};
+    use std::borrow::Cow;
+
+    let page_info = PageInfo::dummy();
+    let mut settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump);
+    settings.embed.register(EmbedProvider {
+        name: Cow::Borrowed("peertube"),
+        arguments: Cow::Owned(vec![
+            EmbedArgument::required("video", EmbedCharset::Identifier),
+            EmbedArgument::optional("start", EmbedCharset::Numeric),
+        ]),
+        kind: EmbedKind::Iframe,
+        src: Cow::Borrowed(
+            "https://videos.example.com/videos/embed/{video}?start={start}",
+        ),
+        allow: None,
+        sandbox: Some(Cow::Borrowed("allow-same-origin allow-scripts")),
+        allow_fullscreen: true,
+        fallback: Cow::Borrowed("https://videos.example.com/w/{video}"),
+    });
+
+    let input = "[[embed PeerTube video=\"abc-123\" start=\"30\"]]";
+    let tokens = crate::tokenize(input);
+    let result = crate::parse(&tokens, &page_info, &settings);
+    let (tree, errors) = result.into();
+    assert!(
+        errors.is_empty(),
+        "Errors produced for custom embed: {errors:#?}"
+    );
+
+    let html_output = HtmlRender.render(&tree, &page_info, &settings);
+    assert_eq!(
+        html_output.body,
+        r#"<div class="wj-embed"><iframe src="https://videos.example.com/videos/embed/abc-123?start=30" frameborder="0" sandbox="allow-same-origin allow-scripts" allowfullscreen></iframe></div>"#,
+    );
+
+    let text_output = TextRender.render(&tree, &page_info, &settings);
+    assert_eq!(text_output, "https://videos.example.com/w/abc-123");
+
+    // Omitted optional arguments are substituted as empty
+    let input = "[[embed PeerTube video=\"abc-123\"]]";
+    let tokens = crate::tokenize(input);
+    let result = crate::parse(&tokens, &page_info, &settings);
+    let (tree, errors) = result.into();
+    assert!(
+        errors.is_empty(),
+        "Errors produced for custom embed: {errors:#?}"
+    );
+
+    let html_output = HtmlRender.render(&tree, &page_info, &settings);
+    assert_eq!(
+        html_output.body,
+        r#"<div class="wj-embed"><iframe src="https://videos.example.com/videos/embed/abc-123?start=" frameborder="0" sandbox="allow-same-origin allow-scripts" allowfullscreen></iframe></div>"#,
+    );
+
+    // Not available without being registered
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump);
+    let result = crate::parse(&tokens, &page_info, &settings);
+    let (_tree, errors) = result.into();
+    assert!(
+        !errors.is_empty(),
+        "No errors produced for unregistered embed"
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn embed_direct_url() {
+    use crate::tree::Embed;
+    use std::borrow::Cow;
+    use std::collections::BTreeMap;
+
+    let embed = Embed::Youtube {
+        video_id: Cow::Borrowed("dQw4w9WgXcQ"),
+    };
+    assert_eq!(embed.direct_url(), "https://youtu.be/dQw4w9WgXcQ");
+
+    let embed = Embed::GithubGist {
+        username: Cow::Borrowed("octocat"),
+        hash: Cow::Borrowed("6cad326836d38bd3a7ae"),
+    };
+    assert_eq!(
+        embed.direct_url(),
+        "https://gist.github.com/octocat/6cad326836d38bd3a7ae",
+    );
+
+    // Only the built-in providers are known
+    let embed = Embed::Custom {
+        provider: Cow::Borrowed("peertube"),
+        arguments: BTreeMap::new(),
+    };
+    assert_eq!(embed.direct_url(), "");
+}
+
+#[test]
+fn iframe_policy() {
+    use crate::parsing::ParseErrorKind;
+    use std::borrow::Cow;
+
+    let page_info = PageInfo::dummy();
+
+    macro_rules! test {
+        ($settings:expr, $input:expr, $expected:expr, $disallowed:expr $(,)?) => {{
+            let settings = $settings;
+            let tokens = crate::tokenize($input);
+            let result = crate::parse(&tokens, &page_info, &settings);
+            let (tree, errors) = result.into();
+            let html_output = HtmlRender.render(&tree, &page_info, &settings);
+
+            println!();
+            println!("Input:  {:?}", $input);
+            println!("Output: {:?}", html_output.body);
+
+            assert_eq!(
+                html_output.body, $expected,
+                "HTML output doesn't match expected",
+            );
+            assert_eq!(
+                errors
+                    .iter()
+                    .any(|error| error.kind() == ParseErrorKind::IframeDisallowed),
+                $disallowed,
+                "Unexpected iframe warnings: {errors:#?}",
+            );
+        }};
+    }
+
+    let page = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump);
+    let forum = WikitextSettings::from_mode(WikitextMode::ForumPost, Layout::Wikijump);
+    let mut allowlist = forum.clone();
+    allowlist.iframe.enabled = true;
+    allowlist.iframe.allowed_origins = Some(Cow::Owned(vec![
+        Cow::Borrowed("*.example.com"),
+        Cow::Borrowed("https://example.com"),
+    ]));
+
+    test!(
+        page.clone(),
+        "[[iframe https://phishing.example.net/]]",
+        r#"<iframe src="https://phishing.example.net/" crossorigin></iframe>"#,
+        false,
+    );
+    test!(
+        forum.clone(),
+        "[[iframe https://phishing.example.net/]]",
+        r#"<div class="wj-error-block wj-iframe-disallowed">Embedded content from this source is not permitted</div>"#,
+        true,
+    );
+    test!(
+        forum,
+        "[[html]]\n<b>Hi</b>\n[[/html]]",
+        r#"<div class="wj-error-block wj-iframe-disallowed">Embedded content from this source is not permitted</div>"#,
+        true,
+    );
+    test!(
+        allowlist.clone(),
+        "[[iframe https://maps.example.com/?q=1]]",
+        r#"<iframe src="https://maps.example.com/?q=1" crossorigin sandbox="allow-scripts allow-popups" referrerpolicy="no-referrer" loading="lazy"></iframe>"#,
+        false,
+    );
+    test!(
+        allowlist.clone(),
+        "[[iframe https://phishing.example.net/]]",
+        r#"<div class="wj-error-block wj-iframe-disallowed">Embedded content from this source is not permitted</div>"#,
+        true,
+    );
+    test!(
+        allowlist,
+        "[[html]]\n<b>Hi</b>\n[[/html]]",
+        r#"<iframe srcdoc="&lt;b&gt;Hi&lt;/b&gt;" sandbox="allow-scripts allow-popups" referrerpolicy="no-referrer" loading="lazy"></iframe>"#,
+        false,
+    );
+}
+
+#[test]
+fn html_block_store() {
+    use crate::parsing::ParseErrorKind;
+    use crate::render::html::{HtmlBlockKey, HtmlBlockStore};
+    use std::borrow::Cow;
+    use std::cell::RefCell;
+    use std::io;
+
+    #[derive(Debug, Default)]
+    struct MemoryStore {
+        blocks: RefCell<Vec<(String, String)>>,
+    }
+
+    impl HtmlBlockStore for MemoryStore {
+        fn store(&self, key: &HtmlBlockKey, html: &str) -> io::Result<String> {
+            self.blocks
+                .borrow_mut()
+                .push((key.hash.clone(), str!(html)));
+
+            Ok(format!("https://html.example.com/{}", key.hash))
+        }
+    }
+
+    let page_info = PageInfo::dummy();
+    let settings = WikitextSettings::from_mode(WikitextMode::Page, Layout::Wikijump);
+    let input = "[[html]]\n<b>A</b>\n[[/html]]\n\n[[html]]\n<b>A</b>\n[[/html]]\n\n[[html]]\n<b>B</b>\n[[/html]]";
+    let tokens = crate::tokenize(input);
+    let result = crate::parse(&tokens, &page_info, &settings);
+    let (tree, _errors) = result.into();
+
+    // Inlined by default
+    let html_output = HtmlRender.render(&tree, &page_info, &settings);
+    assert!(
+        html_output
+            .body
+            .contains(r#"srcdoc="&lt;b&gt;A&lt;/b&gt;""#),
+        "HTML block not inlined: {:?}",
+        html_output.body,
+    );
+    assert_eq!(html_output.html_blocks.len(), 3);
+    assert!(
+        html_output
+            .html_blocks
+            .iter()
+            .all(|block| block.url.is_none() && block.hash.is_none())
+    );
+
+    // Submitted to store, with identical blocks deduplicated
+    let store = MemoryStore::default();
+    let html_output = HtmlRender.render_with_store(&tree, &page_info, &settings, &store);
+    let blocks = store.blocks.into_inner();
+    assert_eq!(blocks.len(), 2, "Identical HTML blocks were stored twice");
+
+    let key_a = HtmlBlockKey::new(&page_info, "<b>A</b>");
+    let url_a = format!("https://html.example.com/{}", key_a.hash);
+    assert!(
+        html_output
+            .body
+            .contains(&format!(r#"<iframe src="{url_a}" crossorigin>"#)),
+        "HTML block iframe doesn't use stored URL: {:?}",
+        html_output.body,
+    );
+
+    let urls = html_output
+        .html_blocks
+        .iter()
+        .map(|block| block.url.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(urls[0], Some(url_a.as_str()));
+    assert_eq!(urls[0], urls[1]);
+    assert_ne!(urls[0], urls[2]);
+    assert_eq!(html_output.html_blocks[0].hash, Some(key_a.hash));
+    assert!(html_output.warnings.is_empty());
+
+    // Stored at an origin not permitted by the iframe policy
+    let mut allowlist = settings.clone();
+    allowlist.iframe.allowed_origins =
+        Some(Cow::Owned(vec![Cow::Borrowed("wiki.example.com")]));
+
+    let store = MemoryStore::default();
+    let html_output = HtmlRender.render_with_store(&tree, &page_info, &allowlist, &store);
+    assert!(
+        html_output.body.starts_with(
+            r#"<div class="wj-error-block wj-iframe-disallowed">Embedded content from this source is not permitted</div>"#,
+        ),
+        "HTML block with disallowed URL not replaced: {:?}",
+        html_output.body,
+    );
+
+    let warnings = html_output
+        .warnings
+        .iter()
+        .map(|warning| (warning.kind(), warning.rule(), warning.span()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        warnings,
+        [
+            (ParseErrorKind::IframeDisallowed, "block-html", 9..17),
+            (ParseErrorKind::IframeDisallowed, "block-html", 38..46),
+            (ParseErrorKind::IframeDisallowed, "block-html", 67..75),
+        ],
+    );
+    assert_eq!(&input[9..17], "<b>A</b>");
+    assert_eq!(&input[67..75], "<b>B</b>");
+}
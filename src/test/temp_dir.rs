@@ -0,0 +1,54 @@
+/*
+ * test/temp_dir.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Temporary directories for tests which touch the filesystem.
+
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+
+/// A temporary directory, which is removed when dropped.
+///
+/// This means the directory is cleaned up even if the test panics.
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates a unique path for the given test, removing anything left there by a previous run.
+    ///
+    /// The directory itself is not created.
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("ftml-{name}-{}", process::id()));
+        let _ = fs::remove_dir_all(&path);
+        TempDir { path }
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
@@ -100,6 +100,24 @@ impl<'t> FullText<'t> {
         &self.text[start..end]
     }
 
+    /// Gets the byte offset of a slice which was taken from this text.
+    ///
+    /// # Panics
+    /// If the slice does not lie within the text, this function will panic.
+    pub fn offset_of(&self, slice: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let offset = (slice.as_ptr() as usize).wrapping_sub(start);
+
+        assert!(
+            offset
+                .checked_add(slice.len())
+                .is_some_and(|end| end <= self.text.len()),
+            "Slice is not from the full text",
+        );
+
+        offset
+    }
+
     /// Gives the length in bytes of the text.
     #[inline]
     pub fn len(&self) -> usize {
@@ -135,6 +153,16 @@ fn slice() {
     }
 }
 
+#[test]
+fn offset_of() {
+    let text = "Apple banana!";
+    let full_text = FullText::new(text);
+
+    assert_eq!(full_text.offset_of(&text[0..5]), 0);
+    assert_eq!(full_text.offset_of(&text[6..12]), 6);
+    assert_eq!(full_text.offset_of(&text[13..]), 13);
+}
+
 #[test]
 #[should_panic]
 fn slice_invalid() {
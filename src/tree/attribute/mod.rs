@@ -111,6 +111,31 @@ impl<'t> AttributeMap<'t> {
         }
     }
 
+    /// Sanitizes the `style` attribute, if present.
+    ///
+    /// Any malformed declarations, or those not permitted by the CSS settings,
+    /// are removed. If none remain, the attribute is removed.
+    pub fn sanitize_style(&mut self, settings: &WikitextSettings) {
+        cfg_if! {
+            if #[cfg(feature = "html")] {
+                use crate::render::html::css::sanitize_inline_style;
+
+                if let Some(value) = self.inner.get_mut("style") {
+                    trace!("Found 'style' attribute, sanitizing value");
+                    let style = sanitize_inline_style(value, &settings.css);
+                    if style.is_empty() {
+                        self.inner.remove("style");
+                    } else if let Cow::Owned(style) = style {
+                        *value = Cow::Owned(style);
+                    }
+                }
+            } else {
+                // Without HTML rendering, style values are never emitted.
+                let _ = settings;
+            }
+        }
+    }
+
     pub fn to_owned(&self) -> AttributeMap<'static> {
         let mut inner = BTreeMap::new();
 
@@ -0,0 +1,388 @@
+/*
+ * tree/bibliography/bibtex.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! A parser for the subset of BibTeX used in bibliographies.
+//!
+//! Only the entry key and the `author`, `title`, `year`, `url`, and `doi`
+//! fields are read, any other fields are parsed but then ignored.
+//! Text outside of entries is ignored as in BibTeX, as are `@comment`,
+//! `@preamble`, and `@string` entries.
+
+use super::BibliographyReference;
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// An error in a BibTeX bibliography.
+///
+/// The span is relative to the start of the bibliography body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BibtexError {
+    pub span: Range<usize>,
+}
+
+/// Parses a BibTeX bibliography into labelled references, in order.
+pub fn parse_bibtex(
+    text: &str,
+) -> Result<Vec<(Cow<'_, str>, BibliographyReference<'_>)>, BibtexError> {
+    let mut scanner = Scanner { text, position: 0 };
+    let mut references = Vec::new();
+
+    while let Some(offset) = scanner.rest().find('@') {
+        scanner.position += offset + 1;
+
+        let kind = scanner.identifier()?;
+        scanner.skip_whitespace();
+
+        let entry_start = scanner.position;
+        let close = match scanner.next_char() {
+            Some('{') => '}',
+            Some('(') => ')',
+            _ => return Err(scanner.error_here()),
+        };
+
+        // Entries which aren't references, skip over their body
+        if ["comment", "preamble", "string"]
+            .iter()
+            .any(|special| kind.eq_ignore_ascii_case(special))
+        {
+            scanner.position -= 1;
+            scanner.delimited()?;
+            continue;
+        }
+
+        // Citation key
+        scanner.skip_whitespace();
+        let key_start = scanner.position;
+        let key =
+            scanner.take_while(|c| !matches!(c, ',' | '}' | ')') && !c.is_whitespace());
+        if key.is_empty() {
+            return Err(BibtexError {
+                span: key_start..(key_start + 1).min(text.len()),
+            });
+        }
+
+        let mut reference = BibliographyReference {
+            kind: Cow::Owned(kind.to_ascii_lowercase()),
+            ..Default::default()
+        };
+
+        // Fields
+        loop {
+            scanner.skip_whitespace();
+            match scanner.next_char() {
+                Some(',') => (),
+                Some(c) if c == close => break,
+                Some(_) => return Err(scanner.error_here()),
+                None => {
+                    // Unclosed, span covers the rest of the input
+                    return Err(BibtexError {
+                        span: entry_start..text.len(),
+                    });
+                }
+            }
+
+            scanner.skip_whitespace();
+            if scanner.rest().starts_with(close) {
+                scanner.position += 1;
+                break;
+            }
+
+            let name = scanner.identifier()?;
+            scanner.skip_whitespace();
+            if scanner.next_char() != Some('=') {
+                return Err(scanner.error_here());
+            }
+
+            let value = scanner.value()?;
+            match name.to_ascii_lowercase().as_str() {
+                "author" => {
+                    reference.authors = split_authors(value);
+                }
+                "title" => reference.title = Some(value),
+                "year" => reference.year = Some(value),
+                "url" => reference.url = Some(value),
+                "doi" => reference.doi = Some(value),
+                _ => (),
+            }
+        }
+
+        if references.iter().any(|(label, _)| label == key) {
+            warn!("Duplicate key in BibTeX bibliography: {key}");
+            return Err(BibtexError {
+                span: key_start..key_start + key.len(),
+            });
+        }
+
+        references.push((Cow::Borrowed(key), reference));
+    }
+
+    Ok(references)
+}
+
+#[derive(Debug)]
+struct Scanner<'t> {
+    text: &'t str,
+    position: usize,
+}
+
+impl<'t> Scanner<'t> {
+    #[inline]
+    fn rest(&self) -> &'t str {
+        &self.text[self.position..]
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.rest().chars().next()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    /// Produces an error spanning the last character consumed.
+    ///
+    /// If nothing has been consumed, then it spans the first character instead.
+    fn error_here(&self) -> BibtexError {
+        let span = match self.text[..self.position].chars().next_back() {
+            Some(c) => self.position - c.len_utf8()..self.position,
+            None => 0..self.text.chars().next().map_or(0, char::len_utf8),
+        };
+
+        BibtexError { span }
+    }
+
+    fn take_while<F>(&mut self, f: F) -> &'t str
+    where
+        F: Fn(char) -> bool,
+    {
+        let rest = self.rest();
+        let length = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    fn identifier(&mut self) -> Result<&'t str, BibtexError> {
+        let identifier =
+            self.take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'));
+
+        if identifier.is_empty() {
+            self.next_char();
+            return Err(self.error_here());
+        }
+
+        Ok(identifier)
+    }
+
+    /// Consumes a balanced `{...}` or `(...)` group, returning its inner text.
+    fn delimited(&mut self) -> Result<&'t str, BibtexError> {
+        let start = self.position;
+        let (open, close) = match self.next_char() {
+            Some('{') => ('{', '}'),
+            Some('(') => ('(', ')'),
+            Some('"') => {
+                let contents = self.take_while(|c| c != '"');
+                if self.next_char().is_none() {
+                    return Err(BibtexError {
+                        span: start..self.position,
+                    });
+                }
+
+                return Ok(contents);
+            }
+            _ => return Err(self.error_here()),
+        };
+
+        let mut depth = 1;
+        while let Some(c) = self.next_char() {
+            if c == open {
+                depth += 1;
+            } else if c == close {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Ok(&self.text[start + 1..self.position - 1]);
+                }
+            }
+        }
+
+        // Unbalanced, span covers the rest of the input
+        Err(BibtexError {
+            span: start..self.position,
+        })
+    }
+
+    /// Consumes a field value, including any `#` concatenations.
+    fn value(&mut self) -> Result<Cow<'t, str>, BibtexError> {
+        let mut parts = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+            let part = match self.rest().chars().next() {
+                Some('{' | '"') => self.delimited()?,
+                Some(c) if c.is_ascii_alphanumeric() => {
+                    self.take_while(|c| c.is_ascii_alphanumeric())
+                }
+                _ => {
+                    self.next_char();
+                    return Err(self.error_here());
+                }
+            };
+
+            parts.push(part);
+
+            self.skip_whitespace();
+            if !self.rest().starts_with('#') {
+                break;
+            }
+
+            self.position += 1;
+        }
+
+        match parts.as_slice() {
+            [part] => Ok(clean_value(part)),
+            _ => Ok(Cow::Owned(clean_value(&parts.concat()).into_owned())),
+        }
+    }
+}
+
+/// Removes grouping braces and collapses whitespace in a field value.
+fn clean_value(value: &str) -> Cow<'_, str> {
+    let value = value.trim();
+    let needs_cleaning = value.contains(['{', '}'])
+        || value.contains("  ")
+        || value.contains(|c: char| c.is_whitespace() && c != ' ');
+
+    if !needs_cleaning {
+        return Cow::Borrowed(value);
+    }
+
+    let words = value
+        .split_whitespace()
+        .map(|word| word.replace(['{', '}'], ""))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+
+    Cow::Owned(words.join(" "))
+}
+
+/// Splits a BibTeX author list, which is delimited by `and`.
+fn split_authors(value: Cow<str>) -> Vec<Cow<str>> {
+    match value {
+        Cow::Borrowed(value) => value
+            .split(" and ")
+            .map(str::trim)
+            .filter(|author| !author.is_empty())
+            .map(Cow::Borrowed)
+            .collect(),
+        Cow::Owned(value) => value
+            .split(" and ")
+            .map(str::trim)
+            .filter(|author| !author.is_empty())
+            .map(|author| Cow::Owned(str!(author)))
+            .collect(),
+    }
+}
+
+#[test]
+fn bibtex() {
+    let text = r#"
+Some leading text, which is ignored.
+
+@comment{ This is a comment, with {braces}. }
+
+@article{bell1968,
+  author = {Bell, J. and Hewish, A. and Pilkington, J. D. H.},
+  title = {Observation of a {Rapidly} Pulsating
+           Radio Source},
+  journal = {Nature},
+  year = 1968,
+  doi = {10.1038/217709a0},
+}
+
+@book(guy1994,
+  author = "Richard K. Guy",
+  title = "Unsolved Problems in " # "Number Theory",
+  year = {1994},
+  url = {https://example.com/guy}
+)
+"#;
+
+    let references = parse_bibtex(text).expect("Unable to parse BibTeX");
+    assert_eq!(
+        references,
+        vec![
+            (
+                Cow::Borrowed("bell1968"),
+                BibliographyReference {
+                    kind: Cow::Borrowed("article"),
+                    authors: vec![
+                        Cow::Borrowed("Bell, J."),
+                        Cow::Borrowed("Hewish, A."),
+                        Cow::Borrowed("Pilkington, J. D. H."),
+                    ],
+                    title: Some(Cow::Borrowed(
+                        "Observation of a Rapidly Pulsating Radio Source",
+                    )),
+                    year: Some(Cow::Borrowed("1968")),
+                    url: None,
+                    doi: Some(Cow::Borrowed("10.1038/217709a0")),
+                },
+            ),
+            (
+                Cow::Borrowed("guy1994"),
+                BibliographyReference {
+                    kind: Cow::Borrowed("book"),
+                    authors: vec![Cow::Borrowed("Richard K. Guy")],
+                    title: Some(Cow::Borrowed("Unsolved Problems in Number Theory")),
+                    year: Some(Cow::Borrowed("1994")),
+                    url: Some(Cow::Borrowed("https://example.com/guy")),
+                    doi: None,
+                },
+            ),
+        ],
+    );
+}
+
+#[test]
+fn bibtex_errors() {
+    macro_rules! check {
+        ($text:expr, $span:expr $(,)?) => {{
+            let text = $text;
+            let error = parse_bibtex(text).expect_err("BibTeX parsed successfully");
+            assert_eq!(
+                error.span,
+                $span,
+                "Span mismatch for BibTeX input: {text:?} ({:?})",
+                &text[error.span.clone()],
+            );
+        }};
+    }
+
+    check!("@article{key, title = {Unclosed}", 8..32);
+    check!("@article{key, title {Missing equals}}", 20..21);
+    check!("@article{, title = {No key}}", 9..10);
+    check!("@article{a, year = 1}\n@book{a, year = 2}", 28..29);
+    check!("@{key}", 1..2);
+    check!("@ért{key}", 1..3);
+    check!("@article{key, title = é}", 22..24);
+    check!("@article{key, é = {Name}}", 14..16);
+}
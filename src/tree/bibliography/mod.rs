@@ -0,0 +1,242 @@
+/*
+ * tree/bibliography/mod.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Structures managing bibliographic references.
+//!
+//! As an extension from Wikidot, we permit multiple bibliographies
+//! to appear on a page, allowing people to have multiple sets,
+//! for instance on separate tabs on a page.
+//!
+//! The first reference found is the one used.
+
+mod bibtex;
+mod style;
+
+pub use self::bibtex::{BibtexError, parse_bibtex};
+pub use self::style::{CitationStyle, ReferencePart};
+
+use super::Element;
+use super::clone::{elements_to_owned, option_string_to_owned, string_to_owned};
+use std::borrow::Cow;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Bibliography<'t> {
+    style: CitationStyle,
+    references: Vec<(Cow<'t, str>, BibliographyContents<'t>)>,
+}
+
+impl<'t> Bibliography<'t> {
+    pub fn new(style: CitationStyle) -> Self {
+        Bibliography {
+            style,
+            references: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn style(&self) -> CitationStyle {
+        self.style
+    }
+
+    pub fn add(&mut self, label: Cow<'t, str>, elements: Vec<Element<'t>>) {
+        self.add_contents(label, BibliographyContents::Elements(elements));
+    }
+
+    pub fn add_reference(
+        &mut self,
+        label: Cow<'t, str>,
+        reference: BibliographyReference<'t>,
+    ) {
+        self.add_contents(label, BibliographyContents::Reference(reference));
+    }
+
+    fn add_contents(&mut self, label: Cow<'t, str>, contents: BibliographyContents<'t>) {
+        // If the reference already exists, it is *not* overwritten.
+        //
+        // This maintains the invariant that the first reference with a given label,
+        // across any bibliography, is the one which is used.
+        if self.get(&label).is_some() {
+            warn!("Duplicate reference in bibliography: {label}");
+            return;
+        }
+
+        self.references.push((label, contents));
+    }
+
+    pub fn get(&self, label: &str) -> Option<(usize, &BibliographyContents<'t>)> {
+        // References are maintained as a list, which means that searching
+        // for a particular label is O(n), but this is fine as the number
+        // of references is always going to be bounded. Even at 100 references
+        // this would run at essentially the same speed.
+        //
+        // This also gives us free indexing based on this order, and the
+        // order based on it, so we don't need a two-index map here.
+        for (index, (ref_label, contents)) in self.references.iter().enumerate() {
+            if label == ref_label {
+                // Change from zero-indexing to one-indexing
+                return Some((index + 1, contents));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    pub fn slice(&self) -> &[(Cow<'t, str>, BibliographyContents<'t>)] {
+        &self.references
+    }
+
+    pub fn to_owned(&self) -> Bibliography<'static> {
+        Bibliography {
+            style: self.style,
+            references: self
+                .references
+                .iter()
+                .map(|(label, contents)| (string_to_owned(label), contents.to_owned()))
+                .collect(),
+        }
+    }
+}
+
+/// The contents of a single bibliography entry.
+///
+/// Entries from a definition list are arbitrary wikitext, whereas
+/// entries from BibTeX are structured, and formatted by the
+/// bibliography's citation style.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", tag = "type", content = "data")]
+pub enum BibliographyContents<'t> {
+    Elements(Vec<Element<'t>>),
+    Reference(BibliographyReference<'t>),
+}
+
+impl BibliographyContents<'_> {
+    pub fn to_owned(&self) -> BibliographyContents<'static> {
+        match self {
+            BibliographyContents::Elements(elements) => {
+                BibliographyContents::Elements(elements_to_owned(elements))
+            }
+            BibliographyContents::Reference(reference) => {
+                BibliographyContents::Reference(reference.to_owned())
+            }
+        }
+    }
+}
+
+/// A structured bibliographic reference, such as one imported from BibTeX.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct BibliographyReference<'t> {
+    /// The BibTeX entry type, such as `article` or `book`.
+    pub kind: Cow<'t, str>,
+
+    /// The list of authors, each as written (e.g. `Bell, J.` or `Jocelyn Bell`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<Cow<'t, str>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<Cow<'t, str>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<Cow<'t, str>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<Cow<'t, str>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doi: Option<Cow<'t, str>>,
+}
+
+impl BibliographyReference<'_> {
+    /// Gets the link for this reference, preferring its DOI over its URL.
+    pub fn link(&self) -> Option<Cow<'_, str>> {
+        match (&self.doi, &self.url) {
+            (Some(doi), _) => Some(Cow::Owned(format!("https://doi.org/{doi}"))),
+            (None, Some(url)) => Some(Cow::Borrowed(url)),
+            (None, None) => None,
+        }
+    }
+
+    pub fn to_owned(&self) -> BibliographyReference<'static> {
+        BibliographyReference {
+            kind: string_to_owned(&self.kind),
+            authors: self
+                .authors
+                .iter()
+                .map(|author| string_to_owned(author))
+                .collect(),
+            title: option_string_to_owned(&self.title),
+            year: option_string_to_owned(&self.year),
+            url: option_string_to_owned(&self.url),
+            doi: option_string_to_owned(&self.doi),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct BibliographyList<'t>(Vec<Bibliography<'t>>);
+
+impl<'t> BibliographyList<'t> {
+    pub fn new() -> Self {
+        BibliographyList::default()
+    }
+
+    pub fn push(&mut self, bibliography: Bibliography<'t>) {
+        self.0.push(bibliography);
+    }
+
+    pub fn append(&mut self, other: &mut Self) {
+        self.0.append(&mut other.0);
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[inline]
+    pub fn next_index(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Finds the reference with this label, and the citation style used to cite it.
+    pub fn get_reference(
+        &self,
+        label: &str,
+    ) -> Option<(CitationStyle, usize, &BibliographyContents<'t>)> {
+        for bibliography in &self.0 {
+            // Find the first entry with the label, per the above invariant.
+            if let Some((index, contents)) = bibliography.get(label) {
+                return Some((bibliography.style(), index, contents));
+            }
+        }
+
+        None
+    }
+
+    pub fn get_bibliography(&self, index: usize) -> &Bibliography<'t> {
+        &self.0[index]
+    }
+
+    pub fn to_owned(&self) -> BibliographyList<'static> {
+        BibliographyList(self.0.iter().map(|b| b.to_owned()).collect())
+    }
+}
@@ -0,0 +1,325 @@
+/*
+ * tree/bibliography/style.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::{BibliographyContents, BibliographyReference};
+use std::borrow::Cow;
+use std::str::FromStr;
+
+/// The style used to cite and list the references in a bibliography.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CitationStyle {
+    /// Citations are the reference number, e.g. `[1]`.
+    #[default]
+    Numeric,
+
+    /// Citations are the authors and year, e.g. `(Bell and Hewish 1968)`.
+    AuthorYear,
+
+    /// Citations and references follow APA, e.g. `(Bell & Hewish, 1968)`.
+    Apa,
+}
+
+/// A piece of a formatted reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReferencePart<'a> {
+    Text(Cow<'a, str>),
+    Title(&'a str),
+    Link(Cow<'a, str>),
+}
+
+impl CitationStyle {
+    pub fn name(self) -> &'static str {
+        match self {
+            CitationStyle::Numeric => "numeric",
+            CitationStyle::AuthorYear => "author-year",
+            CitationStyle::Apa => "apa",
+        }
+    }
+
+    /// Whether references are numbered in this style.
+    #[inline]
+    pub fn numbered(self) -> bool {
+        self == CitationStyle::Numeric
+    }
+
+    /// Produces the text used to cite a reference.
+    ///
+    /// Author-based styles need structured references, other entries
+    /// are cited using their label instead. These citations are already
+    /// parenthesized, so only numeric citations take brackets.
+    pub fn cite_text(
+        self,
+        index: usize,
+        label: &str,
+        contents: &BibliographyContents,
+    ) -> String {
+        let reference = match (self, contents) {
+            (CitationStyle::Numeric, _) => return str!(index),
+            (_, BibliographyContents::Elements(_)) => return format!("({label})"),
+            (_, BibliographyContents::Reference(reference)) => reference,
+        };
+
+        let mut families = reference.authors.iter().map(|author| split_name(author).0);
+        let authors = match reference.authors.len() {
+            0 => reference.title.as_deref().unwrap_or(label).to_string(),
+            1 => str!(families.next().unwrap()),
+            2 => {
+                let first = families.next().unwrap();
+                let second = families.next().unwrap();
+
+                match self {
+                    CitationStyle::Apa => format!("{first} & {second}"),
+                    _ => format!("{first} and {second}"),
+                }
+            }
+            _ => format!("{} et al.", families.next().unwrap()),
+        };
+
+        match (self, &reference.year) {
+            (CitationStyle::Apa, Some(year)) => format!("({authors}, {year})"),
+            (CitationStyle::Apa, None) => format!("({authors}, n.d.)"),
+            (_, Some(year)) => format!("({authors} {year})"),
+            (_, None) => format!("({authors})"),
+        }
+    }
+
+    /// Formats a structured reference for the bibliography listing.
+    pub fn format_reference<'a>(
+        self,
+        reference: &'a BibliographyReference,
+    ) -> Vec<ReferencePart<'a>> {
+        let mut parts = Vec::new();
+        let authors = self.format_authors(&reference.authors);
+        let title = reference.title.as_deref();
+        let year = reference.year.as_deref();
+
+        macro_rules! text {
+            ($($arg:tt)*) => {
+                parts.push(ReferencePart::Text(Cow::Owned(format!($($arg)*))))
+            };
+        }
+
+        match self {
+            // J. Bell and A. Hewish, Title, 1968.
+            CitationStyle::Numeric => {
+                if let Some(authors) = authors {
+                    text!("{authors}, ");
+                }
+
+                if let Some(title) = title {
+                    parts.push(ReferencePart::Title(title));
+                }
+
+                match (title, year) {
+                    (Some(_), Some(year)) => text!(", {year}."),
+                    (None, Some(year)) => text!("{year}."),
+                    (Some(_), None) => text!("."),
+                    (None, None) => (),
+                }
+            }
+
+            // Bell, J., and A. Hewish. 1968. Title.
+            CitationStyle::AuthorYear => {
+                if let Some(authors) = authors {
+                    text!("{}. ", authors.trim_end_matches('.'));
+                }
+
+                if let Some(year) = year {
+                    text!("{year}. ");
+                }
+
+                if let Some(title) = title {
+                    parts.push(ReferencePart::Title(title));
+                    text!(".");
+                }
+            }
+
+            // Bell, J., & Hewish, A. (1968). Title.
+            CitationStyle::Apa => {
+                if let Some(authors) = authors {
+                    text!("{authors} ");
+                }
+
+                text!("({}). ", year.unwrap_or("n.d."));
+
+                if let Some(title) = title {
+                    parts.push(ReferencePart::Title(title));
+                    text!(".");
+                }
+            }
+        }
+
+        if let Some(link) = reference.link() {
+            if let Some(ReferencePart::Text(text)) = parts.last_mut() {
+                *text = Cow::Owned(format!("{} ", text.trim_end()));
+            }
+
+            parts.push(ReferencePart::Link(link));
+        } else if let Some(ReferencePart::Text(text)) = parts.last_mut() {
+            *text = Cow::Owned(str!(text.trim_end()));
+        }
+
+        parts
+    }
+
+    fn format_authors(self, authors: &[Cow<str>]) -> Option<String> {
+        if authors.is_empty() {
+            return None;
+        }
+
+        let names = authors
+            .iter()
+            .enumerate()
+            .map(|(index, author)| {
+                let (family, given) = split_name(author);
+
+                if given.is_empty() {
+                    return str!(family);
+                }
+
+                match self {
+                    CitationStyle::Numeric => format!("{} {family}", initials(given)),
+                    CitationStyle::AuthorYear if index == 0 => {
+                        format!("{family}, {given}")
+                    }
+                    CitationStyle::AuthorYear => format!("{given} {family}"),
+                    CitationStyle::Apa => format!("{family}, {}", initials(given)),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let conjunction = match self {
+            CitationStyle::Apa => "&",
+            _ => "and",
+        };
+
+        Some(match names.as_slice() {
+            [name] => name.clone(),
+            [first, second] if self == CitationStyle::Numeric => {
+                format!("{first} {conjunction} {second}")
+            }
+            [rest @ .., last] => format!("{}, {conjunction} {last}", rest.join(", ")),
+            [] => unreachable!(),
+        })
+    }
+}
+
+impl FromStr for CitationStyle {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s.to_ascii_lowercase().as_str() {
+            "numeric" => Ok(CitationStyle::Numeric),
+            "author-year" | "author_year" | "authoryear" => Ok(CitationStyle::AuthorYear),
+            "apa" => Ok(CitationStyle::Apa),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Splits an author's name into their family and given names.
+///
+/// This accepts both `Family, Given` and `Given Family` forms.
+fn split_name(author: &str) -> (&str, &str) {
+    match author.split_once(',') {
+        Some((family, given)) => (family.trim(), given.trim()),
+        None => match author.trim().rsplit_once(' ') {
+            Some((given, family)) => (family, given.trim()),
+            None => (author.trim(), ""),
+        },
+    }
+}
+
+/// Abbreviates given names into initials, e.g. `Jocelyn Bell` to `J. B.`.
+fn initials(given: &str) -> String {
+    given
+        .split_whitespace()
+        .filter_map(|name| name.chars().next())
+        .map(|c| format!("{c}."))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn citation_styles() {
+    let reference = BibliographyReference {
+        kind: Cow::Borrowed("article"),
+        authors: vec![
+            Cow::Borrowed("Bell, Jocelyn"),
+            Cow::Borrowed("Antony Hewish"),
+        ],
+        title: Some(Cow::Borrowed(
+            "Observation of a Rapidly Pulsating Radio Source",
+        )),
+        year: Some(Cow::Borrowed("1968")),
+        url: None,
+        doi: Some(Cow::Borrowed("10.1038/217709a0")),
+    };
+    let contents = BibliographyContents::Reference(reference.clone());
+
+    macro_rules! check {
+        ($style:expr, $cite:expr, $formatted:expr $(,)?) => {{
+            let style = $style;
+            assert_eq!(style.cite_text(3, "bell", &contents), $cite);
+
+            let formatted = style
+                .format_reference(&reference)
+                .into_iter()
+                .map(|part| match part {
+                    ReferencePart::Text(text) => text.into_owned(),
+                    ReferencePart::Title(title) => format!("<{title}>"),
+                    ReferencePart::Link(link) => format!("[{link}]"),
+                })
+                .collect::<String>();
+
+            assert_eq!(formatted, $formatted, "Formatted reference doesn't match");
+        }};
+    }
+
+    check!(
+        CitationStyle::Numeric,
+        "3",
+        "J. Bell and A. Hewish, <Observation of a Rapidly Pulsating Radio Source>, 1968. [https://doi.org/10.1038/217709a0]",
+    );
+    check!(
+        CitationStyle::AuthorYear,
+        "(Bell and Hewish 1968)",
+        "Bell, Jocelyn, and Antony Hewish. 1968. <Observation of a Rapidly Pulsating Radio Source>. [https://doi.org/10.1038/217709a0]",
+    );
+    check!(
+        CitationStyle::Apa,
+        "(Bell & Hewish, 1968)",
+        "Bell, J., & Hewish, A. (1968). <Observation of a Rapidly Pulsating Radio Source>. [https://doi.org/10.1038/217709a0]",
+    );
+
+    // Entries without structured data are cited by label
+    let contents = BibliographyContents::Elements(vec![]);
+    assert_eq!(CitationStyle::Apa.cite_text(1, "guy", &contents), "(guy)");
+    assert_eq!(CitationStyle::Numeric.cite_text(1, "guy", &contents), "1");
+}
+
+#[test]
+fn parse_style() {
+    assert_eq!("numeric".parse(), Ok(CitationStyle::Numeric));
+    assert_eq!("Author-Year".parse(), Ok(CitationStyle::AuthorYear));
+    assert_eq!("APA".parse(), Ok(CitationStyle::Apa));
+    assert_eq!("chicago".parse::<CitationStyle>(), Err(()));
+}
@@ -0,0 +1,147 @@
+/*
+ * tree/button.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use strum_macros::EnumIter;
+
+/// A page action which can be performed by a `[[button]]`.
+///
+/// These are the actions from the options bar at the bottom of Wikidot pages.
+/// The renderer only emits markup describing which action a button performs,
+/// it is up to the host to bind them to any behavior.
+#[derive(EnumIter, Serialize, Deserialize, Debug, Hash, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PageAction {
+    Edit,
+    EditAppend,
+    EditSections,
+    History,
+    Print,
+    Files,
+    Tags,
+    Source,
+    Backlinks,
+    Talk,
+    Delete,
+    Rename,
+    SiteTools,
+    EditMeta,
+    Watchers,
+    Parent,
+    LockPage,
+}
+
+impl PageAction {
+    pub fn name(self) -> &'static str {
+        match self {
+            PageAction::Edit => "edit",
+            PageAction::EditAppend => "edit-append",
+            PageAction::EditSections => "edit-sections",
+            PageAction::History => "history",
+            PageAction::Print => "print",
+            PageAction::Files => "files",
+            PageAction::Tags => "tags",
+            PageAction::Source => "source",
+            PageAction::Backlinks => "backlinks",
+            PageAction::Talk => "talk",
+            PageAction::Delete => "delete",
+            PageAction::Rename => "rename",
+            PageAction::SiteTools => "site-tools",
+            PageAction::EditMeta => "edit-meta",
+            PageAction::Watchers => "watchers",
+            PageAction::Parent => "parent",
+            PageAction::LockPage => "lock-page",
+        }
+    }
+
+    /// The key for this button's default label, for `Handle::get_message()`.
+    pub fn message(self) -> &'static str {
+        match self {
+            PageAction::Edit => "button-edit",
+            PageAction::EditAppend => "button-edit-append",
+            PageAction::EditSections => "button-edit-sections",
+            PageAction::History => "button-history",
+            PageAction::Print => "button-print",
+            PageAction::Files => "button-files",
+            PageAction::Tags => "button-tags",
+            PageAction::Source => "button-source",
+            PageAction::Backlinks => "button-backlinks",
+            PageAction::Talk => "button-talk",
+            PageAction::Delete => "button-delete",
+            PageAction::Rename => "button-rename",
+            PageAction::SiteTools => "button-site-tools",
+            PageAction::EditMeta => "button-edit-meta",
+            PageAction::Watchers => "button-watchers",
+            PageAction::Parent => "button-parent",
+            PageAction::LockPage => "button-lock-page",
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for PageAction {
+    type Error = &'a str;
+
+    fn try_from(value: &'a str) -> Result<PageAction, &'a str> {
+        match value {
+            "edit" => Ok(PageAction::Edit),
+            "edit-append" => Ok(PageAction::EditAppend),
+            "edit-sections" => Ok(PageAction::EditSections),
+            "history" => Ok(PageAction::History),
+            "print" => Ok(PageAction::Print),
+            "files" => Ok(PageAction::Files),
+            "tags" => Ok(PageAction::Tags),
+            "source" => Ok(PageAction::Source),
+            "backlinks" => Ok(PageAction::Backlinks),
+            "talk" => Ok(PageAction::Talk),
+            "delete" => Ok(PageAction::Delete),
+            "rename" => Ok(PageAction::Rename),
+            "site-tools" => Ok(PageAction::SiteTools),
+            "edit-meta" => Ok(PageAction::EditMeta),
+            "watchers" => Ok(PageAction::Watchers),
+            "parent" => Ok(PageAction::Parent),
+            "lock-page" => Ok(PageAction::LockPage),
+            _ => Err(value),
+        }
+    }
+}
+
+/// Ensure `PageAction::name()` produces the same output as serde.
+#[test]
+fn page_action_name_serde() {
+    use strum::IntoEnumIterator;
+
+    for variant in PageAction::iter() {
+        let output = serde_json::to_string(&variant).expect("Unable to serialize JSON");
+        let serde_name: String =
+            serde_json::from_str(&output).expect("Unable to deserialize JSON");
+
+        assert_eq!(
+            &serde_name,
+            variant.name(),
+            "Serde name does not match variant name",
+        );
+
+        let converted: PageAction = serde_name
+            .as_str()
+            .try_into()
+            .expect("Could not convert item");
+
+        assert_eq!(converted, variant, "Converted item does not match variant");
+    }
+}
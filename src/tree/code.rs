@@ -38,4 +38,58 @@ impl CodeBlock<'_> {
             name: option_string_to_owned(&self.name),
         }
     }
+
+    /// Suggests a MIME type for serving this code block as a file.
+    ///
+    /// This is based on the language given in `type`, falling
+    /// back to `text/plain` for unknown or absent languages.
+    pub fn mime_type(&self) -> &'static str {
+        let language = match &self.language {
+            Some(language) => language.as_ref(),
+            None => return "text/plain",
+        };
+
+        match language {
+            "css" => "text/css",
+            "html" | "htm" => "text/html",
+            "js" | "javascript" | "mjs" => "text/javascript",
+            "json" => "application/json",
+            "xml" => "application/xml",
+            "svg" => "image/svg+xml",
+            "md" | "markdown" => "text/markdown",
+            "csv" => "text/csv",
+            "yaml" | "yml" => "application/yaml",
+            "toml" => "application/toml",
+            "py" | "python" => "text/x-python",
+            "php" => "application/x-httpd-php",
+            "sh" | "bash" => "application/x-sh",
+            _ => "text/plain",
+        }
+    }
+}
+
+#[test]
+fn mime_type() {
+    macro_rules! test {
+        ($language:expr, $expected:expr $(,)?) => {{
+            let code_block = CodeBlock {
+                contents: cow!(""),
+                language: $language.map(Cow::Borrowed),
+                name: None,
+            };
+
+            assert_eq!(
+                code_block.mime_type(),
+                $expected,
+                "MIME type for language {:?} doesn't match expected",
+                $language,
+            );
+        }};
+    }
+
+    test!(None::<&str>, "text/plain");
+    test!(Some("css"), "text/css");
+    test!(Some("javascript"), "text/javascript");
+    test!(Some("json"), "application/json");
+    test!(Some("fortran"), "text/plain");
 }
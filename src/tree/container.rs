@@ -23,7 +23,7 @@
 use super::clone::elements_to_owned;
 use super::{Alignment, AttributeMap, Element, Heading, HtmlTag};
 use crate::layout::Layout;
-use crate::next_index::{NextIndex, TableOfContentsIndex};
+use crate::next_index::NextHeadingId;
 use strum_macros::IntoStaticStr;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -125,7 +125,7 @@ impl ContainerType {
     pub fn html_tag(
         self,
         layout: Layout,
-        indexer: &mut dyn NextIndex<TableOfContentsIndex>,
+        heading_ids: &mut dyn NextHeadingId,
     ) -> HtmlTag {
         // TODO add wikidot compat
         match self {
@@ -152,7 +152,7 @@ impl ContainerType {
                 Layout::Wikidot => HtmlTag::with_style("div", alignment.wd_html_style()),
                 Layout::Wikijump => HtmlTag::with_class("div", alignment.wj_html_class()),
             },
-            ContainerType::Header(heading) => heading.html_tag(indexer),
+            ContainerType::Header(heading) => heading.html_tag(heading_ids),
         }
     }
 
@@ -22,12 +22,14 @@ use crate::data::PageRef;
 use crate::tree::clone::*;
 use crate::tree::{
     Alignment, AnchorTarget, AttributeMap, ClearFloat, CodeBlock, Container, DateItem,
-    DefinitionListItem, Embed, FileSource, FloatAlignment, LinkLabel, LinkLocation,
-    LinkType, ListItem, ListType, Module, PartialElement, Tab, Table, VariableMap,
+    DefinitionListItem, Embed, FileSource, FloatAlignment, GalleryImage, GalleryOrder,
+    GallerySize, LinkLabel, LinkLocation, LinkType, ListItem, ListType, Module,
+    PageAction, PartialElement, Tab, Table, VariableMap,
 };
 use ref_map::*;
 use std::borrow::Cow;
-use std::num::NonZeroU32;
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::ops::Range;
 
 /// Represents an element to be rendered.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -139,6 +141,33 @@ pub enum Element<'t> {
         attributes: AttributeMap<'t>,
     },
 
+    /// A gallery of images, shown as a grid of thumbnails.
+    ///
+    /// If "images" is `None`, then all the files attached to the page are shown.
+    Gallery {
+        images: Option<Vec<GalleryImage<'t>>>,
+        size: GallerySize,
+        order: Option<GalleryOrder>,
+        attributes: AttributeMap<'t>,
+    },
+
+    /// A link to a file attached to a page.
+    ///
+    /// If no label is given, the file name is used.
+    File {
+        source: FileSource<'t>,
+        label: Option<Cow<'t, str>>,
+    },
+
+    /// A button which performs an action on the current page.
+    ///
+    /// If no label is given, a default for the action is used.
+    Button {
+        action: PageAction,
+        label: Option<Cow<'t, str>>,
+        attributes: AttributeMap<'t>,
+    },
+
     /// An ordered or unordered list.
     List {
         #[serde(rename = "type")]
@@ -201,6 +230,16 @@ pub enum Element<'t> {
     /// It is indirectly preserved as the index of the `footnotes` list in the syntax tree.
     Footnote,
 
+    /// A repeated reference to a named footnote.
+    ///
+    /// This is produced by `[[footnote ref="name"]]`, and links to the
+    /// footnote with the given (one-indexed) index, rather than creating
+    /// a new one.
+    FootnoteRef {
+        name: Cow<'t, str>,
+        index: NonZeroUsize,
+    },
+
     /// A footnote block, containing all the footnotes from throughout the page.
     ///
     /// If a `[[footnoteblock]]` is not added somewhere in the content of the page,
@@ -268,9 +307,13 @@ pub enum Element<'t> {
     Embed(Embed<'t>),
 
     /// Element containing a sandboxed HTML block.
+    ///
+    /// The span is the byte range of the contents in the parsed text,
+    /// for warnings which can only be produced while rendering.
     Html {
         contents: Cow<'t, str>,
         attributes: AttributeMap<'t>,
+        span: Range<usize>,
     },
 
     /// Element containing an iframe component.
@@ -352,6 +395,9 @@ impl Element<'_> {
             Element::Image { .. } => "Image",
             Element::Audio { .. } => "Audio",
             Element::Video { .. } => "Video",
+            Element::Gallery { .. } => "Gallery",
+            Element::File { .. } => "File",
+            Element::Button { .. } => "Button",
             Element::List { .. } => "List",
             Element::DefinitionList(_) => "DefinitionList",
             Element::RadioButton { .. } => "RadioButton",
@@ -359,6 +405,7 @@ impl Element<'_> {
             Element::Collapsible { .. } => "Collapsible",
             Element::TableOfContents { .. } => "TableOfContents",
             Element::Footnote => "Footnote",
+            Element::FootnoteRef { .. } => "FootnoteRef",
             Element::FootnoteBlock { .. } => "FootnoteBlock",
             Element::BibliographyCite { .. } => "BibliographyCite",
             Element::BibliographyBlock { .. } => "BibliographyBlock",
@@ -407,12 +454,15 @@ impl Element<'_> {
             Element::Image { .. } => true,
             Element::Audio { .. } => true,
             Element::Video { .. } => true,
+            Element::Gallery { .. } => false,
+            Element::File { .. } | Element::Button { .. } => true,
             Element::List { .. } => false,
             Element::DefinitionList(_) => false,
             Element::RadioButton { .. } | Element::CheckBox { .. } => true,
             Element::Collapsible { .. } => false,
             Element::TableOfContents { .. } => false,
             Element::Footnote => true,
+            Element::FootnoteRef { .. } => true,
             Element::FootnoteBlock { .. } => false,
             Element::BibliographyCite { .. } => true,
             Element::BibliographyBlock { .. } => false,
@@ -512,6 +562,32 @@ impl Element<'_> {
                 alignment: *alignment,
                 attributes: attributes.to_owned(),
             },
+            Element::Gallery {
+                images,
+                size,
+                order,
+                attributes,
+            } => Element::Gallery {
+                images: images.ref_map(|images| {
+                    images.iter().map(GalleryImage::to_owned).collect()
+                }),
+                size: *size,
+                order: *order,
+                attributes: attributes.to_owned(),
+            },
+            Element::File { source, label } => Element::File {
+                source: source.to_owned(),
+                label: option_string_to_owned(label),
+            },
+            Element::Button {
+                action,
+                label,
+                attributes,
+            } => Element::Button {
+                action: *action,
+                label: option_string_to_owned(label),
+                attributes: attributes.to_owned(),
+            },
             Element::DefinitionList(items) => Element::DefinitionList(
                 items.iter().map(|item| item.to_owned()).collect(),
             ),
@@ -553,6 +629,10 @@ impl Element<'_> {
                 attributes: attributes.to_owned(),
             },
             Element::Footnote => Element::Footnote,
+            Element::FootnoteRef { name, index } => Element::FootnoteRef {
+                name: string_to_owned(name),
+                index: *index,
+            },
             Element::FootnoteBlock { title, hide } => Element::FootnoteBlock {
                 title: option_string_to_owned(title),
                 hide: *hide,
@@ -600,9 +680,11 @@ impl Element<'_> {
             Element::Html {
                 contents,
                 attributes,
+                span,
             } => Element::Html {
                 contents: string_to_owned(contents),
                 attributes: attributes.to_owned(),
+                span: Range::clone(span),
             },
             Element::Iframe { url, attributes } => Element::Iframe {
                 url: string_to_owned(url),
@@ -19,7 +19,9 @@
  */
 
 use super::clone::string_to_owned;
+use crate::settings::EmbedSettings;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case", tag = "embed", content = "data")]
@@ -37,6 +39,14 @@ pub enum Embed<'t> {
 
     #[serde(rename_all = "kebab-case")]
     GitlabSnippet { snippet_id: Cow<'t, str> },
+
+    /// An embed from a provider registered in the settings.
+    ///
+    /// See [`EmbedSettings`](crate::settings::EmbedSettings).
+    Custom {
+        provider: Cow<'t, str>,
+        arguments: BTreeMap<Cow<'t, str>, Cow<'t, str>>,
+    },
 }
 
 impl Embed<'_> {
@@ -46,22 +56,55 @@ impl Embed<'_> {
             Embed::Vimeo { .. } => "Vimeo",
             Embed::GithubGist { .. } => "GithubGist",
             Embed::GitlabSnippet { .. } => "GitlabSnippet",
+            Embed::Custom { .. } => "Custom",
         }
     }
 
-    pub fn direct_url(&self) -> String {
+    /// The name of the embed provider which handles this embed.
+    pub fn provider(&self) -> &str {
         match self {
-            Embed::Youtube { video_id } => format!("https://youtu.be/{video_id}"),
-            Embed::Vimeo { video_id } => format!("https://vimeo.com/{video_id}"),
-            Embed::GithubGist { username, hash } => {
-                format!("https://gist.github.com/{username}/{hash}")
+            Embed::Youtube { .. } => "youtube",
+            Embed::Vimeo { .. } => "vimeo",
+            Embed::GithubGist { .. } => "github-gist",
+            Embed::GitlabSnippet { .. } => "gitlab-snippet",
+            Embed::Custom { provider, .. } => provider,
+        }
+    }
+
+    /// The arguments for this embed, by the names the provider uses.
+    pub fn arguments(&self) -> Vec<(&str, &str)> {
+        match self {
+            Embed::Youtube { video_id } | Embed::Vimeo { video_id } => {
+                vec![("video", video_id)]
             }
-            Embed::GitlabSnippet { snippet_id } => {
-                format!("https://gitlab.com/-/snippets/{snippet_id}")
+            Embed::GithubGist { username, hash } => {
+                vec![("username", username), ("hash", hash)]
             }
+            Embed::GitlabSnippet { snippet_id } => vec![("id", snippet_id)],
+            Embed::Custom { arguments, .. } => arguments
+                .iter()
+                .map(|(key, value)| (key.as_ref(), value.as_ref()))
+                .collect(),
         }
     }
 
+    /// Builds a link to this embed's content, using its provider's fallback URL.
+    ///
+    /// Returns `None` if the provider isn't registered in these settings.
+    pub fn fallback_url(&self, settings: &EmbedSettings) -> Option<String> {
+        let provider = settings.get(self.provider())?;
+        let arguments = self.arguments();
+        Some(provider.build_fallback(arguments.iter().copied()))
+    }
+
+    /// Builds a link to this embed's content, using only the built-in providers.
+    ///
+    /// Embeds from other providers produce an empty string.
+    #[deprecated(note = "use `fallback_url()` with the settings' embed providers")]
+    pub fn direct_url(&self) -> String {
+        self.fallback_url(&EmbedSettings::new()).unwrap_or_default()
+    }
+
     pub fn to_owned(&self) -> Embed<'static> {
         match self {
             Embed::Youtube { video_id } => Embed::Youtube {
@@ -80,6 +123,17 @@ impl Embed<'_> {
             Embed::GitlabSnippet { snippet_id } => Embed::GitlabSnippet {
                 snippet_id: string_to_owned(snippet_id),
             },
+
+            Embed::Custom {
+                provider,
+                arguments,
+            } => Embed::Custom {
+                provider: string_to_owned(provider),
+                arguments: arguments
+                    .iter()
+                    .map(|(key, value)| (string_to_owned(key), string_to_owned(value)))
+                    .collect(),
+            },
         }
     }
 }
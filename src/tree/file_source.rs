@@ -83,6 +83,18 @@ impl<'t> FileSource<'t> {
         self.into()
     }
 
+    /// Gets the name of the file, without any page or site.
+    ///
+    /// For URLs, this is the last segment of the path.
+    pub fn file_name(&self) -> &str {
+        match self {
+            FileSource::Url(url) => url.rsplit('/').next().unwrap_or(url),
+            FileSource::File1 { file }
+            | FileSource::File2 { file, .. }
+            | FileSource::File3 { file, .. } => file,
+        }
+    }
+
     pub fn to_owned(&self) -> FileSource<'static> {
         match self {
             FileSource::Url(url) => FileSource::Url(string_to_owned(url)),
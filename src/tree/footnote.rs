@@ -0,0 +1,113 @@
+/*
+ * tree/footnote.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use std::num::NonZeroUsize;
+
+/// Tracks citations of each footnote during rendering.
+///
+/// A footnote is cited once by its definition, and then once more
+/// for each `[[footnote ref="..."]]` which refers to it by name.
+/// Footnotes with multiple citations get one back-link per citation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FootnoteCitations {
+    totals: Vec<usize>,
+    seen: Vec<usize>,
+}
+
+impl FootnoteCitations {
+    pub fn new(footnote_count: usize, footnote_refs: &[NonZeroUsize]) -> Self {
+        let mut totals = vec![1; footnote_count];
+
+        for index in footnote_refs {
+            if let Some(total) = totals.get_mut(index.get() - 1) {
+                *total += 1;
+            }
+        }
+
+        FootnoteCitations {
+            seen: vec![0; footnote_count],
+            totals,
+        }
+    }
+
+    /// Returns how many times this footnote is cited on the page.
+    pub fn total(&self, index: NonZeroUsize) -> usize {
+        self.totals.get(index.get() - 1).copied().unwrap_or(1)
+    }
+
+    /// Records a citation of this footnote, returning its zero-indexed occurrence.
+    pub fn next(&mut self, index: NonZeroUsize) -> usize {
+        match self.seen.get_mut(index.get() - 1) {
+            Some(seen) => {
+                let occurrence = *seen;
+                *seen += 1;
+                occurrence
+            }
+            None => 0,
+        }
+    }
+}
+
+/// Gets the back-link letter for a zero-indexed citation occurrence.
+///
+/// These go `a` through `z`, then `aa`, `ab`, and so on.
+pub fn footnote_citation_letter(mut occurrence: usize) -> String {
+    let mut letters = Vec::new();
+
+    loop {
+        letters.push(char::from(b'a' + (occurrence % 26) as u8));
+
+        if occurrence < 26 {
+            break;
+        }
+
+        occurrence = occurrence / 26 - 1;
+    }
+
+    letters.iter().rev().collect()
+}
+
+#[test]
+fn citations() {
+    macro_rules! index {
+        ($value:expr) => {
+            NonZeroUsize::new($value).unwrap()
+        };
+    }
+
+    let mut citations = FootnoteCitations::new(3, &[index!(2), index!(2), index!(3)]);
+
+    assert_eq!(citations.total(index!(1)), 1);
+    assert_eq!(citations.total(index!(2)), 3);
+    assert_eq!(citations.total(index!(3)), 2);
+    assert_eq!(citations.total(index!(8)), 1);
+
+    assert_eq!(citations.next(index!(2)), 0);
+    assert_eq!(citations.next(index!(1)), 0);
+    assert_eq!(citations.next(index!(2)), 1);
+    assert_eq!(citations.next(index!(2)), 2);
+
+    assert_eq!(footnote_citation_letter(0), "a");
+    assert_eq!(footnote_citation_letter(2), "c");
+    assert_eq!(footnote_citation_letter(25), "z");
+    assert_eq!(footnote_citation_letter(26), "aa");
+    assert_eq!(footnote_citation_letter(27), "ab");
+    assert_eq!(footnote_citation_letter(52), "ba");
+}
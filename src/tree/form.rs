@@ -0,0 +1,465 @@
+/*
+ * tree/form.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Structures to represent a data form, defined by a `[[form]]` block.
+//!
+//! In Wikidot, a category's `_template` page may define a form, which lists
+//! the fields each page in the category has. The definition uses a subset of
+//! YAML:
+//!
+//! ```text
+//! fields:
+//!   name:
+//!     label: Name
+//!     type: text
+//!   class:
+//!     label: Object Class
+//!     type: select
+//!     values:
+//!       safe: Safe
+//!       euclid: Euclid
+//! ```
+//!
+//! Read <https://www.wikidot.com/doc:data-forms> for more details.
+
+use super::clone::{option_string_to_owned, string_to_owned};
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// A data form definition, listing the fields of the pages using it.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Form<'t> {
+    pub fields: Vec<FormField<'t>>,
+}
+
+/// A single field within a data form.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct FormField<'t> {
+    /// The name of this field, as used in `%%form_data{name}%%`.
+    pub name: Cow<'t, str>,
+
+    /// The human-readable label for this field.
+    ///
+    /// If not specified, this is the same as the name.
+    pub label: Cow<'t, str>,
+
+    /// What kind of value this field holds.
+    #[serde(flatten)]
+    pub field_type: FormFieldType<'t>,
+
+    /// The value used if a page does not specify one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Cow<'t, str>>,
+
+    /// A description shown when editing this field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<Cow<'t, str>>,
+}
+
+/// The type of a data form field, and any settings specific to it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum FormFieldType<'t> {
+    /// Plain text, displayed as-is.
+    Text,
+
+    /// Wikitext, which is parsed when displayed.
+    Wiki,
+
+    /// One of a fixed set of options.
+    Select { options: Vec<FormOption<'t>> },
+
+    /// The name of a page, displayed as a link.
+    ///
+    /// If a category is specified, names without one are taken to be within it.
+    Page {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        category: Option<Cow<'t, str>>,
+    },
+
+    /// A date, in any format accepted by `[[date]]`.
+    Date,
+}
+
+/// One of the options in a select field.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct FormOption<'t> {
+    /// The value stored for this option.
+    pub value: Cow<'t, str>,
+
+    /// The label displayed for this option.
+    pub label: Cow<'t, str>,
+}
+
+/// An error in a form definition.
+///
+/// The span is relative to the start of the definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormSyntaxError {
+    pub span: Range<usize>,
+}
+
+impl<'t> Form<'t> {
+    /// Parses a form definition, the body of a `[[form]]` block.
+    pub fn parse(definition: &'t str) -> Result<Self, FormSyntaxError> {
+        let lines = split_lines(definition)?;
+        let mut index = 0;
+        let root = parse_map(&lines, &mut index, 0)?;
+
+        // Fields are the only top-level key we use, others are ignored
+        let fields = match root.iter().find(|entry| entry.key == "fields") {
+            Some(entry) => entry.as_map()?,
+            None => return Ok(Form::default()),
+        };
+
+        let fields = fields
+            .iter()
+            .map(FormField::from_entry)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Form { fields })
+    }
+
+    /// Gets the field with the given name, if it exists.
+    pub fn get(&self, name: &str) -> Option<&FormField<'t>> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    pub fn to_owned(&self) -> Form<'static> {
+        Form {
+            fields: self.fields.iter().map(FormField::to_owned).collect(),
+        }
+    }
+}
+
+impl<'t> FormField<'t> {
+    fn from_entry(entry: &Entry<'t>) -> Result<Self, FormSyntaxError> {
+        let properties = entry.as_map()?;
+        let get = |key: &str| {
+            properties
+                .iter()
+                .find(|property| property.key == key)
+                .map(Entry::as_scalar)
+                .transpose()
+        };
+
+        let field_type = match properties.iter().find(|property| property.key == "type") {
+            None => FormFieldType::Text,
+            Some(property) => match property.as_scalar()? {
+                "text" => FormFieldType::Text,
+                "wiki" => FormFieldType::Wiki,
+                "date" => FormFieldType::Date,
+                "page" => FormFieldType::Page {
+                    category: get("category")?.map(Cow::Borrowed),
+                },
+                "select" => {
+                    let values = properties
+                        .iter()
+                        .find(|property| property.key == "values")
+                        .ok_or_else(|| property.error())?;
+
+                    let options = values
+                        .as_map()?
+                        .iter()
+                        .map(|option| {
+                            Ok(FormOption {
+                                value: cow!(option.key),
+                                label: cow!(option.as_scalar()?),
+                            })
+                        })
+                        .collect::<Result<_, _>>()?;
+
+                    FormFieldType::Select { options }
+                }
+                _ => return Err(property.error()),
+            },
+        };
+
+        Ok(FormField {
+            name: cow!(entry.key),
+            label: cow!(get("label")?.unwrap_or(entry.key)),
+            field_type,
+            default: get("default")?.map(Cow::Borrowed),
+            hint: get("hint")?.map(Cow::Borrowed),
+        })
+    }
+
+    pub fn to_owned(&self) -> FormField<'static> {
+        FormField {
+            name: string_to_owned(&self.name),
+            label: string_to_owned(&self.label),
+            field_type: match &self.field_type {
+                FormFieldType::Text => FormFieldType::Text,
+                FormFieldType::Wiki => FormFieldType::Wiki,
+                FormFieldType::Date => FormFieldType::Date,
+                FormFieldType::Page { category } => FormFieldType::Page {
+                    category: option_string_to_owned(category),
+                },
+                FormFieldType::Select { options } => FormFieldType::Select {
+                    options: options
+                        .iter()
+                        .map(|option| FormOption {
+                            value: string_to_owned(&option.value),
+                            label: string_to_owned(&option.label),
+                        })
+                        .collect(),
+                },
+            },
+            default: option_string_to_owned(&self.default),
+            hint: option_string_to_owned(&self.hint),
+        }
+    }
+}
+
+// YAML subset parsing
+
+/// A `key: value` line in the definition.
+#[derive(Debug)]
+struct Line<'t> {
+    indent: usize,
+    key: &'t str,
+    value: &'t str,
+    span: Range<usize>,
+}
+
+/// A key with either a scalar value, or a nested map.
+#[derive(Debug)]
+struct Entry<'t> {
+    key: &'t str,
+    value: Value<'t>,
+    span: Range<usize>,
+}
+
+#[derive(Debug)]
+enum Value<'t> {
+    Scalar(&'t str),
+    Map(Vec<Entry<'t>>),
+}
+
+impl<'t> Entry<'t> {
+    fn error(&self) -> FormSyntaxError {
+        FormSyntaxError {
+            span: Range::clone(&self.span),
+        }
+    }
+
+    fn as_scalar(&self) -> Result<&'t str, FormSyntaxError> {
+        match self.value {
+            Value::Scalar(value) => Ok(value),
+            Value::Map(_) => Err(self.error()),
+        }
+    }
+
+    fn as_map(&self) -> Result<&[Entry<'t>], FormSyntaxError> {
+        match &self.value {
+            Value::Map(entries) => Ok(entries),
+            Value::Scalar(_) => Err(self.error()),
+        }
+    }
+}
+
+fn split_lines(definition: &str) -> Result<Vec<Line<'_>>, FormSyntaxError> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+
+    for line in definition.split_inclusive('\n') {
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        let span = start + indent..start + line.trim_end().len();
+        start += line.len();
+
+        // Skip blank lines and comments
+        let content = line.trim();
+        if content.is_empty() || content.starts_with('#') {
+            continue;
+        }
+
+        // Only spaces may be used for indentation
+        let (key, value) = match content.split_once(':') {
+            Some((key, value)) if !key.is_empty() && content.len() == span.len() => {
+                (key.trim_end(), unquote(value.trim()))
+            }
+            _ => return Err(FormSyntaxError { span }),
+        };
+
+        lines.push(Line {
+            indent,
+            key: unquote(key),
+            value,
+            span,
+        });
+    }
+
+    Ok(lines)
+}
+
+fn parse_map<'t>(
+    lines: &[Line<'t>],
+    index: &mut usize,
+    indent: usize,
+) -> Result<Vec<Entry<'t>>, FormSyntaxError> {
+    let mut entries = Vec::new();
+
+    while let Some(line) = lines.get(*index) {
+        if line.indent < indent {
+            break;
+        }
+
+        if line.indent > indent {
+            // Indented without a parent key
+            return Err(FormSyntaxError {
+                span: Range::clone(&line.span),
+            });
+        }
+
+        *index += 1;
+
+        // An empty value with indented lines after is a nested map
+        let value = match lines.get(*index) {
+            Some(next) if line.value.is_empty() && next.indent > indent => {
+                Value::Map(parse_map(lines, index, next.indent)?)
+            }
+            _ => Value::Scalar(line.value),
+        };
+
+        entries.push(Entry {
+            key: line.key,
+            value,
+            span: Range::clone(&line.span),
+        });
+    }
+
+    Ok(entries)
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return &value[1..value.len() - 1];
+        }
+    }
+
+    value
+}
+
+#[test]
+fn parse() {
+    let definition = "\
+# Fields for SCP articles
+fields:
+  item:
+    label: Item #
+    hint: \"Such as: SCP-173\"
+  class:
+    label: Object Class
+    type: select
+    values:
+      safe: Safe
+      euclid: Euclid
+    default: safe
+
+  description:
+    type: wiki
+  author:
+    type: page
+    category: user
+  created:
+    type: date
+";
+
+    let form = Form::parse(definition).expect("Unable to parse form");
+    assert_eq!(
+        form,
+        Form {
+            fields: vec![
+                FormField {
+                    name: cow!("item"),
+                    label: cow!("Item #"),
+                    field_type: FormFieldType::Text,
+                    default: None,
+                    hint: Some(cow!("Such as: SCP-173")),
+                },
+                FormField {
+                    name: cow!("class"),
+                    label: cow!("Object Class"),
+                    field_type: FormFieldType::Select {
+                        options: vec![
+                            FormOption {
+                                value: cow!("safe"),
+                                label: cow!("Safe"),
+                            },
+                            FormOption {
+                                value: cow!("euclid"),
+                                label: cow!("Euclid"),
+                            },
+                        ],
+                    },
+                    default: Some(cow!("safe")),
+                    hint: None,
+                },
+                FormField {
+                    name: cow!("description"),
+                    label: cow!("description"),
+                    field_type: FormFieldType::Wiki,
+                    default: None,
+                    hint: None,
+                },
+                FormField {
+                    name: cow!("author"),
+                    label: cow!("author"),
+                    field_type: FormFieldType::Page {
+                        category: Some(cow!("user")),
+                    },
+                    default: None,
+                    hint: None,
+                },
+                FormField {
+                    name: cow!("created"),
+                    label: cow!("created"),
+                    field_type: FormFieldType::Date,
+                    default: None,
+                    hint: None,
+                },
+            ],
+        },
+    );
+
+    assert!(form.get("class").is_some());
+    assert!(form.get("missing").is_none());
+}
+
+#[test]
+fn parse_errors() {
+    macro_rules! check {
+        ($definition:expr, $span:expr $(,)?) => {{
+            let error = Form::parse($definition).expect_err("Malformed form parsed");
+            assert_eq!(error.span, $span, "Wrong span for form {:?}", $definition);
+        }};
+    }
+
+    check!("fields:\n  name\n", 10..14);
+    check!("fields:\n  name:\n    type: number\n", 20..32);
+    check!("fields:\n  name:\n    type: select\n", 20..32);
+    check!("fields:\n  name:\n      label: A\n    type: text\n", 35..45);
+    check!("fields: none\n", 0..12);
+    check!("fields:\n  name: text\n", 10..20);
+}
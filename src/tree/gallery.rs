@@ -0,0 +1,241 @@
+/*
+ * tree/gallery.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::clone::option_string_to_owned;
+use super::{FileSource, LinkLocation};
+use ref_map::*;
+use std::borrow::Cow;
+use std::str::FromStr;
+
+/// An image shown in a `[[gallery]]`.
+#[derive(Serialize, Deserialize, Debug, Hash, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct GalleryImage<'t> {
+    pub source: FileSource<'t>,
+    pub link: Option<LinkLocation<'t>>,
+    pub caption: Option<Cow<'t, str>>,
+}
+
+impl GalleryImage<'_> {
+    /// Gets the file name of this image, used for sorting.
+    #[inline]
+    pub fn file_name(&self) -> &str {
+        self.source.file_name()
+    }
+
+    pub fn to_owned(&self) -> GalleryImage<'static> {
+        GalleryImage {
+            source: self.source.to_owned(),
+            link: self.link.ref_map(|link| link.to_owned()),
+            caption: option_string_to_owned(&self.caption),
+        }
+    }
+}
+
+/// Gets the images to show in a gallery, in display order.
+///
+/// If the gallery has no explicit list of images, then the files
+/// attached to the page are used instead, as returned by `get_files`.
+pub fn gallery_images<'t, F>(
+    images: Option<&[GalleryImage<'t>]>,
+    order: Option<GalleryOrder>,
+    get_files: F,
+) -> Vec<GalleryImage<'t>>
+where
+    F: FnOnce() -> Vec<String>,
+{
+    let mut images = match images {
+        Some(images) => images.to_vec(),
+        None => get_files()
+            .into_iter()
+            .map(|file| GalleryImage {
+                source: FileSource::File1 {
+                    file: Cow::Owned(file),
+                },
+                link: None,
+                caption: None,
+            })
+            .collect(),
+    };
+
+    if let Some(order) = order {
+        order.sort(&mut images);
+    }
+
+    images
+}
+
+/// How large the thumbnails in a gallery are.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Default, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GallerySize {
+    /// Cropped to 75 by 75 pixels.
+    Square,
+
+    /// At most 100 pixels on the longest side.
+    #[default]
+    Thumbnail,
+
+    /// At most 240 pixels on the longest side.
+    Small,
+
+    /// At most 500 pixels on the longest side.
+    Medium,
+}
+
+impl GallerySize {
+    pub fn name(self) -> &'static str {
+        match self {
+            GallerySize::Square => "square",
+            GallerySize::Thumbnail => "thumbnail",
+            GallerySize::Small => "small",
+            GallerySize::Medium => "medium",
+        }
+    }
+}
+
+impl FromStr for GallerySize {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s.to_ascii_lowercase().as_str() {
+            "square" => Ok(GallerySize::Square),
+            "thumbnail" => Ok(GallerySize::Thumbnail),
+            "small" => Ok(GallerySize::Small),
+            "medium" => Ok(GallerySize::Medium),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The order the images in a gallery are shown in.
+///
+/// Files are assumed to be listed in the order they were uploaded.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GalleryOrder {
+    Name,
+    NameDesc,
+    Created,
+    CreatedDesc,
+}
+
+impl GalleryOrder {
+    /// Sorts a list of images according to this order.
+    pub fn sort(self, images: &mut [GalleryImage]) {
+        match self {
+            GalleryOrder::Name => images.sort_by(|a, b| a.file_name().cmp(b.file_name())),
+            GalleryOrder::NameDesc => {
+                images.sort_by(|a, b| b.file_name().cmp(a.file_name()));
+            }
+            GalleryOrder::Created => (),
+            GalleryOrder::CreatedDesc => images.reverse(),
+        }
+    }
+}
+
+impl FromStr for GalleryOrder {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        // Wikidot uses the column names, such as "created_at desc"
+        let mut parts = s.split_whitespace();
+        let column = parts.next().unwrap_or("").to_ascii_lowercase();
+        let descending = match parts.next() {
+            None => false,
+            Some(direction) if direction.eq_ignore_ascii_case("desc") => true,
+            Some(direction) if direction.eq_ignore_ascii_case("asc") => false,
+            Some(_) => return Err(()),
+        };
+
+        if parts.next().is_some() {
+            return Err(());
+        }
+
+        match (column.as_str(), descending) {
+            ("name", false) => Ok(GalleryOrder::Name),
+            ("name", true) => Ok(GalleryOrder::NameDesc),
+            ("created" | "created_at", false) => Ok(GalleryOrder::Created),
+            ("created" | "created_at", true) => Ok(GalleryOrder::CreatedDesc),
+            _ => Err(()),
+        }
+    }
+}
+
+#[test]
+fn images() {
+    let files = || vec![str!("b.png"), str!("c.png"), str!("a.png")];
+    let names = |images: Vec<GalleryImage>| {
+        images
+            .iter()
+            .map(|image| str!(image.file_name()))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        names(gallery_images(None, None, files)),
+        ["b.png", "c.png", "a.png"]
+    );
+    assert_eq!(
+        names(gallery_images(None, Some(GalleryOrder::Name), files)),
+        ["a.png", "b.png", "c.png"],
+    );
+    assert_eq!(
+        names(gallery_images(None, Some(GalleryOrder::CreatedDesc), files)),
+        ["a.png", "c.png", "b.png"],
+    );
+
+    let listed = [
+        GalleryImage {
+            source: FileSource::Url(cow!("https://example.com/images/z.jpg")),
+            link: None,
+            caption: None,
+        },
+        GalleryImage {
+            source: FileSource::File2 {
+                page: cow!("scp-001"),
+                file: cow!("y.jpg"),
+            },
+            link: None,
+            caption: Some(cow!("Caption")),
+        },
+    ];
+
+    assert_eq!(
+        names(gallery_images(
+            Some(&listed),
+            Some(GalleryOrder::NameDesc),
+            || { panic!("Files fetched for explicit list") }
+        )),
+        ["z.jpg", "y.jpg"],
+    );
+}
+
+#[test]
+fn parse_order() {
+    assert_eq!("name".parse(), Ok(GalleryOrder::Name));
+    assert_eq!("Name DESC".parse(), Ok(GalleryOrder::NameDesc));
+    assert_eq!("created_at".parse(), Ok(GalleryOrder::Created));
+    assert_eq!("created_at asc".parse(), Ok(GalleryOrder::Created));
+    assert_eq!("created desc".parse(), Ok(GalleryOrder::CreatedDesc));
+    assert_eq!("size".parse::<GalleryOrder>(), Err(()));
+    assert_eq!("name sideways".parse::<GalleryOrder>(), Err(()));
+    assert_eq!("".parse::<GalleryOrder>(), Err(()));
+}
@@ -19,7 +19,7 @@
  */
 
 use super::HtmlTag;
-use crate::next_index::{NextIndex, TableOfContentsIndex};
+use crate::next_index::NextHeadingId;
 use std::convert::TryFrom;
 
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -37,13 +37,12 @@ pub struct Heading {
 }
 
 impl Heading {
-    pub fn html_tag(self, indexer: &mut dyn NextIndex<TableOfContentsIndex>) -> HtmlTag {
+    pub fn html_tag(self, ids: &mut dyn NextHeadingId) -> HtmlTag {
         let tag = self.level.html_tag();
 
         if self.has_toc
-            && let Some(index) = indexer.next()
+            && let Some(id) = ids.next_heading_id()
         {
-            let id = format!("toc{index}");
             HtmlTag::with_id(tag, id)
         } else {
             HtmlTag::new(tag)
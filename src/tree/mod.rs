@@ -23,6 +23,7 @@ pub mod attribute;
 mod align;
 mod anchor;
 mod bibliography;
+mod button;
 mod clear_float;
 mod clone;
 mod code;
@@ -32,12 +33,16 @@ mod definition_list;
 mod element;
 mod embed;
 mod file_source;
+mod footnote;
+mod form;
+mod gallery;
 mod heading;
 mod link;
 mod list;
 mod module;
 mod partial;
 mod ruby;
+mod section;
 mod tab;
 mod table;
 mod tag;
@@ -47,6 +52,7 @@ pub use self::align::*;
 pub use self::anchor::*;
 pub use self::attribute::AttributeMap;
 pub use self::bibliography::*;
+pub use self::button::*;
 pub use self::clear_float::*;
 pub use self::code::CodeBlock;
 pub use self::container::*;
@@ -55,12 +61,16 @@ pub use self::definition_list::*;
 pub use self::element::*;
 pub use self::embed::*;
 pub use self::file_source::*;
+pub use self::footnote::*;
+pub use self::form::*;
+pub use self::gallery::*;
 pub use self::heading::*;
 pub use self::link::*;
 pub use self::list::*;
 pub use self::module::*;
 pub use self::partial::*;
 pub use self::ruby::*;
+pub use self::section::*;
 pub use self::tab::*;
 pub use self::table::*;
 pub use self::tag::*;
@@ -69,6 +79,7 @@ pub use self::variables::*;
 use self::clone::{elements_lists_to_owned, elements_to_owned, string_to_owned};
 use crate::parsing::{ParseError, ParseOutcome};
 use std::borrow::Cow;
+use std::num::NonZeroUsize;
 use std::ops::Not;
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
@@ -88,6 +99,12 @@ pub struct SyntaxTree<'t> {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub table_of_contents: Vec<Element<'t>>,
 
+    /// Every heading on this page, with the span of its section.
+    ///
+    /// This includes headings which are excluded from the table of contents.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headings: Vec<HeadingSection>,
+
     /// The full list of HTML blocks for this page.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub html_blocks: Vec<Cow<'t, str>>,
@@ -100,6 +117,13 @@ pub struct SyntaxTree<'t> {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub footnotes: Vec<Vec<Element<'t>>>,
 
+    /// The footnote references for this page.
+    ///
+    /// Each entry is the index of a named footnote which was
+    /// cited again using `[[footnote ref="..."]]`, in page order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub footnote_refs: Vec<NonZeroUsize>,
+
     /// Whether the renderer should add its own footnote block.
     ///
     /// This is true if there is no footnote block in the element
@@ -113,6 +137,10 @@ pub struct SyntaxTree<'t> {
     #[serde(default, skip_serializing_if = "BibliographyList::is_empty")]
     pub bibliographies: BibliographyList<'t>,
 
+    /// The data form defined by a `[[form]]` block, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub form: Option<Form<'t>>,
+
     /// Hint for the size of the wikitext input.
     ///
     /// This is an optimization to make rendering large parges slightly faster.
@@ -125,19 +153,26 @@ impl<'t> SyntaxTree<'t> {
         elements: Vec<Element<'t>>,
         errors: Vec<ParseError>,
         (html_blocks, code_blocks): (Vec<Cow<'t, str>>, Vec<CodeBlock<'t>>),
-        table_of_contents: Vec<Element<'t>>,
-        (footnotes, needs_footnote_block): (Vec<Vec<Element<'t>>>, bool),
-        bibliographies: BibliographyList<'t>,
+        (table_of_contents, headings): (Vec<Element<'t>>, Vec<HeadingSection>),
+        (footnotes, footnote_refs, needs_footnote_block): (
+            Vec<Vec<Element<'t>>>,
+            Vec<NonZeroUsize>,
+            bool,
+        ),
+        (bibliographies, form): (BibliographyList<'t>, Option<Form<'t>>),
         wikitext_len: usize,
     ) -> ParseOutcome<Self> {
         let tree = SyntaxTree {
             elements,
             table_of_contents,
+            headings,
             html_blocks,
             code_blocks,
             footnotes,
+            footnote_refs,
             needs_footnote_block,
             bibliographies,
+            form,
             wikitext_len,
         };
         ParseOutcome::new(tree, errors)
@@ -147,6 +182,7 @@ impl<'t> SyntaxTree<'t> {
         SyntaxTree {
             elements: elements_to_owned(&self.elements),
             table_of_contents: elements_to_owned(&self.table_of_contents),
+            headings: self.headings.clone(),
             html_blocks: self
                 .html_blocks
                 .iter()
@@ -158,8 +194,10 @@ impl<'t> SyntaxTree<'t> {
                 .map(|code| code.to_owned())
                 .collect(),
             footnotes: elements_lists_to_owned(&self.footnotes),
+            footnote_refs: self.footnote_refs.clone(),
             needs_footnote_block: self.needs_footnote_block,
             bibliographies: self.bibliographies.to_owned(),
+            form: self.form.as_ref().map(Form::to_owned),
             wikitext_len: self.wikitext_len,
         }
     }
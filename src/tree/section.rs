@@ -0,0 +1,138 @@
+/*
+ * tree/section.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+use super::Heading;
+use std::ops::Range;
+
+/// A heading recognized during parsing, along with the section it begins.
+///
+/// A heading's section runs from the start of its line up to the next
+/// heading of the same or a higher level, or the end of the input.
+/// Offsets are in bytes, relative to the text which was tokenized.
+///
+/// Headings within blocks such as `[[div]]` or `[[collapsible]]` are not
+/// editable, since their sections cannot be separated from the block.
+/// Such a section also ends at the next editable heading, as the block
+/// must close before it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct HeadingSection {
+    /// The level and table of contents flag for this heading.
+    #[serde(flatten)]
+    pub heading: Heading,
+
+    /// The heading's name, rendered as plain text.
+    pub name: String,
+
+    /// The index of this heading's table of contents entry, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toc_index: Option<usize>,
+
+    /// Whether this heading is at the top level of the page.
+    ///
+    /// Only these sections can be edited independently,
+    /// see [`replace_section()`](crate::sections::replace_section).
+    pub editable: bool,
+
+    /// The byte range of this heading's section in the parsed text.
+    ///
+    /// This is the wikitext after includes and preprocessing. Use
+    /// [`map_headings()`](crate::sections::map_headings) to get
+    /// the range in the page source.
+    pub span: Range<usize>,
+}
+
+/// Sets where each heading's section ends.
+///
+/// The headings are in document order, and their spans
+/// initially only mark where each heading starts.
+pub(crate) fn set_section_ends(headings: &mut [HeadingSection], text_len: usize) {
+    for index in 0..headings.len() {
+        let level = headings[index].heading.level.value();
+        let editable = headings[index].editable;
+        let end = headings[index + 1..]
+            .iter()
+            .find(|next| {
+                let higher = next.heading.level.value() <= level;
+
+                if editable {
+                    next.editable && higher
+                } else {
+                    next.editable || higher
+                }
+            })
+            .map(|next| next.span.start)
+            .unwrap_or(text_len);
+
+        headings[index].span.end = end;
+    }
+}
+
+#[test]
+fn heading_sections() {
+    use super::HeadingLevel;
+
+    let section = |level, editable, start| HeadingSection {
+        heading: Heading {
+            level,
+            has_toc: true,
+        },
+        name: String::new(),
+        toc_index: None,
+        editable,
+        span: start..start,
+    };
+
+    let mut sections = vec![
+        section(HeadingLevel::One, true, 0),
+        section(HeadingLevel::Two, true, 10),
+        section(HeadingLevel::Three, true, 20),
+        section(HeadingLevel::Two, true, 30),
+        section(HeadingLevel::One, true, 40),
+    ];
+
+    set_section_ends(&mut sections, 50);
+
+    let spans = sections
+        .iter()
+        .map(|section| section.span.clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(spans, vec![0..40, 10..30, 20..30, 30..40, 40..50]);
+
+    // Headings within blocks don't end editable sections,
+    // but their own sections can't extend past the block.
+    let mut sections = vec![
+        section(HeadingLevel::Two, true, 0),
+        section(HeadingLevel::One, false, 10),
+        section(HeadingLevel::Three, false, 20),
+        section(HeadingLevel::Three, true, 30),
+        section(HeadingLevel::Two, true, 40),
+    ];
+
+    set_section_ends(&mut sections, 50);
+
+    let spans = sections
+        .iter()
+        .map(|section| section.span.clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(spans, vec![0..40, 10..30, 20..30, 30..40, 40..50]);
+}
@@ -19,11 +19,83 @@
  */
 
 use super::clone::string_map_to_owned;
+use crate::data::{PageInfo, ScoreValue};
+use crate::settings::WikitextSettings;
 use std::borrow::Cow;
 use std::collections::HashMap;
 
 pub type VariableMap<'t> = HashMap<Cow<'t, str>, Cow<'t, str>>;
 
+/// The names of all built-in variables.
+///
+/// These are always available when rendering, and are derived from
+/// the [`PageInfo`] of the page being rendered:
+///
+/// * `page_name` &mdash; The page slug, without its category.
+/// * `category` &mdash; The page's category, or `_default`.
+/// * `full_name` &mdash; The page slug including its category, if not `_default`.
+/// * `title` &mdash; The page's title.
+/// * `alt_title` &mdash; The page's alternate title, or its title if it has none.
+/// * `site` &mdash; The slug of the site the page is on.
+/// * `score` &mdash; The page's current score.
+/// * `tags` &mdash; The page's tags, separated by spaces.
+/// * `language` &mdash; The language the page is being rendered for.
+/// * `render_timestamp` &mdash; The time of rendering, as a Unix timestamp, if provided.
+pub const BUILTIN_VARIABLES: [&str; 10] = [
+    "page_name",
+    "category",
+    "full_name",
+    "title",
+    "alt_title",
+    "site",
+    "score",
+    "tags",
+    "language",
+    "render_timestamp",
+];
+
+/// Determines if the variable is known when not within an include.
+///
+/// That is, if it is built-in or registered in the settings.
+pub fn is_known_variable(name: &str, settings: &WikitextSettings) -> bool {
+    BUILTIN_VARIABLES.contains(&name) || settings.variables.contains_key(name)
+}
+
+/// Builds the values of all built-in variables for the given page.
+pub fn builtin_variables(info: &PageInfo) -> VariableMap<'static> {
+    let category = info.category.as_deref().unwrap_or("_default");
+    let full_name = match info.category {
+        Some(ref category) => format!("{category}:{}", info.page),
+        None => str!(info.page),
+    };
+
+    let score = match info.score {
+        ScoreValue::Integer(value) => value.to_string(),
+        ScoreValue::Float(value) => value.to_string(),
+    };
+
+    let values = [
+        str!(info.page),
+        str!(category),
+        full_name,
+        str!(info.title),
+        str!(info.alt_title.as_ref().unwrap_or(&info.title)),
+        str!(info.site),
+        score,
+        info.tags.join(" "),
+        str!(info.language),
+        info.render_timestamp
+            .map(|timestamp| timestamp.to_string())
+            .unwrap_or_default(),
+    ];
+
+    BUILTIN_VARIABLES
+        .iter()
+        .zip(values)
+        .map(|(&name, value)| (Cow::Borrowed(name), Cow::Owned(value)))
+        .collect()
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct VariableScopes {
     scopes: Vec<VariableMap<'static>>,
@@ -35,6 +107,20 @@ impl VariableScopes {
         VariableScopes::default()
     }
 
+    /// Creates a new instance with the variables available to a page.
+    ///
+    /// The bottom scope contains the built-in variables, with any
+    /// variables registered in the settings taking precedence.
+    /// Scopes pushed afterwards, such as by includes, shadow both.
+    pub fn for_page(info: &PageInfo, settings: &WikitextSettings) -> Self {
+        let mut globals = builtin_variables(info);
+        globals.extend(string_map_to_owned(&settings.variables));
+
+        VariableScopes {
+            scopes: vec![globals],
+        }
+    }
+
     pub fn get(&self, name: &str) -> Option<&str> {
         for scope in self.scopes.iter().rev() {
             if let Some(value) = scope.get(name) {
@@ -0,0 +1,251 @@
+/*
+ * wasm/includes.rs
+ *
+ * ftml - Library to parse Wikidot text
+ * Copyright (C) 2019-2026 Wikijump Team
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU Affero General Public License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with this program. If not, see <http://www.gnu.org/licenses/>.
+ */
+
+//! Exports legacy `[[include]]` processing, with pages provided by JavaScript.
+//!
+//! Since `include()` is synchronous, pages may be fetched either by a JS
+//! object with synchronous callbacks, or fetched ahead of time and passed
+//! as a map. For the latter, `include_refs()` lists the pages to fetch.
+
+use super::prelude::*;
+use super::settings::WikitextSettings;
+use crate::data::PageRef;
+use crate::includes::{FetchedPage, IncludeRef, Includer, missing_page_notice};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[wasm_bindgen(typescript_custom_section)]
+const INCLUDER_TYPES: &str = r#"
+export interface Includer {
+    include_pages(includes: IncludeRef[]): FetchedPage[];
+    no_such_include(page_ref: PageRef): string;
+}
+
+export interface PageRef {
+    site: string | null;
+    page: string;
+    extra: string | null;
+}
+
+export interface IncludeRef {
+    "page-ref": PageRef;
+    variables: Record<string, string>;
+}
+
+export interface FetchedPage {
+    "page-ref": PageRef;
+    content: string | null;
+}
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Includer")]
+    pub type JsIncluder;
+
+    #[wasm_bindgen(method, catch)]
+    fn include_pages(this: &JsIncluder, includes: JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    fn no_such_include(this: &JsIncluder, page_ref: JsValue) -> Result<JsValue, JsValue>;
+}
+
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct IncludeOutcome {
+    inner: Arc<(String, Vec<PageRef>)>,
+}
+
+#[wasm_bindgen]
+impl IncludeOutcome {
+    #[wasm_bindgen]
+    pub fn copy(&self) -> IncludeOutcome {
+        IncludeOutcome {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn text(&self) -> String {
+        self.inner.0.clone()
+    }
+
+    #[wasm_bindgen]
+    pub fn pages(&self) -> Result<JsValue, JsValue> {
+        rust_to_js!(self.inner.1)
+    }
+}
+
+// Includer implementations
+
+/// Fetches pages by calling methods on a JS object.
+#[derive(Debug)]
+struct CallbackIncluder<'a> {
+    inner: &'a JsIncluder,
+}
+
+impl<'t> Includer<'t> for CallbackIncluder<'_> {
+    type Error = JsValue;
+
+    fn include_pages(
+        &mut self,
+        includes: &[IncludeRef<'t>],
+    ) -> Result<Vec<FetchedPage<'t>>, JsValue> {
+        let includes = rust_to_js!(includes)?;
+        let pages = self.inner.include_pages(includes)?;
+        let pages: Vec<FetchedPage<'static>> = js_to_rust!(pages)?;
+        Ok(pages)
+    }
+
+    fn no_such_include(&mut self, page_ref: &PageRef) -> Result<Cow<'t, str>, JsValue> {
+        let page_ref = rust_to_js!(page_ref)?;
+        let content: String = js_to_rust!(self.inner.no_such_include(page_ref)?)?;
+        Ok(Cow::Owned(content))
+    }
+}
+
+/// Fetches pages from a map of page references to their contents.
+///
+/// Keys are formatted as in the include block, e.g. `page` or `:site:page`.
+/// Pages which are absent or `null` are considered missing.
+#[derive(Debug)]
+struct MapIncluder {
+    pages: HashMap<String, Option<String>>,
+}
+
+impl<'t> Includer<'t> for MapIncluder {
+    type Error = JsValue;
+
+    fn include_pages(
+        &mut self,
+        includes: &[IncludeRef<'t>],
+    ) -> Result<Vec<FetchedPage<'t>>, JsValue> {
+        let pages = includes
+            .iter()
+            .map(|include| {
+                let page_ref = include.page_ref().clone();
+                let content = self
+                    .pages
+                    .get(&page_ref.to_string())
+                    .cloned()
+                    .flatten()
+                    .map(Cow::Owned);
+
+                FetchedPage { page_ref, content }
+            })
+            .collect();
+
+        Ok(pages)
+    }
+
+    fn no_such_include(&mut self, page_ref: &PageRef) -> Result<Cow<'t, str>, JsValue> {
+        Ok(Cow::Owned(missing_page_notice(page_ref)))
+    }
+}
+
+/// Includes every page as empty, to find which pages are requested.
+#[derive(Debug)]
+struct PlaceholderIncluder;
+
+impl<'t> Includer<'t> for PlaceholderIncluder {
+    type Error = JsValue;
+
+    fn include_pages(
+        &mut self,
+        includes: &[IncludeRef<'t>],
+    ) -> Result<Vec<FetchedPage<'t>>, JsValue> {
+        let pages = includes
+            .iter()
+            .map(|include| FetchedPage {
+                page_ref: include.page_ref().clone(),
+                content: Some(Cow::Borrowed("")),
+            })
+            .collect();
+
+        Ok(pages)
+    }
+
+    fn no_such_include(&mut self, _page_ref: &PageRef) -> Result<Cow<'t, str>, JsValue> {
+        Ok(Cow::Borrowed(""))
+    }
+}
+
+// Exported functions
+
+#[wasm_bindgen]
+pub fn include(
+    text: String,
+    settings: WikitextSettings,
+    includer: &JsIncluder,
+) -> Result<IncludeOutcome, JsValue> {
+    let includer = CallbackIncluder { inner: includer };
+    run_include(&text, &settings, includer)
+}
+
+#[wasm_bindgen]
+pub fn include_from_map(
+    text: String,
+    settings: WikitextSettings,
+    pages: JsValue,
+) -> Result<IncludeOutcome, JsValue> {
+    let includer = MapIncluder {
+        pages: js_to_rust!(pages)?,
+    };
+
+    run_include(&text, &settings, includer)
+}
+
+/// Lists the pages directly included by the given wikitext.
+///
+/// Only one level of includes is found, since the contents of the listed
+/// pages are not known here. To handle nested includes, call this again on
+/// each fetched page and fetch any new references, until none remain.
+#[wasm_bindgen]
+pub fn include_refs(
+    text: String,
+    settings: WikitextSettings,
+) -> Result<JsValue, JsValue> {
+    let (_, pages) =
+        crate::include(&text, settings.get(), PlaceholderIncluder, mismatched_pages)?;
+
+    rust_to_js!(pages)
+}
+
+// Utility functions
+
+fn run_include<'t, I>(
+    text: &'t str,
+    settings: &WikitextSettings,
+    includer: I,
+) -> Result<IncludeOutcome, JsValue>
+where
+    I: Includer<'t, Error = JsValue>,
+{
+    let result = crate::include(text, settings.get(), includer, mismatched_pages)?;
+
+    Ok(IncludeOutcome {
+        inner: Arc::new(result),
+    })
+}
+
+fn mismatched_pages() -> JsValue {
+    JsValue::from_str("Includer returned pages not matching the request")
+}
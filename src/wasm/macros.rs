@@ -25,9 +25,17 @@ macro_rules! js_to_rust {
     }};
 }
 
+// Maps become plain objects and `None` becomes `null`,
+// matching the JSON-style TypeScript declarations.
 macro_rules! rust_to_js {
     ($object:expr) => {{
         use crate::wasm::error::error_to_js;
-        serde_wasm_bindgen::to_value(&$object).map_err(error_to_js)
+        use serde::Serialize;
+
+        let serializer = serde_wasm_bindgen::Serializer::new()
+            .serialize_maps_as_objects(true)
+            .serialize_missing_as_null(true);
+
+        $object.serialize(&serializer).map_err(error_to_js)
     }};
 }
@@ -22,6 +22,7 @@
 mod macros;
 
 mod error;
+mod includes;
 mod misc;
 mod page_info;
 mod parsing;
@@ -35,6 +36,9 @@ mod prelude {
     pub use wasm_bindgen::prelude::*;
 }
 
+pub use self::includes::{
+    IncludeOutcome, JsIncluder, include, include_from_map, include_refs,
+};
 pub use self::misc::version;
 pub use self::parsing::{ParseOutcome, SyntaxTree, parse};
 pub use self::preproc::preprocess;
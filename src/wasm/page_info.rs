@@ -91,4 +91,11 @@ impl PageInfo {
     pub fn language(&self) -> String {
         self.inner.language.to_string()
     }
+
+    #[wasm_bindgen(getter)]
+    pub fn render_timestamp(&self) -> Option<f64> {
+        self.inner
+            .render_timestamp
+            .map(|timestamp| timestamp as f64)
+    }
 }
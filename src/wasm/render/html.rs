@@ -60,6 +60,26 @@ impl HtmlOutput {
     pub fn backlinks(&self) -> Result<JsValue, JsValue> {
         rust_to_js!(self.inner.backlinks)
     }
+
+    #[wasm_bindgen]
+    pub fn html_blocks(&self) -> Result<JsValue, JsValue> {
+        rust_to_js!(self.inner.html_blocks)
+    }
+
+    #[wasm_bindgen]
+    pub fn code_blocks(&self) -> Result<JsValue, JsValue> {
+        rust_to_js!(self.inner.code_blocks)
+    }
+
+    #[wasm_bindgen]
+    pub fn outline(&self) -> Result<JsValue, JsValue> {
+        rust_to_js!(self.inner.outline)
+    }
+
+    #[wasm_bindgen]
+    pub fn warnings(&self) -> Result<JsValue, JsValue> {
+        rust_to_js!(self.inner.warnings)
+    }
 }
 
 // Function exports